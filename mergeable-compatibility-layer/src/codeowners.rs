@@ -0,0 +1,208 @@
+use std::fmt::{Display, Formatter};
+
+use regex::Regex;
+
+/// Where GitHub looks for the CODEOWNERS file, the first one found is used.
+pub const CODEOWNERS_PATHS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// An owner of paths in a CODEOWNERS file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Owner {
+    /// A login, written as `@login`.
+    User(String),
+    /// A team by `org/team_slug`, written as `@org/team_slug`.
+    Team(String),
+    /// An email address, which can't be resolved to a login without the user's cooperation.
+    Email(String),
+}
+
+impl Owner {
+    fn parse(token: &str) -> Option<Self> {
+        if let Some(handle) = token.strip_prefix('@') {
+            return match handle.split_once('/') {
+                Some((org, team)) if is_name(org) && is_name(team) => {
+                    Some(Owner::Team(handle.to_owned()))
+                }
+                None if is_name(handle) => Some(Owner::User(handle.to_owned())),
+                _ => None,
+            };
+        }
+        match token.split_once('@') {
+            Some((user, domain)) if !user.is_empty() && domain.contains('.') => {
+                Some(Owner::Email(token.to_owned()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Logins, organizations and team slugs consist of alphanumerics, hyphens and underscores.
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+}
+
+impl Display for Owner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Owner::User(handle) | Owner::Team(handle) => write!(f, "@{handle}"),
+            Owner::Email(email) => f.write_str(email),
+        }
+    }
+}
+
+/// The rules of a CODEOWNERS file, later rules take precedence.
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<OwnerRule>,
+}
+
+#[derive(Debug, Clone)]
+struct OwnerRule {
+    pattern: Regex,
+    /// Empty if the matched paths have no owners.
+    owners: Vec<Owner>,
+}
+
+impl CodeOwners {
+    /// Parses a CODEOWNERS file, like GitHub invalid lines are skipped.
+    pub fn parse(content: &str) -> Self {
+        let rules = content.lines().filter_map(OwnerRule::parse).collect();
+        Self { rules }
+    }
+
+    /// The owners of a path relative to the repository root, the last matching rule wins.
+    pub fn owners_of(&self, path: &str) -> &[Owner] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.pattern.is_match(path))
+            .map_or(&[], |rule| &rule.owners)
+    }
+
+    /// The owners of any of the paths, e.g. the files a pull request changes, in order of
+    /// appearance.
+    pub fn owners_for<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<Owner> {
+        let mut owners: Vec<Owner> = vec![];
+        for path in paths {
+            for owner in self.owners_of(path) {
+                if !owners.contains(owner) {
+                    owners.push(owner.clone());
+                }
+            }
+        }
+        owners
+    }
+}
+
+impl OwnerRule {
+    fn parse(line: &str) -> Option<Self> {
+        let mut tokens = tokens(line).into_iter();
+        let pattern = compile(&tokens.next()?)?;
+        let owners = tokens
+            .map(|token| Owner::parse(&token))
+            .collect::<Option<_>>()?;
+        Some(Self { pattern, owners })
+    }
+}
+
+/// Splits a line at unescaped whitespace and drops the comment, `\#` and `\ ` are literal.
+fn tokens(line: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut chars = line.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => match chars.next() {
+                Some(escaped @ ('#' | ' ')) => token.push(escaped),
+                Some(other) => {
+                    token.push('\\');
+                    token.push(other);
+                }
+                None => token.push('\\'),
+            },
+            '#' if token.is_empty() => break,
+            char if char.is_whitespace() => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            char => token.push(char),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Translates a pattern to a regex matching the paths of the files it owns, following
+/// GitHub's gitignore-like semantics.
+///
+/// Negations and character ranges aren't supported by GitHub, patterns using them are invalid.
+fn compile(pattern: &str) -> Option<Regex> {
+    if pattern.starts_with('!') || pattern.contains('[') || pattern.contains(']') {
+        return None;
+    }
+    let (anchored, pattern) = match pattern.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    let (directory, pattern) = match pattern.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    if pattern.is_empty() {
+        return None;
+    }
+    // a slash anywhere but at the end anchors the pattern to the root, like in gitignore
+    let anchored = anchored || pattern.contains('/');
+    let segments: Vec<&str> = pattern.split('/').collect();
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let last = segments.len() - 1;
+    for (index, segment) in segments.iter().enumerate() {
+        match *segment {
+            "**" if index == last => regex.push_str(".*"),
+            // matches zero or more directories
+            "**" => regex.push_str("(?:.*/)?"),
+            segment => {
+                regex.push_str(&translate(segment));
+                if index < last {
+                    regex.push('/');
+                }
+            }
+        }
+    }
+    // `docs/*` owns the files in `docs`, not those in its subdirectories
+    let files_only = segments.len() > 1 && segments[last] == "*";
+    if directory {
+        regex.push_str("/.*");
+    } else if !files_only {
+        // a pattern matching a directory owns everything within it
+        regex.push_str("(?:/.*)?");
+    }
+    regex.push('$');
+    Regex::new(&regex).ok()
+}
+
+fn translate(segment: &str) -> String {
+    let mut translated = String::new();
+    let mut literal = String::new();
+    for char in segment.chars() {
+        let wildcard = match char {
+            '*' => "[^/]*",
+            '?' => "[^/]",
+            char => {
+                literal.push(char);
+                continue;
+            }
+        };
+        translated.push_str(&regex::escape(&std::mem::take(&mut literal)));
+        translated.push_str(wildcard);
+    }
+    translated.push_str(&regex::escape(&literal));
+    translated
+}
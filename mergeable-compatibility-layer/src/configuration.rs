@@ -1,34 +1,82 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
 pub mod actions;
 pub mod basics;
+pub mod diagnostics;
 pub mod error;
 pub mod fail;
 pub mod filter;
 pub mod options;
+pub mod parse;
 pub mod pass;
 pub mod validate;
+pub mod when;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Configuration {
-    version: u32,
-    mergeable: Vec<Rule>,
+    pub(crate) version: u32,
+    pub(crate) mergeable: Vec<Rule>,
+}
+
+impl Configuration {
+    pub fn rules(&self) -> &[Rule] {
+        &self.mergeable
+    }
+
+    /// The rules whose `when` clause matches the event, in configuration order.
+    pub fn rules_triggered_by<'a>(
+        &'a self,
+        event: &'a str,
+        action: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a Rule> {
+        self.mergeable
+            .iter()
+            .filter(move |rule| rule.is_triggered_by(event, action))
+    }
+}
+
+impl FromStr for Configuration {
+    type Err = parse::ParseError;
+
+    /// Parses the content of a `.github/mergeable.yml` file.
+    fn from_str(yaml: &str) -> Result<Self, Self::Err> {
+        parse::parse(yaml)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Rule {
     #[serde(skip_serializing_if = "Option::is_none")]
-    when: Option<String>,
+    pub(crate) when: Option<when::When>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<String>,
+    pub(crate) name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    filter: Option<Vec<filter::Filter>>,
+    pub(crate) filter: Option<Vec<filter::Filter>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    validate: Option<Vec<validate::Validator>>,
+    pub(crate) validate: Option<Vec<validate::Validator>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pass: Option<Vec<pass::Pass>>,
+    pub(crate) pass: Option<Vec<pass::Pass>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    fail: Option<Vec<fail::Fail>>,
+    pub(crate) fail: Option<Vec<fail::Fail>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<Vec<error::Error>>,
+    pub(crate) error: Option<Vec<error::Error>>,
+}
+
+impl Rule {
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn when(&self) -> Option<&when::When> {
+        self.when.as_ref()
+    }
+
+    /// Rules without a `when` clause are never triggered.
+    pub fn is_triggered_by(&self, event: &str, action: Option<&str>) -> bool {
+        self.when
+            .as_ref()
+            .is_some_and(|when| when.matches(event, action))
+    }
 }
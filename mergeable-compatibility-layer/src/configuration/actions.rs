@@ -1,13 +1,16 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
-use super::basics::OneOrMany;
+use super::{
+    basics::OneOrMany,
+    parse::{deserialize_tagged, Tagged},
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct AssignAction {
     #[serde(rename = "assignees")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    assignees: Option<OneOrMany<String>>,
+    pub(crate) assignees: Option<OneOrMany<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,10 +18,10 @@ pub struct AssignAction {
 pub struct CheckAction {
     #[serde(rename = "status")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    status: Option<String>,
+    pub(crate) status: Option<String>,
     #[serde(rename = "payload")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    payload: Option<Payload>,
+    pub(crate) payload: Option<Payload>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,28 +29,28 @@ pub struct CheckAction {
 pub struct Payload {
     #[serde(rename = "title")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    title: Option<String>,
+    pub(crate) title: Option<String>,
     #[serde(rename = "summary")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    summary: Option<String>,
+    pub(crate) summary: Option<String>,
     #[serde(rename = "text")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    text: Option<String>,
+    pub(crate) text: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct CloseAction;
+pub struct CloseAction {}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct CommentAction {
     #[serde(rename = "payload")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    payload: Option<CommentPayload>,
+    pub(crate) payload: Option<CommentPayload>,
     #[serde(rename = "leave_old_comment")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    leave_old_comment: Option<bool>,
+    pub(crate) leave_old_comment: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,7 +58,7 @@ pub struct CommentAction {
 pub struct CommentPayload {
     #[serde(rename = "body")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    body: Option<String>,
+    pub(crate) body: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,13 +66,13 @@ pub struct CommentPayload {
 pub struct MergeAction {
     #[serde(rename = "merge_method")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    merge_method: Option<String>,
+    pub(crate) merge_method: Option<String>,
     #[serde(rename = "commit_title")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    commit_title: Option<String>,
+    pub(crate) commit_title: Option<String>,
     #[serde(rename = "commit_message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    commit_message: Option<String>,
+    pub(crate) commit_message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -77,13 +80,13 @@ pub struct MergeAction {
 pub struct LabelsAction {
     #[serde(rename = "add")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    add: Option<OneOrMany<String>>,
+    pub(crate) add: Option<OneOrMany<String>>,
     #[serde(rename = "delete")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    delete: Option<OneOrMany<String>>,
+    pub(crate) delete: Option<OneOrMany<String>>,
     #[serde(rename = "replace")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    replace: Option<OneOrMany<String>>,
+    pub(crate) replace: Option<OneOrMany<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -91,15 +94,14 @@ pub struct LabelsAction {
 pub struct RequestReviewAction {
     #[serde(rename = "reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    reviewers: Option<OneOrMany<String>>,
+    pub(crate) reviewers: Option<OneOrMany<String>>,
     #[serde(rename = "teams")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    teams: Option<OneOrMany<String>>,
+    pub(crate) teams: Option<OneOrMany<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(tag = "do")]
-#[serde(deny_unknown_fields)]
 pub enum Action {
     /// Supported Events 'pull_request.*', 'issues.*'
     #[serde(rename = "assign")]
@@ -123,3 +125,180 @@ pub enum Action {
     #[serde(rename = "request_review")]
     RequestReview(RequestReviewAction),
 }
+
+impl Tagged for Action {
+    const KINDS: &'static [&'static str] = &[
+        "assign",
+        "checks",
+        "close",
+        "comment",
+        "merge",
+        "labels",
+        "request_review",
+    ];
+
+    fn deserialize_kind<'de, D: Deserializer<'de>>(kind: &str, rest: D) -> Result<Self, D::Error> {
+        Ok(match kind {
+            "assign" => Action::Assign(Deserialize::deserialize(rest)?),
+            "checks" => Action::Checks(Deserialize::deserialize(rest)?),
+            "close" => Action::Close(Deserialize::deserialize(rest)?),
+            "comment" => Action::Comment(Deserialize::deserialize(rest)?),
+            "merge" => Action::Merge(Deserialize::deserialize(rest)?),
+            "labels" => Action::Labels(Deserialize::deserialize(rest)?),
+            "request_review" => Action::RequestReview(Deserialize::deserialize(rest)?),
+            _ => return Err(de::Error::unknown_variant(kind, Self::KINDS)),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_tagged(deserializer)
+    }
+}
+
+impl Action {
+    /// The `do:` name of the action.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Action::Assign(_) => "assign",
+            Action::Checks(_) => "checks",
+            Action::Close(_) => "close",
+            Action::Comment(_) => "comment",
+            Action::Merge(_) => "merge",
+            Action::Labels(_) => "labels",
+            Action::RequestReview(_) => "request_review",
+        }
+    }
+
+    /// The events the action can be applied for.
+    pub fn supported_events(&self) -> &'static [&'static str] {
+        match self {
+            Action::Assign(_) => &["pull_request.*", "issues.*"],
+            Action::Checks(_) => &[
+                "pull_request.assigned",
+                "pull_request.auto_merge_disabled",
+                "pull_request.auto_merge_enabled",
+                "pull_request.converted_to_draft",
+                "pull_request.demilestoned",
+                "pull_request.dequeued",
+                "pull_request.edited",
+                "pull_request.enqueued",
+                "pull_request.labeled",
+                "pull_request.locked",
+                "pull_request.milestoned",
+                "pull_request.opened",
+                "pull_request.push_synchronize",
+                "pull_request.ready_for_review",
+                "pull_request.reopened",
+                "pull_request.review_request_removed",
+                "pull_request.review_requested",
+                "pull_request.synchronize",
+                "pull_request.unassigned",
+                "pull_request.unlabeled",
+                "pull_request.unlocked",
+                "pull_request_review.dismissed",
+                "pull_request_review.edited",
+                "pull_request_review.submitted",
+            ],
+            Action::Close(_) | Action::Comment(_) | Action::Labels(_) => {
+                &["schedule.repository", "pull_request.*", "issues.*"]
+            }
+            Action::Merge(_) => &[
+                "pull_request.*",
+                "pull_request_review.*",
+                "status.*",
+                "check_suite.*",
+            ],
+            Action::RequestReview(_) => &["pull_request.*"],
+        }
+    }
+}
+
+fn strings(values: &Option<OneOrMany<String>>) -> impl Iterator<Item = &str> {
+    values.iter().flat_map(OneOrMany::iter).map(String::as_str)
+}
+
+impl AssignAction {
+    pub fn assignees(&self) -> impl Iterator<Item = &str> {
+        strings(&self.assignees)
+    }
+}
+
+impl CheckAction {
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    pub fn payload(&self) -> Option<&Payload> {
+        self.payload.as_ref()
+    }
+}
+
+impl Payload {
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+}
+
+impl CommentAction {
+    pub fn body(&self) -> Option<&str> {
+        self.payload
+            .as_ref()
+            .and_then(|payload| payload.body.as_deref())
+    }
+
+    /// Previous comments are removed unless configured otherwise.
+    pub fn leave_old_comment(&self) -> bool {
+        self.leave_old_comment.unwrap_or(false)
+    }
+}
+
+impl MergeAction {
+    pub fn merge_method(&self) -> Option<&str> {
+        self.merge_method.as_deref()
+    }
+
+    pub fn commit_title(&self) -> Option<&str> {
+        self.commit_title.as_deref()
+    }
+
+    pub fn commit_message(&self) -> Option<&str> {
+        self.commit_message.as_deref()
+    }
+}
+
+impl LabelsAction {
+    pub fn add(&self) -> impl Iterator<Item = &str> {
+        strings(&self.add)
+    }
+
+    pub fn delete(&self) -> impl Iterator<Item = &str> {
+        strings(&self.delete)
+    }
+
+    /// `None` if the labels shouldn't be replaced, an empty replacement removes all labels.
+    pub fn replace(&self) -> Option<impl Iterator<Item = &str>> {
+        self.replace
+            .as_ref()
+            .map(|replace| replace.iter().map(String::as_str))
+    }
+}
+
+impl RequestReviewAction {
+    pub fn reviewers(&self) -> impl Iterator<Item = &str> {
+        strings(&self.reviewers)
+    }
+
+    pub fn teams(&self) -> impl Iterator<Item = &str> {
+        strings(&self.teams)
+    }
+}
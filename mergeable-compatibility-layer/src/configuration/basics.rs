@@ -1,20 +1,131 @@
-use std::fmt::Debug;
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+};
 
-use crate::configuration::options::{BeginsWith, EndsWith, MustExclude, MustInclude};
-use serde::{Deserialize, Serialize};
+use crate::configuration::{
+    options::{BeginsWith, EndsWith, Max, Min, MustExclude, MustInclude},
+    validate::FilesContent,
+};
+use serde::{
+    de::{
+        self,
+        value::{MapAccessDeserializer, SeqAccessDeserializer},
+        IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    },
+    Deserialize, Deserializer, Serialize,
+};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum OneOrMany<T: Debug> {
     Many(Vec<T>),
     Single(T),
 }
 
+impl<T: Debug> OneOrMany<T> {
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        match self {
+            OneOrMany::Many(many) => many.iter(),
+            OneOrMany::Single(single) => std::slice::from_ref(single).iter(),
+        }
+    }
+}
+
+/// Deserialized by hand, an untagged enum would hide why neither variant matched.
+impl<'de, T: Debug + Deserialize<'de>> Deserialize<'de> for OneOrMany<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OneOrManyVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Debug + Deserialize<'de>> Visitor<'de> for OneOrManyVisitor<T> {
+            type Value = OneOrMany<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a value or a list of values")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(SeqAccessDeserializer::new(seq)).map(OneOrMany::Many)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                T::deserialize(MapAccessDeserializer::new(map)).map(OneOrMany::Single)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+        }
+
+        deserializer.deserialize_any(OneOrManyVisitor(PhantomData))
+    }
+}
+
+/// A clause written either as a plain string or as a map of options.
+enum ShortOrLong<L> {
+    Short(String),
+    Long(L),
+}
+
+impl<'de, L: Deserialize<'de>> ShortOrLong<L> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+        expecting: &'static str,
+    ) -> Result<Self, D::Error> {
+        struct ShortOrLongVisitor<L> {
+            expecting: &'static str,
+            long: PhantomData<L>,
+        }
+
+        impl<'de, L: Deserialize<'de>> Visitor<'de> for ShortOrLongVisitor<L> {
+            type Value = ShortOrLong<L>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(self.expecting)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(ShortOrLong::Short(value.to_owned()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                L::deserialize(MapAccessDeserializer::new(map)).map(ShortOrLong::Long)
+            }
+        }
+
+        deserializer.deserialize_any(ShortOrLongVisitor {
+            expecting,
+            long: PhantomData,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
-pub struct MessageClause(String);
+pub struct MessageClause(pub(crate) String);
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+impl MessageClause {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum MatchClause {
     Long {
@@ -27,8 +138,7 @@ pub enum MatchClause {
     Short(String),
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum RegexClause {
     Long {
@@ -44,72 +154,244 @@ pub enum RegexClause {
     Short(String),
 }
 
+impl<'de> Deserialize<'de> for MatchClause {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Long {
+            #[serde(rename = "match")]
+            match_clause: OneOrMany<String>,
+            #[serde(rename = "message")]
+            message: Option<MessageClause>,
+        }
+
+        let clause = ShortOrLong::<Long>::deserialize(
+            deserializer,
+            "a string or a map with `match` and an optional `message`",
+        )?;
+        Ok(match clause {
+            ShortOrLong::Short(short) => MatchClause::Short(short),
+            ShortOrLong::Long(Long {
+                match_clause,
+                message,
+            }) => MatchClause::Long {
+                match_clause,
+                message,
+            },
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for RegexClause {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Long {
+            #[serde(rename = "regex")]
+            regex: OneOrMany<String>,
+            #[serde(rename = "regex_flag")]
+            regex_flag: Option<String>,
+            #[serde(rename = "message")]
+            message: Option<MessageClause>,
+        }
+
+        let clause = ShortOrLong::<Long>::deserialize(
+            deserializer,
+            "a regex or a map with `regex` and optional `regex_flag` and `message`",
+        )?;
+        Ok(match clause {
+            ShortOrLong::Short(short) => RegexClause::Short(short),
+            ShortOrLong::Long(Long {
+                regex,
+                regex_flag,
+                message,
+            }) => RegexClause::Long {
+                regex,
+                regex_flag,
+                message,
+            },
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct CountClause {
     #[serde(rename = "count")]
-    count: u64,
+    pub(crate) count: u64,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TimeClause {
     #[serde(rename = "days")]
-    days: u32,
+    pub(crate) days: u32,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct BooleanClause {
     #[serde(rename = "match")]
-    match_clause: bool,
+    pub(crate) match_clause: bool,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
-pub struct ChainedAndOrIncludeExcludeClause {
+/// The tree of matchers shared by the validators and filters matching strings, e.g. `title`,
+/// `label` or `changeset`: all options of a clause have to pass, `and` and `or` nest clauses.
+///
+/// The kinds share one shape, `K` restricts the options a kind accepts.
+#[derive(Debug, Serialize)]
+#[serde(bound = "")]
+pub struct Chain<K> {
     #[serde(rename = "and")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<Self>>,
+    pub(crate) and: Option<Vec<Self>>,
     #[serde(rename = "or")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<Self>>,
+    pub(crate) or: Option<Vec<Self>>,
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
-pub struct ChainedAndOrIncludeExcludeClauseBeginsEnds {
-    #[serde(rename = "and")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<Self>>,
-    #[serde(rename = "or")]
+    pub(crate) exclude: Option<MustExclude>,
+    #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<Self>>,
-    #[serde(rename = "must_include")]
+    pub(crate) begins_with: Option<BeginsWith>,
+    #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
-    #[serde(rename = "must_exclude")]
+    pub(crate) ends_with: Option<EndsWith>,
+    #[serde(rename = "min")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
-    #[serde(rename = "begins_with")]
+    pub(crate) min: Option<Min>,
+    #[serde(rename = "max")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
-    #[serde(rename = "ends_with")]
+    pub(crate) max: Option<Max>,
+    #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) files: Option<FilesContent>,
+    #[serde(skip)]
+    kind: PhantomData<K>,
+}
+
+/// The options a kind of [`Chain`] accepts.
+pub trait ChainKind {
+    const OPTIONS: &'static [&'static str];
+}
+
+/// `and`, `or`, `must_include` and `must_exclude`.
+#[derive(Debug)]
+pub struct IncludeExclude;
+
+impl ChainKind for IncludeExclude {
+    const OPTIONS: &'static [&'static str] = &["and", "or", "must_include", "must_exclude"];
+}
+
+/// Additionally `begins_with` and `ends_with`.
+#[derive(Debug)]
+pub struct BeginsEnds;
+
+impl ChainKind for BeginsEnds {
+    const OPTIONS: &'static [&'static str] = &[
+        "and",
+        "or",
+        "must_include",
+        "must_exclude",
+        "begins_with",
+        "ends_with",
+    ];
+}
+
+/// Additionally `min`, `max` and the `files` the clause applies to.
+#[derive(Debug)]
+pub struct Changeset;
+
+impl ChainKind for Changeset {
+    const OPTIONS: &'static [&'static str] = &[
+        "and",
+        "or",
+        "must_include",
+        "must_exclude",
+        "begins_with",
+        "ends_with",
+        "min",
+        "max",
+        "files",
+    ];
+}
+
+pub type ChainedAndOrIncludeExcludeClause = Chain<IncludeExclude>;
+
+pub type ChainedAndOrIncludeExcludeClauseBeginsEnds = Chain<BeginsEnds>;
+
+impl<'de, K: ChainKind> Deserialize<'de> for Chain<K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ChainVisitor<K>(PhantomData<K>);
+
+        impl<'de, K: ChainKind> Visitor<'de> for ChainVisitor<K> {
+            type Value = Chain<K>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of matchers")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut chain = Chain {
+                    and: None,
+                    or: None,
+                    include: None,
+                    exclude: None,
+                    begins_with: None,
+                    ends_with: None,
+                    min: None,
+                    max: None,
+                    files: None,
+                    kind: PhantomData,
+                };
+                while let Some(key) = map.next_key::<String>()? {
+                    if !K::OPTIONS.contains(&key.as_str()) {
+                        return Err(de::Error::unknown_field(&key, K::OPTIONS));
+                    }
+                    match key.as_str() {
+                        "and" => next_value(&mut chain.and, "and", &mut map)?,
+                        "or" => next_value(&mut chain.or, "or", &mut map)?,
+                        "must_include" => next_value(&mut chain.include, "must_include", &mut map)?,
+                        "must_exclude" => next_value(&mut chain.exclude, "must_exclude", &mut map)?,
+                        "begins_with" => {
+                            next_value(&mut chain.begins_with, "begins_with", &mut map)?
+                        }
+                        "ends_with" => next_value(&mut chain.ends_with, "ends_with", &mut map)?,
+                        "min" => next_value(&mut chain.min, "min", &mut map)?,
+                        "max" => next_value(&mut chain.max, "max", &mut map)?,
+                        "files" => next_value(&mut chain.files, "files", &mut map)?,
+                        _ => unreachable!("every option of a kind is handled"),
+                    }
+                }
+                Ok(chain)
+            }
+        }
+
+        // as a struct, flattened chains consume their options from the map of the validator
+        deserializer.deserialize_struct("Chain", K::OPTIONS, ChainVisitor(PhantomData))
+    }
+}
+
+fn next_value<'de, T: Deserialize<'de>, A: MapAccess<'de>>(
+    option: &mut Option<T>,
+    key: &'static str,
+    map: &mut A,
+) -> Result<(), A::Error> {
+    if option.is_some() {
+        return Err(de::Error::duplicate_field(key));
+    }
+    *option = Some(map.next_value()?);
+    Ok(())
 }
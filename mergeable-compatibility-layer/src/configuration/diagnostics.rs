@@ -0,0 +1,485 @@
+use std::fmt::{Display, Formatter};
+
+use super::{
+    actions::Action,
+    basics::{Chain, OneOrMany, RegexClause},
+    filter::{Filter, FilterAnd, FilterNot, FilterOr, TheRepository},
+    options::{Jira, MustExclude, MustInclude, Required},
+    parse::{self, Location, Segment},
+    validate::{
+        TheBaseRef, TheCommit, TheDescription, TheHeadRef, TheLabel, TheMilestone, TheTitle,
+        Validator, ValidatorAnd, ValidatorNot, ValidatorOr,
+    },
+    when::EventMatcher,
+    Configuration, Rule,
+};
+
+/// The configuration versions this implementation understands.
+const SUPPORTED_VERSIONS: &[u32] = &[2];
+
+const MERGE_METHODS: &[&str] = &["merge", "squash", "rebase"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The configuration works, but likely not as intended.
+    Warning,
+    /// Part of the configuration can't work.
+    Error,
+}
+
+/// A problem with a configuration that deserialized fine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    rule_index: Option<usize>,
+    rule_name: Option<String>,
+    kind: Option<String>,
+    key: Option<String>,
+    path: Vec<Segment>,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The index of the offending rule within `mergeable`.
+    pub fn rule_index(&self) -> Option<usize> {
+        self.rule_index
+    }
+
+    pub fn rule_name(&self) -> Option<&str> {
+        self.rule_name.as_deref()
+    }
+
+    /// The `do` of the validator, filter or action the problem is in.
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
+    /// The key holding the offending value.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Where the diagnostic applies within the configuration file it was parsed from.
+    pub fn locate(&self, yaml: &str) -> Option<Location> {
+        parse::locate(yaml, &self.path)
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        let mut context = vec![severity.to_owned()];
+        match (self.rule_index, &self.rule_name) {
+            (Some(index), Some(name)) => context.push(format!("rule {index} `{name}`")),
+            (Some(index), None) => context.push(format!("rule {index}")),
+            _ => {}
+        }
+        if let Some(kind) = &self.kind {
+            context.push(format!("do: {kind}"));
+        }
+        if let Some(key) = &self.key {
+            context.push(format!("key `{key}`"));
+        }
+        write!(f, "{}: {}", context.join(", "), self.message)
+    }
+}
+
+impl Configuration {
+    /// Checks what deserialization can't, e.g. regexes and the events validators are used with.
+    ///
+    /// Diagnostics are returned in configuration order.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Diagnostics::default();
+        if !SUPPORTED_VERSIONS.contains(&self.version) {
+            diagnostics.push(
+                Severity::Error,
+                None,
+                &[Segment::key("version")],
+                format!("unsupported version {}, expected 2", self.version),
+            );
+        }
+        for (index, rule) in self.mergeable.iter().enumerate() {
+            diagnostics.rule_index = Some(index);
+            diagnostics.rule_name = rule.name.clone();
+            diagnostics.path = vec![Segment::key("mergeable"), Segment::Index(index)];
+            diagnostics.rule(rule);
+        }
+        diagnostics.found
+    }
+}
+
+/// Collects diagnostics attributed to the rule and element being checked.
+#[derive(Default)]
+struct Diagnostics {
+    found: Vec<Diagnostic>,
+    rule_index: Option<usize>,
+    rule_name: Option<String>,
+    /// The path of the element being checked within the document.
+    path: Vec<Segment>,
+}
+
+impl Diagnostics {
+    /// Records a diagnostic about the value at `at` relative to the element being checked.
+    fn push(
+        &mut self,
+        severity: Severity,
+        kind: Option<&str>,
+        at: &[Segment],
+        message: impl Into<String>,
+    ) {
+        let key = at.iter().rev().find_map(|segment| match segment {
+            Segment::Key(key) => Some(key.clone()),
+            Segment::Index(_) => None,
+        });
+        let diagnostic = Diagnostic {
+            severity,
+            message: message.into(),
+            rule_index: self.rule_index,
+            rule_name: self.rule_name.clone(),
+            kind: kind.map(str::to_owned),
+            key,
+            path: self.path.iter().chain(at).cloned().collect(),
+        };
+        self.found.push(diagnostic);
+    }
+
+    /// Checks the elements of a list like `validate` in turn.
+    fn each<T>(&mut self, key: &str, elements: &[T], mut check: impl FnMut(&mut Self, &T)) {
+        self.path.push(Segment::key(key));
+        for (index, element) in elements.iter().enumerate() {
+            self.path.push(Segment::Index(index));
+            check(self, element);
+            self.path.pop();
+        }
+        self.path.pop();
+    }
+
+    fn rule(&mut self, rule: &Rule) {
+        let matchers = match &rule.when {
+            Some(when) => when.matchers(),
+            None => {
+                self.push(
+                    Severity::Warning,
+                    None,
+                    &[Segment::key("when")],
+                    "the rule has no `when` and is never triggered",
+                );
+                &[]
+            }
+        };
+        if let Some(filters) = &rule.filter {
+            self.each("filter", filters, |this, filter| {
+                this.filter(filter, matchers)
+            });
+        }
+        if let Some(validators) = &rule.validate {
+            self.each("validate", validators, |this, validator| {
+                this.validator(validator, matchers)
+            });
+        }
+        if let Some(pass) = &rule.pass {
+            self.each("pass", pass, |this, pass| this.action(&pass.0, matchers));
+        }
+        if let Some(fail) = &rule.fail {
+            self.each("fail", fail, |this, fail| this.action(&fail.0, matchers));
+        }
+        if let Some(error) = &rule.error {
+            self.each("error", error, |this, error| {
+                this.action(&error.0, matchers)
+            });
+        }
+    }
+
+    fn filter(&mut self, filter: &Filter, matchers: &[EventMatcher]) {
+        match filter {
+            Filter::And(FilterAnd(stack))
+            | Filter::Or(FilterOr(stack))
+            | Filter::Not(FilterNot(stack)) => {
+                self.each("filter", &stack.filter, |this, filter| {
+                    this.filter(filter, matchers)
+                });
+            }
+            _ => {
+                if let Some(supported) = filter.supported_events() {
+                    self.events(filter.kind(), supported, matchers);
+                }
+                self.regexes(filter.kind(), Regexes::of_filter(filter));
+                if let Filter::Payload(_) = filter {
+                    self.push(
+                        Severity::Error,
+                        Some(filter.kind()),
+                        &[Segment::key("do")],
+                        "the `payload` filter isn't supported, the rule never applies",
+                    );
+                }
+            }
+        }
+    }
+
+    fn validator(&mut self, validator: &Validator, matchers: &[EventMatcher]) {
+        match validator {
+            Validator::And(ValidatorAnd(stack))
+            | Validator::Or(ValidatorOr(stack))
+            | Validator::Not(ValidatorNot(stack)) => {
+                self.each("validate", &stack.validate, |this, validator| {
+                    this.validator(validator, matchers)
+                });
+            }
+            _ => {
+                if let Some(supported) = validator.supported_events() {
+                    self.events(validator.kind(), supported, matchers);
+                }
+                self.regexes(validator.kind(), Regexes::of_validator(validator));
+                if let Validator::Approvals(approvals) = validator {
+                    if let Some(required) = &approvals.required {
+                        self.required(required);
+                    }
+                }
+                if let Validator::BaseRef(base_ref) = validator {
+                    if base_ref.media_type.is_some() {
+                        self.push(
+                            Severity::Warning,
+                            Some(validator.kind()),
+                            &[Segment::key("mediaType")],
+                            "`mediaType` has no effect, GitHub graduated the API previews",
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn action(&mut self, action: &Action, matchers: &[EventMatcher]) {
+        self.events(action.kind(), action.supported_events(), matchers);
+        if let Action::Merge(merge) = action {
+            if let Some(method) = merge.merge_method() {
+                if !MERGE_METHODS.contains(&method) {
+                    self.push(
+                        Severity::Error,
+                        Some(action.kind()),
+                        &[Segment::key("merge_method")],
+                        format!(
+                            "unknown merge method `{method}`, expected one of {}",
+                            quoted(MERGE_METHODS)
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Warns about every `when` entry the element doesn't support.
+    fn events(&mut self, kind: &str, supported: &[&str], matchers: &[EventMatcher]) {
+        let supported: Vec<EventMatcher> = supported
+            .iter()
+            .map(|event| event.parse().expect("supported events are valid"))
+            .collect();
+        for matcher in matchers {
+            if !supported.iter().any(|other| matcher.is_covered_by(other)) {
+                self.push(
+                    Severity::Warning,
+                    Some(kind),
+                    &[Segment::key("do")],
+                    format!(
+                        "`{kind}` doesn't support `{matcher}`, only {}",
+                        quoted(supported.iter().map(ToString::to_string))
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Reports the regexes of a validator or filter which don't compile.
+    fn regexes(&mut self, kind: &str, regexes: Regexes) {
+        for (at, message) in regexes.invalid {
+            self.push(Severity::Error, Some(kind), &at, message);
+        }
+    }
+
+    fn required(&mut self, required: &Required) {
+        let reviewers = match &required.reviewers {
+            Some(OneOrMany::Many(reviewers)) if reviewers.is_empty() => {
+                self.push(
+                    Severity::Warning,
+                    Some("approvals"),
+                    &[Segment::key("required"), Segment::key("reviewers")],
+                    "`required.reviewers` is empty",
+                );
+                false
+            }
+            Some(_) => true,
+            None => false,
+        };
+        let enabled = [
+            required.owners,
+            required.assignees,
+            required.requested_reviewers,
+        ];
+        if !reviewers && !enabled.contains(&Some(true)) {
+            self.push(
+                Severity::Warning,
+                Some("approvals"),
+                &[Segment::key("required")],
+                "`required` requires no approvals, \
+                 expected `reviewers`, `owners`, `assignees` or `requested_reviewers`",
+            );
+        }
+    }
+}
+
+/// Compiles the regexes of a validator or filter, nested validators and filters are checked
+/// on their own.
+#[derive(Default)]
+struct Regexes {
+    /// The path of the clause being checked within the element.
+    at: Vec<Segment>,
+    invalid: Vec<(Vec<Segment>, String)>,
+}
+
+impl Regexes {
+    fn of_validator(validator: &Validator) -> Self {
+        let mut regexes = Regexes::default();
+        match validator {
+            Validator::Author(author) => regexes.chain(&author.author),
+            Validator::BaseRef(TheBaseRef {
+                include,
+                exclude,
+                jira,
+                media_type: _,
+            })
+            | Validator::HeadRef(TheHeadRef {
+                include,
+                exclude,
+                jira,
+            }) => {
+                regexes.include_exclude(include, exclude);
+                regexes.jira(jira);
+            }
+            Validator::ChangeSet(changeset) => regexes.chain(&changeset.changeset),
+            Validator::Commit(TheCommit { message, jira }) => {
+                if let Some(pattern) = message.as_ref().and_then(|message| message.regex.as_ref()) {
+                    // evaluated as a single pattern with the default flags
+                    let clause = RegexClause::Short(pattern.clone());
+                    regexes.within("message", |regexes| regexes.clause("regex", Some(&clause)));
+                }
+                regexes.jira(jira);
+            }
+            Validator::Contents(contents) => {
+                regexes.include_exclude(&contents.content.include, &contents.content.exclude)
+            }
+            Validator::Description(TheDescription {
+                description, jira, ..
+            }) => {
+                if let Some(description) = description {
+                    regexes.chain(description);
+                }
+                regexes.jira(jira);
+            }
+            Validator::Label(TheLabel {
+                label: chain, jira, ..
+            })
+            | Validator::Milestone(TheMilestone {
+                milestone: chain,
+                jira,
+                ..
+            })
+            | Validator::Title(TheTitle {
+                title: chain, jira, ..
+            }) => {
+                regexes.chain(chain);
+                regexes.jira(jira);
+            }
+            Validator::Project(project) => regexes.include_exclude(&project.include, &None),
+            Validator::Age(_)
+            | Validator::Approvals(_)
+            | Validator::Assignee(_)
+            | Validator::Dependent(_)
+            | Validator::Size(_)
+            | Validator::Stale(_)
+            | Validator::And(_)
+            | Validator::Or(_)
+            | Validator::Not(_) => {}
+        }
+        regexes
+    }
+
+    fn of_filter(filter: &Filter) -> Self {
+        let mut regexes = Regexes::default();
+        match filter {
+            Filter::Author(author) => regexes.chain(&author.filter),
+            Filter::Repository(TheRepository {
+                visibility: _,
+                name,
+                topics,
+            }) => {
+                for (key, chain) in [("name", name), ("topics", topics)] {
+                    if let Some(chain) = chain {
+                        regexes.within(key, |regexes| regexes.chain(chain));
+                    }
+                }
+            }
+            Filter::Payload(_) | Filter::And(_) | Filter::Or(_) | Filter::Not(_) => {}
+        }
+        regexes
+    }
+
+    /// The matchers of a chain and the chains nested in its `and` and `or`.
+    fn chain<K>(&mut self, chain: &Chain<K>) {
+        self.include_exclude(&chain.include, &chain.exclude);
+        for (key, nested) in [("and", &chain.and), ("or", &chain.or)] {
+            let Some(nested) = nested else {
+                continue;
+            };
+            self.within(key, |regexes| {
+                for (index, chain) in nested.iter().enumerate() {
+                    regexes.at.push(Segment::Index(index));
+                    regexes.chain(chain);
+                    regexes.at.pop();
+                }
+            });
+        }
+    }
+
+    fn include_exclude(&mut self, include: &Option<MustInclude>, exclude: &Option<MustExclude>) {
+        self.clause("must_include", include.as_ref().map(|include| &include.0));
+        self.clause("must_exclude", exclude.as_ref().map(|exclude| &exclude.0));
+    }
+
+    fn jira(&mut self, jira: &Option<Jira>) {
+        self.clause("jira", jira.as_ref().map(|jira| &jira.0));
+    }
+
+    fn clause(&mut self, key: &str, clause: Option<&RegexClause>) {
+        let Some(Err(outcome)) = clause.map(RegexClause::compile) else {
+            return;
+        };
+        self.within(key, |regexes| {
+            regexes.invalid.push((regexes.at.clone(), outcome.message))
+        });
+    }
+
+    fn within(&mut self, key: &str, check: impl FnOnce(&mut Self)) {
+        self.at.push(Segment::key(key));
+        check(self);
+        self.at.pop();
+    }
+}
+
+fn quoted<T: Display>(values: impl IntoIterator<Item = T>) -> String {
+    values
+        .into_iter()
+        .map(|value| format!("`{value}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -4,4 +4,4 @@ use super::actions::Action;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct Error(Action);
+pub struct Error(pub(crate) Action);
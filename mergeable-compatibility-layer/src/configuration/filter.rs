@@ -1,10 +1,12 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
-use crate::configuration::basics::{BooleanClause, ChainedAndOrIncludeExcludeClause};
+use crate::configuration::{
+    basics::{BooleanClause, ChainedAndOrIncludeExcludeClause},
+    parse::{deserialize_tagged, Tagged},
+};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(tag = "do")]
-#[serde(deny_unknown_fields)]
 pub enum Filter {
     /// supported events 'pull_request.*', 'pull_request_review.*'
     #[serde(rename = "author")]
@@ -23,32 +25,68 @@ pub enum Filter {
     Not(FilterNot),
 }
 
+impl Filter {
+    /// The events the filter can be evaluated for, `None` for the combinators which
+    /// support what their filters support.
+    pub fn supported_events(&self) -> Option<&'static [&'static str]> {
+        Some(match self {
+            Filter::Author(_) | Filter::Repository(_) => {
+                &["pull_request.*", "pull_request_review.*"]
+            }
+            Filter::Payload(_) => &["pull_request.*", "pull_request_review.*", "issues.*"],
+            Filter::And(_) | Filter::Or(_) | Filter::Not(_) => return None,
+        })
+    }
+}
+
+impl Tagged for Filter {
+    const KINDS: &'static [&'static str] = &["author", "repository", "payload", "and", "or", "not"];
+
+    fn deserialize_kind<'de, D: Deserializer<'de>>(kind: &str, rest: D) -> Result<Self, D::Error> {
+        Ok(match kind {
+            "author" => Filter::Author(Deserialize::deserialize(rest)?),
+            "repository" => Filter::Repository(Deserialize::deserialize(rest)?),
+            "payload" => Filter::Payload(Deserialize::deserialize(rest)?),
+            "and" => Filter::And(FilterAnd(Deserialize::deserialize(rest)?)),
+            "or" => Filter::Or(FilterOr(Deserialize::deserialize(rest)?)),
+            "not" => Filter::Not(FilterNot(Deserialize::deserialize(rest)?)),
+            _ => return Err(de::Error::unknown_variant(kind, Self::KINDS)),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_tagged(deserializer)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct FilterAnd(FilterStack);
+pub struct FilterAnd(pub(crate) FilterStack);
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct FilterOr(FilterStack);
+pub struct FilterOr(pub(crate) FilterStack);
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct FilterNot(FilterStack);
+pub struct FilterNot(pub(crate) FilterStack);
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-struct FilterStack {
-    filter: Vec<Filter>,
+pub(crate) struct FilterStack {
+    pub(crate) filter: Vec<Filter>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TheAuthor {
     #[serde(flatten)]
-    filter: ChainedAndOrIncludeExcludeClause,
+    pub(crate) filter: ChainedAndOrIncludeExcludeClause,
     #[serde(rename = "team")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    team: Option<String>,
+    pub(crate) team: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,13 +94,13 @@ pub struct TheAuthor {
 pub struct TheRepository {
     #[serde(rename = "visibility")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    visibility: Option<String>,
+    pub(crate) visibility: Option<String>,
     #[serde(rename = "name")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<ChainedAndOrIncludeExcludeClause>,
+    pub(crate) name: Option<ChainedAndOrIncludeExcludeClause>,
     #[serde(rename = "topics")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    topics: Option<ChainedAndOrIncludeExcludeClause>,
+    pub(crate) topics: Option<ChainedAndOrIncludeExcludeClause>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -70,11 +108,11 @@ pub struct TheRepository {
 pub struct ThePayload {
     // TODO proper handling
     #[serde(skip_serializing_if = "Option::is_none")]
-    pull_request: Option<serde_yaml::Value>,
+    pub(crate) pull_request: Option<serde_yaml::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    review: Option<serde_yaml::Value>,
+    pub(crate) review: Option<serde_yaml::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    sender: Option<serde_yaml::Value>,
+    pub(crate) sender: Option<serde_yaml::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -10,31 +10,31 @@ use crate::configuration::basics::{
 ///   'payload'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct CommonBoolean(BooleanClause);
+pub struct CommonBoolean(pub(crate) BooleanClause);
 
 /// Supported Validators:
 ///   'changeset', 'content', 'description', 'label', 'milestone', 'title'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct BeginsWith(MatchClause);
+pub struct BeginsWith(pub(crate) MatchClause);
 
 /// Supported Validators:
 ///   'changeset', 'content', 'description', 'label', 'milestone', 'title'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct EndsWith(MatchClause);
+pub struct EndsWith(pub(crate) MatchClause);
 
 /// Supported Validators:
 ///   'baseRef', 'headRef', 'changeset', 'commit', 'content', 'description', 'label', 'milestone', 'project', 'title'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct MustInclude(RegexClause);
+pub struct MustInclude(pub(crate) RegexClause);
 
 /// Supported Validators:
 ///   'baseRef', 'headRef', 'changeset', 'content', 'description', 'label', 'milestone', 'title'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct MustExclude(RegexClause);
+pub struct MustExclude(pub(crate) RegexClause);
 
 /// Supported Validators:
 ///   'changeset', 'description', 'label', 'milestone', 'title'
@@ -42,10 +42,10 @@ pub struct MustExclude(RegexClause);
 #[serde(deny_unknown_fields)]
 pub struct NoEmpty {
     #[serde(rename = "enabled")]
-    enabled: bool,
+    pub(crate) enabled: bool,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
 /// Supported Validators:
@@ -55,35 +55,35 @@ pub struct NoEmpty {
 pub struct Required {
     #[serde(rename = "reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    reviewers: Option<OneOrMany<String>>,
+    pub(crate) reviewers: Option<OneOrMany<String>>,
     #[serde(rename = "owners")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    owners: Option<bool>,
+    pub(crate) owners: Option<bool>,
     #[serde(rename = "assignees")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    assignees: Option<bool>,
+    pub(crate) assignees: Option<bool>,
     #[serde(rename = "requested_reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    requested_reviewers: Option<bool>,
+    pub(crate) requested_reviewers: Option<bool>,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
 /// Supported Validators:
 ///   'approvals', 'assignee', 'changeset', 'label', 'size'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct Min(CountClause);
+pub struct Min(pub(crate) CountClause);
 
 /// Supported Validators:
 ///   'approvals', 'assignee', 'changeset', 'label'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct Max(CountClause);
+pub struct Max(pub(crate) CountClause);
 
 /// Supported Validators:
 ///   'commit', 'description', 'headRef', 'label', 'milestone', 'title'
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct Jira(RegexClause);
+pub struct Jira(pub(crate) RegexClause);
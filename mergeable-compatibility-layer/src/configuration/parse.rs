@@ -0,0 +1,346 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+};
+
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, Visitor},
+    Deserializer,
+};
+use serde_yaml::{Mapping, Value};
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver},
+    scanner::Marker,
+};
+
+use super::Configuration;
+
+/// A 1-based position in a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Why a configuration file doesn't parse, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(Box<Details>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Details {
+    message: String,
+    location: Option<Location>,
+    rule_index: Option<usize>,
+    rule_name: Option<String>,
+    kind: Option<String>,
+    key: Option<String>,
+}
+
+impl ParseError {
+    pub fn message(&self) -> &str {
+        &self.0.message
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        self.0.location
+    }
+
+    /// The index of the offending rule within `mergeable`.
+    pub fn rule_index(&self) -> Option<usize> {
+        self.0.rule_index
+    }
+
+    pub fn rule_name(&self) -> Option<&str> {
+        self.0.rule_name.as_deref()
+    }
+
+    /// The `do` of the validator, filter or action the error is in.
+    pub fn kind(&self) -> Option<&str> {
+        self.0.kind.as_deref()
+    }
+
+    /// The key that is unknown, missing or holds an invalid value.
+    pub fn key(&self) -> Option<&str> {
+        self.0.key.as_deref()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let details = &self.0;
+        let mut context = vec![];
+        if let Some(Location { line, column }) = details.location {
+            context.push(format!("line {line} column {column}"));
+        }
+        match (details.rule_index, &details.rule_name) {
+            (Some(index), Some(name)) => context.push(format!("rule {index} `{name}`")),
+            (Some(index), None) => context.push(format!("rule {index}")),
+            _ => {}
+        }
+        if let Some(kind) = &details.kind {
+            context.push(format!("do: {kind}"));
+        }
+        if let Some(key) = &details.key {
+            context.push(format!("key `{key}`"));
+        }
+        if !context.is_empty() {
+            write!(f, "{}: ", context.join(", "))?;
+        }
+        f.write_str(&details.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub(crate) fn parse(yaml: &str) -> Result<Configuration, ParseError> {
+    serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(yaml))
+        .map_err(|error| ParseError::new(yaml, error))
+}
+
+impl ParseError {
+    fn new(yaml: &str, error: serde_path_to_error::Error<serde_yaml::Error>) -> Self {
+        let path = error.path().clone();
+        let error = error.into_inner();
+        let location = error.location().map(|location| Location {
+            line: location.line(),
+            column: location.column(),
+        });
+        let mut parsed = Details {
+            message: bare_message(&error, location),
+            location,
+            rule_index: None,
+            rule_name: None,
+            kind: None,
+            key: None,
+        };
+
+        // the path is attributed by walking the document, a syntax error leaves only the location
+        let Ok(document) = serde_yaml::from_str::<Value>(yaml) else {
+            return Self(Box::new(parsed));
+        };
+        let mut node = &document;
+        for (depth, segment) in path.iter().enumerate() {
+            match segment {
+                serde_path_to_error::Segment::Map { key } => {
+                    node = &node[key.as_str()];
+                    parsed.key = Some(key.clone());
+                }
+                serde_path_to_error::Segment::Seq { index } => {
+                    node = &node[*index];
+                    if depth == 1 {
+                        parsed.rule_index = Some(*index);
+                        parsed.rule_name = node["name"].as_str().map(str::to_owned);
+                    }
+                }
+                serde_path_to_error::Segment::Enum { .. }
+                | serde_path_to_error::Segment::Unknown => continue,
+            }
+            if let Some(kind) = node["do"].as_str() {
+                parsed.kind = Some(kind.to_owned());
+                parsed.key = None;
+            }
+        }
+        if parsed.key.is_none() {
+            parsed.key = quoted_field(&parsed.message).or_else(|| {
+                let unknown_kind = parsed.kind.is_some() && parsed.message.contains("variant");
+                unknown_kind.then(|| "do".to_owned())
+            });
+        }
+        Self(Box::new(parsed))
+    }
+}
+
+/// The message of a YAML error without the path and location it's displayed with.
+fn bare_message(error: &serde_yaml::Error, location: Option<Location>) -> String {
+    let mut message = error.to_string();
+    if let Some(Location { line, column }) = location {
+        let suffix = format!(" at line {line} column {column}");
+        if message.ends_with(&suffix) {
+            message.truncate(message.len() - suffix.len());
+        }
+    }
+    match message.split_once(": ") {
+        Some((path, rest)) if !path.contains(char::is_whitespace) => rest.to_owned(),
+        _ => message,
+    }
+}
+
+/// The field named by serde's unknown and missing field errors.
+fn quoted_field(message: &str) -> Option<String> {
+    ["unknown field `", "missing field `"]
+        .iter()
+        .find_map(|prefix| message.strip_prefix(prefix))
+        .and_then(|rest| rest.split_once('`'))
+        .map(|(field, _)| field.to_owned())
+}
+
+/// A step of the path to a value within a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+impl Segment {
+    pub(crate) fn key(key: &str) -> Self {
+        Self::Key(key.to_owned())
+    }
+}
+
+/// Finds the value at the path, or the closest of its parents present in the document.
+pub(crate) fn locate(yaml: &str, path: &[Segment]) -> Option<Location> {
+    let positions = Positions::of(yaml)?;
+    (0..=path.len())
+        .rev()
+        .find_map(|length| positions.0.get(&path[..length]).copied())
+}
+
+/// The position of every value of a document by its path, keys stand for their values so a
+/// path ending in a key leads to the key.
+struct Positions(HashMap<Vec<Segment>, Location>);
+
+impl Positions {
+    /// Reads the first document in one pass, `None` if it isn't valid YAML.
+    fn of(yaml: &str) -> Option<Self> {
+        let mut reader = PositionReader {
+            positions: HashMap::new(),
+            open: vec![],
+        };
+        yaml_rust2::parser::Parser::new_from_str(yaml)
+            .load(&mut reader, false)
+            .ok()?;
+        Some(Self(reader.positions))
+    }
+}
+
+struct PositionReader {
+    positions: HashMap<Vec<Segment>, Location>,
+    /// The maps and sequences the next node is nested in, innermost last.
+    open: Vec<Collection>,
+}
+
+/// A map or sequence being read, `path` is `None` within keys which aren't strings.
+struct Collection {
+    path: Option<Vec<Segment>>,
+    next: Next,
+}
+
+/// What the next node within a collection is.
+enum Next {
+    Index(usize),
+    Key,
+    /// The value of the key, `None` for keys which aren't strings.
+    Value(Option<String>),
+}
+
+impl PositionReader {
+    /// The path of the node starting at `location`, recorded unless it's the value of a key.
+    ///
+    /// `scalar` is the value of scalars, which name the value following them as keys.
+    fn node(&mut self, scalar: Option<&str>, location: Location) -> Option<Vec<Segment>> {
+        let Some(parent) = self.open.last_mut() else {
+            self.positions.insert(vec![], location);
+            return Some(vec![]);
+        };
+        let (segment, record) = match std::mem::replace(&mut parent.next, Next::Key) {
+            Next::Index(index) => {
+                parent.next = Next::Index(index + 1);
+                (Some(Segment::Index(index)), true)
+            }
+            Next::Key => {
+                parent.next = Next::Value(scalar.map(str::to_owned));
+                (scalar.map(Segment::key), true)
+            }
+            Next::Value(key) => (key.map(Segment::Key), false),
+        };
+        let path = parent.path.clone().zip(segment).map(|(mut path, segment)| {
+            path.push(segment);
+            path
+        });
+        if let (Some(path), true) = (&path, record) {
+            self.positions.entry(path.clone()).or_insert(location);
+        }
+        path
+    }
+}
+
+impl MarkedEventReceiver for PositionReader {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let location = Location {
+            line: mark.line(),
+            column: mark.col() + 1,
+        };
+        match event {
+            Event::Scalar(value, ..) => {
+                self.node(Some(&value), location);
+            }
+            Event::Alias(_) => {
+                self.node(None, location);
+            }
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                let path = self.node(None, location);
+                let next = match event {
+                    Event::MappingStart(..) => Next::Key,
+                    _ => Next::Index(0),
+                };
+                self.open.push(Collection { path, next });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.open.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A validator, filter or action selected by its `do` key.
+pub(crate) trait Tagged: Sized {
+    const KINDS: &'static [&'static str];
+
+    /// Deserializes the kind named by `do` from the other keys of the map.
+    fn deserialize_kind<'de, D: Deserializer<'de>>(kind: &str, rest: D) -> Result<Self, D::Error>;
+}
+
+/// Unlike serde's internally tagged enums, the keys following `do` are streamed into the
+/// selected kind, which keeps the path and position of errors within it.
+pub(crate) fn deserialize_tagged<'de, T: Tagged, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    struct TaggedVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Tagged> Visitor<'de> for TaggedVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("a map with `do`")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+            let first = map.next_key::<String>()?;
+            if first.as_deref() == Some("do") {
+                let kind: String = map.next_value()?;
+                return T::deserialize_kind(&kind, MapAccessDeserializer::new(map));
+            }
+            // `do` isn't the first key, the map has to be buffered until it is found
+            let mut kind = None;
+            let mut rest = Mapping::new();
+            let mut key = first;
+            while let Some(name) = key {
+                let value: Value = map.next_value()?;
+                if name == "do" {
+                    kind = Some(value.as_str().map(str::to_owned).ok_or_else(|| {
+                        de::Error::invalid_type(de::Unexpected::Other("non-string"), &"a kind")
+                    })?);
+                } else {
+                    rest.insert(Value::String(name), value);
+                }
+                key = map.next_key()?;
+            }
+            let kind = kind.ok_or_else(|| de::Error::missing_field("do"))?;
+            T::deserialize_kind(&kind, Value::Mapping(rest)).map_err(de::Error::custom)
+        }
+    }
+
+    deserializer.deserialize_map(TaggedVisitor(PhantomData))
+}
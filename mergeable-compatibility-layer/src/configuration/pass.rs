@@ -4,4 +4,4 @@ use super::actions::Action;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct Pass(Action);
+pub struct Pass(pub(crate) Action);
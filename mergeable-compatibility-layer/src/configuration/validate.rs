@@ -1,18 +1,27 @@
+use std::fmt;
+
 use crate::configuration::basics::{
-    ChainedAndOrIncludeExcludeClause, ChainedAndOrIncludeExcludeClauseBeginsEnds, CountClause,
-    MessageClause, TimeClause,
+    BeginsEnds, Chain, ChainedAndOrIncludeExcludeClause,
+    ChainedAndOrIncludeExcludeClauseBeginsEnds, Changeset, CountClause, MessageClause, TimeClause,
+};
+use chrono::Weekday;
+use chrono_tz::Tz;
+use serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
 };
-use serde::{Deserialize, Serialize};
 
 use crate::configuration::options::{
     BeginsWith, EndsWith, Jira, Max, Min, MustExclude, MustInclude, NoEmpty, Required,
 };
 
-use super::basics::OneOrMany;
+use super::{
+    basics::OneOrMany,
+    parse::{deserialize_tagged, Tagged},
+};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(tag = "do")]
-#[serde(deny_unknown_fields)]
 pub enum Validator {
     /// supported events 'pull_request.*', 'pull_request_review.*',
     #[serde(rename = "age")]
@@ -44,7 +53,7 @@ pub enum Validator {
     /// supported events 'pull_request.*', 'pull_request_review.*', 'issues.*'
     #[serde(rename = "description")]
     Description(TheDescription),
-    /// supported events 'pull_request.*', 'pull_request_review.*'
+    /// supported events 'pull_request.*', 'pull_request_review.*', 'check_suite.*', status.*
     #[serde(rename = "headRef")]
     HeadRef(TheHeadRef),
     /// supported events 'pull_request.*', 'pull_request_review.*', 'issues.*'
@@ -73,51 +82,142 @@ pub enum Validator {
     Not(ValidatorNot),
 }
 
+const PULL_REQUESTS: &[&str] = &["pull_request.*", "pull_request_review.*"];
+const PULL_REQUESTS_AND_ISSUES: &[&str] = &["pull_request.*", "pull_request_review.*", "issues.*"];
+
+impl Validator {
+    /// The events the validator can be evaluated for, `None` for the combinators which
+    /// support what their validators support.
+    pub fn supported_events(&self) -> Option<&'static [&'static str]> {
+        Some(match self {
+            Validator::Age(_)
+            | Validator::Approvals(_)
+            | Validator::Author(_)
+            | Validator::ChangeSet(_)
+            | Validator::Commit(_)
+            | Validator::Contents(_)
+            | Validator::Dependent(_)
+            | Validator::Size(_) => PULL_REQUESTS,
+            Validator::Assignee(_)
+            | Validator::Description(_)
+            | Validator::Label(_)
+            | Validator::Milestone(_)
+            | Validator::Project(_)
+            | Validator::Title(_) => PULL_REQUESTS_AND_ISSUES,
+            Validator::BaseRef(_) | Validator::HeadRef(_) => &[
+                "pull_request.*",
+                "pull_request_review.*",
+                "check_suite.*",
+                "status.*",
+            ],
+            Validator::Stale(_) => &["schedule.repository"],
+            Validator::And(_) | Validator::Or(_) | Validator::Not(_) => return None,
+        })
+    }
+}
+
+impl Tagged for Validator {
+    const KINDS: &'static [&'static str] = &[
+        "age",
+        "approvals",
+        "assignee",
+        "author",
+        "baseRef",
+        "changeset",
+        "commit",
+        "contents",
+        "dependent",
+        "description",
+        "headRef",
+        "label",
+        "milestone",
+        "project",
+        "size",
+        "stale",
+        "title",
+        "and",
+        "or",
+        "not",
+    ];
+
+    fn deserialize_kind<'de, D: Deserializer<'de>>(kind: &str, rest: D) -> Result<Self, D::Error> {
+        Ok(match kind {
+            "age" => Validator::Age(Deserialize::deserialize(rest)?),
+            "approvals" => Validator::Approvals(Deserialize::deserialize(rest)?),
+            "assignee" => Validator::Assignee(Deserialize::deserialize(rest)?),
+            "author" => Validator::Author(Deserialize::deserialize(rest)?),
+            "baseRef" => Validator::BaseRef(Deserialize::deserialize(rest)?),
+            "changeset" => Validator::ChangeSet(Deserialize::deserialize(rest)?),
+            "commit" => Validator::Commit(Deserialize::deserialize(rest)?),
+            "contents" => Validator::Contents(Deserialize::deserialize(rest)?),
+            "dependent" => Validator::Dependent(Deserialize::deserialize(rest)?),
+            "description" => Validator::Description(Deserialize::deserialize(rest)?),
+            "headRef" => Validator::HeadRef(Deserialize::deserialize(rest)?),
+            "label" => Validator::Label(Deserialize::deserialize(rest)?),
+            "milestone" => Validator::Milestone(Deserialize::deserialize(rest)?),
+            "project" => Validator::Project(Deserialize::deserialize(rest)?),
+            "size" => Validator::Size(Deserialize::deserialize(rest)?),
+            "stale" => Validator::Stale(Deserialize::deserialize(rest)?),
+            "title" => Validator::Title(Deserialize::deserialize(rest)?),
+            "and" => Validator::And(ValidatorAnd(Deserialize::deserialize(rest)?)),
+            "or" => Validator::Or(ValidatorOr(Deserialize::deserialize(rest)?)),
+            "not" => Validator::Not(ValidatorNot(Deserialize::deserialize(rest)?)),
+            _ => return Err(de::Error::unknown_variant(kind, Self::KINDS)),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Validator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_tagged(deserializer)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct ValidatorAnd(ValidatorStack);
+pub struct ValidatorAnd(pub(crate) ValidatorStack);
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct ValidatorOr(ValidatorStack);
+pub struct ValidatorOr(pub(crate) ValidatorStack);
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub struct ValidatorNot(ValidatorStack);
+pub struct ValidatorNot(pub(crate) ValidatorStack);
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-struct ValidatorStack {
+pub(crate) struct ValidatorStack {
     #[serde(rename = "validate")]
-    validate: Vec<Validator>,
+    pub(crate) validate: Vec<Validator>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TheAge {
     #[serde(rename = "created_at")]
-    created_at: TimeClause,
+    pub(crate) created_at: TimeClause,
     #[serde(rename = "updated_at")]
-    updated_at: TimeClause,
+    pub(crate) updated_at: TimeClause,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TheApprovals {
     #[serde(rename = "min")]
-    min: Min,
+    pub(crate) min: Min,
     #[serde(rename = "required")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    required: Option<Required>,
+    pub(crate) required: Option<Required>,
     #[serde(rename = "block")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    block: Option<ApprovalsBlock>,
+    pub(crate) block: Option<ApprovalsBlock>,
     #[serde(rename = "limit")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    limit: Option<ApprovalsLimit>,
+    pub(crate) limit: Option<ApprovalsLimit>,
     #[serde(rename = "exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<ApprovalsExclude>,
+    pub(crate) exclude: Option<ApprovalsExclude>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -125,20 +225,20 @@ pub struct TheApprovals {
 pub struct TheAssignee {
     #[serde(rename = "min")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    min: Option<Min>,
+    pub(crate) min: Option<Min>,
     #[serde(rename = "max")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    max: Option<Max>,
+    pub(crate) max: Option<Max>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TheAuthor {
     #[serde(flatten)]
-    author: ChainedAndOrIncludeExcludeClause,
+    pub(crate) author: ChainedAndOrIncludeExcludeClause,
     #[serde(rename = "team")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    team: Option<String>,
+    pub(crate) team: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -146,13 +246,27 @@ pub struct TheAuthor {
 pub struct TheBaseRef {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "mediaType")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    media_type: Option<serde_yaml::Value>,
+    pub(crate) media_type: Option<MediaType>,
+    #[serde(rename = "jira")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) jira: Option<Jira>,
+}
+
+/// API previews mergeable requested for `status.*` events on some GitHub Enterprise servers.
+///
+/// The previews have graduated, the option is accepted for compatibility and has no effect,
+/// which diagnostics warn about.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MediaType {
+    #[serde(rename = "previews")]
+    pub(crate) previews: OneOrMany<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -160,9 +274,9 @@ pub struct TheBaseRef {
 pub struct TheChangeset {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    changeset: ChangesetChain,
+    pub(crate) changeset: ChangesetChain,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -170,10 +284,10 @@ pub struct TheChangeset {
 pub struct TheCommit {
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<Message>,
+    pub(crate) message: Option<Message>,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -181,9 +295,9 @@ pub struct TheCommit {
 pub struct TheContents {
     #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<Files>,
+    pub(crate) files: Option<Files>,
     #[serde(flatten)]
-    content: ContentsFilter,
+    pub(crate) content: ContentsFilter,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -191,13 +305,13 @@ pub struct TheContents {
 pub struct TheDependent {
     #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<Vec<String>>,
+    pub(crate) files: Option<Vec<String>>,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
     #[serde(rename = "changed")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    changed: Option<ChangedFiles>,
+    pub(crate) changed: Option<ChangedFiles>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -205,13 +319,13 @@ pub struct TheDependent {
 pub struct TheDescription {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    description: Option<DescriptionChain>,
+    pub(crate) description: Option<DescriptionChain>,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -219,13 +333,13 @@ pub struct TheDescription {
 pub struct TheHeadRef {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -233,12 +347,12 @@ pub struct TheHeadRef {
 pub struct TheLabel {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    label: LabelChain,
+    pub(crate) label: LabelChain,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -246,12 +360,12 @@ pub struct TheLabel {
 pub struct TheMilestone {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    milestone: MilestoneChain,
+    pub(crate) milestone: MilestoneChain,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -259,7 +373,7 @@ pub struct TheMilestone {
 pub struct TheProject {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -267,13 +381,13 @@ pub struct TheProject {
 pub struct TheSize {
     #[serde(rename = "match")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    r#match: Option<Vec<String>>,
+    pub(crate) r#match: Option<Vec<String>>,
     #[serde(rename = "ignore")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore: Option<Vec<String>>,
+    pub(crate) ignore: Option<Vec<String>>,
     #[serde(rename = "lines")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    lines: Option<LinesChain>,
+    pub(crate) lines: Option<LinesChain>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -281,25 +395,25 @@ pub struct TheSize {
 pub struct TheStale {
     #[serde(rename = "days")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    days: Option<u32>,
+    pub(crate) days: Option<u32>,
     #[serde(rename = "type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    r#type: Option<OneOrMany<String> /*Vec<ResourceType>*/>,
+    pub(crate) r#type: Option<OneOrMany<String> /*Vec<ResourceType>*/>,
     #[serde(rename = "ignore_drafts")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_drafts: Option<bool>,
+    pub(crate) ignore_drafts: Option<bool>,
     #[serde(rename = "ignore_milestones")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_milestones: Option<bool>,
+    pub(crate) ignore_milestones: Option<bool>,
     #[serde(rename = "ignore_projects")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_projects: Option<bool>,
+    pub(crate) ignore_projects: Option<bool>,
     #[serde(rename = "label")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    label: Option<LabelMatch>,
+    pub(crate) label: Option<LabelMatch>,
     #[serde(rename = "time_constraint")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    time_constraint: Option<TimeConstraint>,
+    pub(crate) time_constraint: Option<TimeConstraint>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -316,12 +430,12 @@ pub enum ResourceType {
 pub struct TheTitle {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    title: ChainedAndOrIncludeExcludeClauseBeginsEnds,
+    pub(crate) title: ChainedAndOrIncludeExcludeClauseBeginsEnds,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -329,29 +443,29 @@ pub struct TheTitle {
 pub struct ApprovalsRequired {
     #[serde(rename = "reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    reviewers: Option<Vec<String>>,
+    pub(crate) reviewers: Option<Vec<String>>,
     #[serde(rename = "owners")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    owners: Option<bool>,
+    pub(crate) owners: Option<bool>,
     #[serde(rename = "assignees")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    assignees: Option<bool>,
+    pub(crate) assignees: Option<bool>,
     #[serde(rename = "requested_reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    requested_reviewers: Option<bool>,
+    pub(crate) requested_reviewers: Option<bool>,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ApprovalsBlock {
     #[serde(rename = "changes_requested")]
-    changes_requested: bool,
+    pub(crate) changes_requested: bool,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -359,13 +473,13 @@ pub struct ApprovalsBlock {
 pub struct ApprovalsLimit {
     #[serde(rename = "teams")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    teams: Option<Vec<String>>,
+    pub(crate) teams: Option<Vec<String>>,
     #[serde(rename = "users")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    users: Option<Vec<String>>,
+    pub(crate) users: Option<Vec<String>>,
     #[serde(rename = "owners")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    owners: Option<bool>,
+    pub(crate) owners: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -373,53 +487,23 @@ pub struct ApprovalsLimit {
 pub struct ApprovalsExclude {
     #[serde(rename = "users")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    users: Option<Vec<String>>,
+    pub(crate) users: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct ChangesetChain {
-    #[serde(rename = "and")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<ChangesetChain>>,
-    #[serde(rename = "or")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<ChangesetChain>>,
-    #[serde(rename = "must_include")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
-    #[serde(rename = "must_exclude")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
-    #[serde(rename = "begins_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
-    #[serde(rename = "ends_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
-    #[serde(rename = "min")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    min: Option<Min>,
-    #[serde(rename = "max")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    max: Option<Max>,
-    #[serde(rename = "files")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<FilesContent>,
-}
+pub type ChangesetChain = Chain<Changeset>;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct FilesContent {
     #[serde(rename = "added")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    added: Option<bool>,
+    pub(crate) added: Option<bool>,
     #[serde(rename = "modified")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    modified: Option<bool>,
+    pub(crate) modified: Option<bool>,
     #[serde(rename = "removed")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    removed: Option<bool>,
+    pub(crate) removed: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -427,25 +511,25 @@ pub struct FilesContent {
 pub struct Message {
     #[serde(rename = "regex")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    regex: Option<String>,
+    pub(crate) regex: Option<String>,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
     #[serde(rename = "skip_merge")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    skip_merge: Option<bool>,
+    pub(crate) skip_merge: Option<bool>,
     #[serde(rename = "oldest_only")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    oldest_only: Option<bool>,
+    pub(crate) oldest_only: Option<bool>,
     #[serde(rename = "newest_only")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    newest_only: Option<bool>,
+    pub(crate) newest_only: Option<bool>,
     #[serde(rename = "single_commit_only")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    single_commit_only: Option<bool>,
+    pub(crate) single_commit_only: Option<bool>,
     #[serde(rename = "message_type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message_type: Option<String>,
+    pub(crate) message_type: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -453,10 +537,10 @@ pub struct Message {
 pub struct Files {
     #[serde(rename = "pr_diff")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pr_diff: Option<bool>,
+    pub(crate) pr_diff: Option<bool>,
     #[serde(rename = "ignore")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore: Option<Vec<String>>,
+    pub(crate) ignore: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -464,16 +548,16 @@ pub struct Files {
 pub struct ContentsFilter {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
+    pub(crate) begins_with: Option<BeginsWith>,
     #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) ends_with: Option<EndsWith>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -481,99 +565,36 @@ pub struct ContentsFilter {
 pub struct ChangedFiles {
     #[serde(rename = "file")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    file: Option<String>,
+    pub(crate) file: Option<String>,
     #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<Vec<String>>,
+    pub(crate) files: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct DescriptionChain {
-    #[serde(rename = "and")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<DescriptionChain>>,
-    #[serde(rename = "or")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<DescriptionChain>>,
-    #[serde(rename = "must_include")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
-    #[serde(rename = "must_exclude")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
-    #[serde(rename = "begins_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
-    #[serde(rename = "ends_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
-}
+pub type DescriptionChain = Chain<BeginsEnds>;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct LabelChain {
-    #[serde(rename = "and")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<LabelChain>>,
-    #[serde(rename = "or")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<LabelChain>>,
-    #[serde(rename = "must_include")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
-    #[serde(rename = "must_exclude")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
-    #[serde(rename = "begins_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
-    #[serde(rename = "ends_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
-}
+pub type LabelChain = Chain<BeginsEnds>;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct MilestoneChain {
-    #[serde(rename = "and")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<MilestoneChain>>,
-    #[serde(rename = "or")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<MilestoneChain>>,
-    #[serde(rename = "must_include")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
-    #[serde(rename = "must_exclude")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
-    #[serde(rename = "begins_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
-    #[serde(rename = "ends_with")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
-}
+pub type MilestoneChain = Chain<BeginsEnds>;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct LinesChain {
     #[serde(rename = "total")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    total: Option<CountClause>,
+    pub(crate) total: Option<CountClause>,
     #[serde(rename = "additions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    additions: Option<CountClause>,
+    pub(crate) additions: Option<CountClause>,
     #[serde(rename = "deletions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    deletions: Option<CountClause>,
+    pub(crate) deletions: Option<CountClause>,
     #[serde(rename = "max")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    max: Option<Max>,
+    pub(crate) max: Option<Max>,
     #[serde(rename = "ignore_comments")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_comments: Option<bool>,
+    pub(crate) ignore_comments: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -581,22 +602,138 @@ pub struct LinesChain {
 pub struct LabelMatch {
     #[serde(rename = "match")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    r#match: Option<Vec<String>>,
+    pub(crate) r#match: Option<Vec<String>>,
     #[serde(rename = "ignore")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore: Option<Vec<String>>,
+    pub(crate) ignore: Option<Vec<String>>,
 }
 
+/// The window in which scheduled validators like `stale` may act, checked when loading the
+/// configuration.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TimeConstraint {
+    /// UTC if unset.
     #[serde(rename = "time_zone")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    time_zone: Option<String>,
+    pub(crate) time_zone: Option<TimeZone>,
+    /// All day if unset.
     #[serde(rename = "hours_between")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    hours_between: Option<Vec<String>>,
+    pub(crate) hours_between: Option<HoursBetween>,
+    /// Every day if unset.
     #[serde(rename = "days_of_week")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    days_of_week: Option<Vec<String>>,
+    pub(crate) days_of_week: Option<Vec<DayOfWeek>>,
+}
+
+/// An IANA time zone name, e.g. `America/Los_Angeles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeZone(pub(crate) Tz);
+
+impl Serialize for TimeZone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeZone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map(TimeZone).map_err(|_| {
+            de::Error::invalid_value(
+                Unexpected::Str(&name),
+                &"an IANA time zone name like `America/Los_Angeles`",
+            )
+        })
+    }
+}
+
+/// The first and the last hour of the day to act in, the window wraps around midnight if the
+/// first hour is the later one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HoursBetween {
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+}
+
+impl Serialize for HoursBetween {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self.start.to_string(), self.end.to_string()].serialize(serializer)
+    }
+}
+
+/// Hours are written as numbers or, as in the mergeable documentation, strings.
+impl<'de> Deserialize<'de> for HoursBetween {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Hour(u32);
+
+        impl<'de> Deserialize<'de> for Hour {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct HourVisitor;
+
+                impl<'de> Visitor<'de> for HourVisitor {
+                    type Value = Hour;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("an hour between 0 and 23")
+                    }
+
+                    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Hour, E> {
+                        match u32::try_from(value) {
+                            Ok(hour) if hour < 24 => Ok(Hour(hour)),
+                            _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+                        }
+                    }
+
+                    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Hour, E> {
+                        u64::try_from(value)
+                            .map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+                            .and_then(|value| self.visit_u64(value))
+                    }
+
+                    fn visit_str<E: de::Error>(self, value: &str) -> Result<Hour, E> {
+                        match value.trim().parse() {
+                            Ok(hour) if hour < 24 => Ok(Hour(hour)),
+                            _ => Err(E::invalid_value(Unexpected::Str(value), &self)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_any(HourVisitor)
+            }
+        }
+
+        let hours = Vec::<Hour>::deserialize(deserializer)?;
+        match hours.as_slice() {
+            [Hour(start), Hour(end)] => Ok(HoursBetween {
+                start: *start,
+                end: *end,
+            }),
+            _ => Err(de::Error::invalid_length(
+                hours.len(),
+                &"the first and the last hour",
+            )),
+        }
+    }
+}
+
+/// A weekday like `Mon` or `Monday`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayOfWeek(pub(crate) Weekday);
+
+impl Serialize for DayOfWeek {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for DayOfWeek {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.trim()
+            .parse()
+            .map(DayOfWeek)
+            .map_err(|_| de::Error::invalid_value(Unexpected::Str(&name), &"a weekday like `Mon`"))
+    }
 }
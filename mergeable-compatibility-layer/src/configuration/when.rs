@@ -0,0 +1,173 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The parsed `when` clause of a rule, e.g. `pull_request.opened, issues.*`.
+///
+/// Each comma separated entry consists of an event name and an optional action, a missing
+/// action or `*` matches every action of the event, an event name of `*` matches every event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct When(Vec<EventMatcher>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventMatcher {
+    event: Pattern,
+    action: Option<Pattern>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    Any,
+    Exact(String),
+}
+
+impl When {
+    pub fn matchers(&self) -> &[EventMatcher] {
+        &self.0
+    }
+
+    /// Whether any entry matches the event, `action` is `None` for events without actions.
+    pub fn matches(&self, event: &str, action: Option<&str>) -> bool {
+        self.0.iter().any(|matcher| matcher.matches(event, action))
+    }
+}
+
+impl EventMatcher {
+    pub fn matches(&self, event: &str, action: Option<&str>) -> bool {
+        let event_matches = match &self.event {
+            Pattern::Any => true,
+            Pattern::Exact(expected) => expected == event,
+        };
+        let action_matches = match (&self.action, action) {
+            (None | Some(Pattern::Any), _) => true,
+            (Some(Pattern::Exact(expected)), Some(action)) => expected == action,
+            (Some(Pattern::Exact(_)), None) => false,
+        };
+        event_matches && action_matches
+    }
+
+    /// Whether every event and action this entry matches is also matched by `other`.
+    pub(crate) fn is_covered_by(&self, other: &EventMatcher) -> bool {
+        let event_covered = match (&self.event, &other.event) {
+            (_, Pattern::Any) => true,
+            (Pattern::Exact(event), Pattern::Exact(covering)) => event == covering,
+            (Pattern::Any, Pattern::Exact(_)) => false,
+        };
+        let action_covered = match (&self.action, &other.action) {
+            (_, None | Some(Pattern::Any)) => true,
+            (Some(Pattern::Exact(action)), Some(Pattern::Exact(covering))) => action == covering,
+            (None | Some(Pattern::Any), Some(Pattern::Exact(_))) => false,
+        };
+        event_covered && action_covered
+    }
+}
+
+impl std::str::FromStr for When {
+    type Err = WhenParseError;
+
+    fn from_str(when: &str) -> Result<Self, Self::Err> {
+        let matchers = when
+            .split(',')
+            .map(str::trim)
+            .map(EventMatcher::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(matchers))
+    }
+}
+
+impl std::str::FromStr for EventMatcher {
+    type Err = WhenParseError;
+
+    fn from_str(entry: &str) -> Result<Self, Self::Err> {
+        let pattern = |part: &str| match part {
+            "*" => Ok(Pattern::Any),
+            part if !part.is_empty()
+                && part.chars().all(|c| c.is_ascii_lowercase() || c == '_') =>
+            {
+                Ok(Pattern::Exact(part.to_owned()))
+            }
+            _ => Err(WhenParseError(entry.to_owned())),
+        };
+        let (event, action) = match entry.split_once('.') {
+            Some((event, action)) => (pattern(event)?, Some(pattern(action)?)),
+            None => (pattern(entry)?, None),
+        };
+        Ok(Self { event, action })
+    }
+}
+
+impl Display for When {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, matcher) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{matcher}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for EventMatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.event)?;
+        if let Some(action) = &self.action {
+            write!(f, ".{action}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Any => f.write_str("*"),
+            Pattern::Exact(name) => f.write_str(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhenParseError(String);
+
+impl Display for WhenParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid event '{}', expected 'event' or 'event.action'",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for WhenParseError {}
+
+impl Serialize for When {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for When {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct WhenVisitor;
+
+        impl<'de> Visitor<'de> for WhenVisitor {
+            type Value = When;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("events like `pull_request.opened, issues.*`")
+            }
+
+            // parsing within the visitor lets the deserializer attach the position of the value
+            fn visit_str<E: de::Error>(self, when: &str) -> Result<When, E> {
+                when.parse().map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_str(WhenVisitor)
+    }
+}
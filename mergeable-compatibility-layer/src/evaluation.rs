@@ -0,0 +1,192 @@
+use crate::configuration::{actions::Action, Rule};
+
+use self::context::Context;
+
+pub mod context;
+mod filter;
+mod glob;
+mod matcher;
+pub mod requirements;
+mod validate;
+
+/// Outcome of a single check, ordered by severity so that the worst outcome
+/// of a group can be determined with `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status {
+    Pass,
+    Fail,
+    Error,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Error => "error",
+        }
+    }
+}
+
+/// Result of evaluating one filter or validator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    /// The `do:` kind of the evaluated element, e.g. `title`.
+    pub kind: &'static str,
+    pub status: Status,
+    /// The configured `message` if one applies to the outcome, otherwise a generated description.
+    pub message: String,
+    /// Verdicts of nested elements for the `and`, `or` and `not` combinators.
+    pub details: Vec<Verdict>,
+    /// Numbers of the issues and pull requests a searching validator like `stale` found.
+    pub found: Option<Vec<u64>>,
+}
+
+impl Verdict {
+    pub(crate) fn new(kind: &'static str, outcome: Outcome) -> Self {
+        Self {
+            kind,
+            status: outcome.status,
+            message: outcome.message,
+            details: Vec::new(),
+            found: None,
+        }
+    }
+
+    pub(crate) fn error(kind: &'static str, message: impl Into<String>) -> Self {
+        Self::new(kind, Outcome::error(message))
+    }
+}
+
+/// Result of evaluating a whole [`Rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleVerdict {
+    pub name: Option<String>,
+    pub filters: Vec<Verdict>,
+    pub validators: Vec<Verdict>,
+}
+
+impl RuleVerdict {
+    /// A rule only applies if all of its filters pass.
+    pub fn is_applicable(&self) -> bool {
+        self.filters
+            .iter()
+            .all(|filter| filter.status == Status::Pass)
+    }
+
+    /// The worst status of all validators, a rule without validators passes.
+    pub fn status(&self) -> Status {
+        self.validators
+            .iter()
+            .map(|validator| validator.status)
+            .max()
+            .unwrap_or(Status::Pass)
+    }
+
+    /// The issues and pull requests found by searching validators, which the actions of the
+    /// rule apply to instead of the subject. `None` if no validator searched.
+    pub fn found(&self) -> Option<Vec<u64>> {
+        let mut found: Option<Vec<u64>> = None;
+        let mut pending: Vec<&Verdict> = self.validators.iter().collect();
+        while let Some(verdict) = pending.pop() {
+            if let Some(numbers) = &verdict.found {
+                found.get_or_insert_with(Vec::new).extend(numbers);
+            }
+            pending.extend(&verdict.details);
+        }
+        found.map(|mut found| {
+            found.sort_unstable();
+            found.dedup();
+            found
+        })
+    }
+}
+
+impl Rule {
+    pub fn evaluate(&self, context: &Context) -> RuleVerdict {
+        let filters = self
+            .filter
+            .iter()
+            .flatten()
+            .map(|filter| filter.evaluate(context))
+            .collect();
+        let validators = self
+            .validate
+            .iter()
+            .flatten()
+            .map(|validator| validator.evaluate(context))
+            .collect();
+        RuleVerdict {
+            name: self.name.clone(),
+            filters,
+            validators,
+        }
+    }
+
+    /// The `pass`, `fail` or `error` actions of the rule, depending on the status.
+    pub fn actions_for(&self, status: Status) -> impl Iterator<Item = &Action> {
+        let actions: Box<dyn Iterator<Item = &Action> + Send> = match status {
+            Status::Pass => Box::new(self.pass.iter().flatten().map(|pass| &pass.0)),
+            Status::Fail => Box::new(self.fail.iter().flatten().map(|fail| &fail.0)),
+            Status::Error => Box::new(self.error.iter().flatten().map(|error| &error.0)),
+        };
+        actions
+    }
+}
+
+/// Intermediate result of a check before it is attributed to a filter or validator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Outcome {
+    pub(crate) status: Status,
+    pub(crate) message: String,
+}
+
+impl Outcome {
+    pub(crate) fn pass(message: impl Into<String>) -> Self {
+        Self {
+            status: Status::Pass,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn fail(message: impl Into<String>) -> Self {
+        Self {
+            status: Status::Fail,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn error(message: impl Into<String>) -> Self {
+        Self {
+            status: Status::Error,
+            message: message.into(),
+        }
+    }
+
+    /// Passes only if every outcome passes, `None` if there is nothing to combine.
+    pub(crate) fn all(outcomes: impl IntoIterator<Item = Outcome>) -> Option<Self> {
+        Self::combine(outcomes, |statuses| statuses.max())
+    }
+
+    /// Passes if at least one outcome passes, `None` if there is nothing to combine.
+    pub(crate) fn any(outcomes: impl IntoIterator<Item = Outcome>) -> Option<Self> {
+        Self::combine(outcomes, |statuses| statuses.min())
+    }
+
+    fn combine(
+        outcomes: impl IntoIterator<Item = Outcome>,
+        select: impl FnOnce(&mut dyn Iterator<Item = Status>) -> Option<Status>,
+    ) -> Option<Self> {
+        let outcomes: Vec<_> = outcomes.into_iter().collect();
+        let status = select(&mut outcomes.iter().map(|outcome| outcome.status))?;
+        // e.g. `min` and `max` both passing report the same count
+        let mut messages: Vec<String> = vec![];
+        for outcome in outcomes {
+            if outcome.status == status && !messages.contains(&outcome.message) {
+                messages.push(outcome.message);
+            }
+        }
+        let message = messages.join("; ");
+        Some(Self { status, message })
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::codeowners::Owner;
+
+use super::Outcome;
+
+/// Normalized view on the event a [`Rule`](crate::configuration::Rule) is evaluated against.
+///
+/// The context is independent of any GitHub client library, it's up to the caller to fill it
+/// from the webhook payload and to fetch any additional data the configured validators need.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    /// Name of the event, e.g. `pull_request`.
+    pub event: String,
+    /// Action of the event, e.g. `opened`.
+    pub action: Option<String>,
+    pub repository: Repository,
+    /// Login of the user that triggered the event.
+    pub sender: Option<String>,
+    /// The pull request or issue the event is about.
+    pub subject: Option<Subject>,
+    /// The time validators like `age` measure against, the current time if unset.
+    ///
+    /// Callers collecting [`Rule::searches`](crate::configuration::Rule::searches) must pin it,
+    /// the `stale` validator looks its results up by a query depending on the time.
+    pub now: Option<DateTime<Utc>>,
+    /// Logins of the members of the teams rules and code owners refer to, by `org/team_slug`.
+    pub teams: HashMap<String, Vec<String>>,
+    /// Issues and pull requests found by the searches of validators like `stale`, by query.
+    pub search_results: HashMap<String, Vec<Subject>>,
+    /// What Jira knows about the issue keys validators extract, `None` unless the caller
+    /// verifies them.
+    pub jira_issues: Option<HashMap<String, JiraIssue>>,
+}
+
+impl Context {
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now.unwrap_or_else(Utc::now)
+    }
+
+    pub(crate) fn team_members(&self, team: &str) -> Result<&[String], Outcome> {
+        self.teams
+            .get(team)
+            .map(Vec::as_slice)
+            .ok_or_else(|| Outcome::error(format!("the members of team `{team}` are unknown")))
+    }
+}
+
+/// The verification of an issue key against Jira.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JiraIssue {
+    /// The issue exists in an allowed status.
+    Valid,
+    /// No issue has the key.
+    Missing,
+    /// The issue exists in a status that isn't allowed.
+    Disallowed { status: String },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Repository {
+    pub owner: String,
+    pub name: String,
+    pub private: bool,
+    pub topics: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Subject {
+    Issue(Issue),
+    PullRequest(Box<PullRequest>),
+}
+
+impl Subject {
+    /// The fields pull requests share with issues.
+    pub fn issue(&self) -> &Issue {
+        match self {
+            Subject::Issue(issue) => issue,
+            Subject::PullRequest(pull_request) => &pull_request.issue,
+        }
+    }
+
+    pub fn issue_mut(&mut self) -> &mut Issue {
+        match self {
+            Subject::Issue(issue) => issue,
+            Subject::PullRequest(pull_request) => &mut pull_request.issue,
+        }
+    }
+
+    pub fn pull_request(&self) -> Option<&PullRequest> {
+        match self {
+            Subject::Issue(_) => None,
+            Subject::PullRequest(pull_request) => Some(pull_request),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    /// Login of the user that opened the issue or pull request.
+    pub author: String,
+    pub labels: Vec<String>,
+    /// Title of the milestone.
+    pub milestone: Option<String>,
+    pub assignees: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Projects the issue or pull request is added to, `None` unless fetched.
+    pub projects: Option<Vec<Project>>,
+}
+
+/// A GitHub project (v2) an issue or pull request is added to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    pub title: String,
+    /// The column of the item on boards, i.e. the value of its `Status` field.
+    pub column: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PullRequest {
+    pub issue: Issue,
+    pub base_ref: String,
+    pub head_ref: String,
+    pub head_sha: String,
+    pub draft: bool,
+    pub requested_reviewers: Vec<String>,
+    /// Reviews in the order they were submitted, `None` unless fetched.
+    pub reviews: Option<Vec<Review>>,
+    /// The changed files, `None` unless fetched.
+    pub files: Option<Vec<ChangedFile>>,
+    /// The files at the head, `None` unless fetched.
+    pub tree: Option<Vec<TreeEntry>>,
+    /// Contents at the head of the files contents validators read, `None` unless fetched.
+    pub contents: Option<Vec<FileContent>>,
+    /// Commits oldest first, `None` unless fetched.
+    pub commits: Option<Vec<Commit>>,
+    /// Code owners of the changed files, `None` unless fetched.
+    pub code_owners: Option<Vec<Owner>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    /// Path relative to the repository root.
+    pub filename: String,
+    pub status: FileStatus,
+    pub additions: u64,
+    pub deletions: u64,
+    /// The unified diff of the file, GitHub omits it for large and binary files.
+    pub patch: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Removed,
+    Renamed,
+    Copied,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry {
+    pub path: String,
+    /// In bytes.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileContent {
+    pub path: String,
+    /// `None` if it couldn't be fetched, e.g. because the file isn't text.
+    pub content: Option<String>,
+}
+
+impl FileContent {
+    /// Contents validators read at most this many files, the first ones by path.
+    pub const MAX_FILES: usize = 100;
+    /// Contents validators skip larger files, in bytes.
+    pub const MAX_SIZE: u64 = 1024 * 1024;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    pub sha: String,
+    pub message: String,
+    /// Merge commits have more than one.
+    pub parents: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Review {
+    pub reviewer: String,
+    pub state: ReviewState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
@@ -0,0 +1,115 @@
+use crate::configuration::filter::{Filter, FilterStack, TheAuthor, TheRepository};
+
+use super::{
+    context::Context,
+    matcher::{membership, Input},
+    Outcome, Status, Verdict,
+};
+
+impl Filter {
+    /// The `do:` name of the filter.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Filter::Author(_) => "author",
+            Filter::Repository(_) => "repository",
+            Filter::Payload(_) => "payload",
+            Filter::And(_) => "and",
+            Filter::Or(_) => "or",
+            Filter::Not(_) => "not",
+        }
+    }
+
+    pub fn evaluate(&self, context: &Context) -> Verdict {
+        let kind = self.kind();
+        match self {
+            Filter::Author(author) => Verdict::new(kind, author.evaluate(context)),
+            Filter::Repository(repository) => Verdict::new(kind, repository.evaluate(context)),
+            Filter::Payload(_) => Verdict::error(kind, "the payload filter is not supported yet"),
+            Filter::And(and) => and.0.evaluate(kind, context, Outcome::all),
+            Filter::Or(or) => or.0.evaluate(kind, context, Outcome::any),
+            Filter::Not(not) => {
+                let mut verdict = not.0.evaluate(kind, context, Outcome::all);
+                verdict.status = match verdict.status {
+                    Status::Pass => Status::Fail,
+                    Status::Fail => Status::Pass,
+                    Status::Error => Status::Error,
+                };
+                verdict
+            }
+        }
+    }
+}
+
+impl FilterStack {
+    fn evaluate(
+        &self,
+        kind: &'static str,
+        context: &Context,
+        combine: fn(Vec<Outcome>) -> Option<Outcome>,
+    ) -> Verdict {
+        let details: Vec<_> = self
+            .filter
+            .iter()
+            .map(|filter| filter.evaluate(context))
+            .collect();
+        let outcomes = details
+            .iter()
+            .map(|verdict| Outcome {
+                status: verdict.status,
+                message: verdict.message.clone(),
+            })
+            .collect();
+        let outcome =
+            combine(outcomes).unwrap_or_else(|| Outcome::error(format!("empty `{kind}` filter")));
+        Verdict {
+            details,
+            ..Verdict::new(kind, outcome)
+        }
+    }
+}
+
+impl TheAuthor {
+    fn evaluate(&self, context: &Context) -> Outcome {
+        let Some(subject) = &context.subject else {
+            return Outcome::error("the author filter requires a pull request or issue");
+        };
+        let login = &subject.issue().author;
+        let chain = self.filter.evaluate(&Input::single("author", login));
+        let team = self
+            .team
+            .as_ref()
+            .map(|team| membership(context, "author", login, team));
+        Outcome::all([chain, team].into_iter().flatten())
+            .unwrap_or_else(|| Outcome::pass("no author restrictions configured"))
+    }
+}
+
+impl TheRepository {
+    fn evaluate(&self, context: &Context) -> Outcome {
+        let repository = &context.repository;
+        let visibility = self.visibility.as_ref().map(|visibility| {
+            let actual = if repository.private {
+                "private"
+            } else {
+                "public"
+            };
+            if visibility.eq_ignore_ascii_case(actual) {
+                Outcome::pass(format!("repository is {actual}"))
+            } else {
+                Outcome::fail(format!("repository must be {visibility}"))
+            }
+        });
+        let name = self
+            .name
+            .as_ref()
+            .and_then(|name| name.evaluate(&Input::single("repository name", &repository.name)));
+        let topics = self.topics.as_ref().and_then(|topics| {
+            topics.evaluate(&Input::many(
+                "repository topics",
+                repository.topics.iter().map(String::as_str),
+            ))
+        });
+        Outcome::all([visibility, name, topics].into_iter().flatten())
+            .unwrap_or_else(|| Outcome::pass("no repository restrictions configured"))
+    }
+}
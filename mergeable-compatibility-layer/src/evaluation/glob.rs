@@ -0,0 +1,78 @@
+use regex::Regex;
+
+use super::Outcome;
+
+/// A minimatch style pattern matching paths relative to the repository root, as used by the
+/// `ignore` and `match` options of mergeable.
+///
+/// `*` and `?` don't cross directories, `**` does, `{a,b}` are alternatives and `[...]`
+/// character classes.
+#[derive(Debug, Clone)]
+pub(crate) struct Glob(Regex);
+
+impl Glob {
+    pub(crate) fn new(pattern: &str) -> Result<Self, Outcome> {
+        let mut regex = String::from("^");
+        let mut chars = pattern.chars().peekable();
+        let mut alternatives = 0;
+        while let Some(char) = chars.next() {
+            match char {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                '{' => {
+                    alternatives += 1;
+                    regex.push_str("(?:");
+                }
+                ',' if alternatives > 0 => regex.push('|'),
+                '}' if alternatives > 0 => {
+                    alternatives -= 1;
+                    regex.push(')');
+                }
+                '[' => {
+                    regex.push('[');
+                    if chars.next_if_eq(&'!').is_some() {
+                        regex.push('^');
+                    }
+                    for char in chars.by_ref() {
+                        if char == ']' {
+                            break;
+                        }
+                        if char == '\\' || char == '[' {
+                            regex.push('\\');
+                        }
+                        regex.push(char);
+                    }
+                    regex.push(']');
+                }
+                char => regex.push_str(&regex::escape(&char.to_string())),
+            }
+        }
+        regex.push('$');
+        Regex::new(&regex)
+            .map(Self)
+            .map_err(|err| Outcome::error(format!("invalid glob '{pattern}': {err}")))
+    }
+
+    pub(crate) fn is_match(&self, path: &str) -> bool {
+        self.0.is_match(path)
+    }
+
+    /// Compiles all patterns, failing on the first invalid one.
+    pub(crate) fn all<'a>(
+        patterns: impl IntoIterator<Item = &'a String>,
+    ) -> Result<Vec<Self>, Outcome> {
+        patterns
+            .into_iter()
+            .map(|pattern| Self::new(pattern))
+            .collect()
+    }
+}
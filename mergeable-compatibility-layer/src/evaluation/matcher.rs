@@ -0,0 +1,348 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::configuration::{
+    basics::{Chain, MatchClause, MessageClause, RegexClause},
+    options::{BeginsWith, EndsWith, Max, Min, MustExclude, MustInclude, NoEmpty},
+    validate::FilesContent,
+};
+
+use super::{
+    context::{ChangedFile, Context, FileStatus},
+    Outcome,
+};
+
+/// The value(s) a matcher is applied to, e.g. the title of a pull request or its labels.
+///
+/// A single value is treated as a set with one element: `must_include` passes if any element
+/// matches, whereas `must_exclude` fails as soon as any element matches.
+#[derive(Clone)]
+pub(crate) struct Input<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) values: Vec<&'a str>,
+    /// The status of each value if the values are changed files, for `files` to select from.
+    statuses: Option<Vec<FileStatus>>,
+}
+
+impl<'a> Input<'a> {
+    pub(crate) fn single(name: &'a str, value: &'a str) -> Self {
+        Self {
+            name,
+            values: vec![value],
+            statuses: None,
+        }
+    }
+
+    pub(crate) fn many(name: &'a str, values: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            name,
+            values: values.into_iter().collect(),
+            statuses: None,
+        }
+    }
+
+    /// The paths of changed files, which `files` can select by status.
+    pub(crate) fn files(name: &'a str, files: &'a [ChangedFile]) -> Self {
+        Self {
+            name,
+            values: files.iter().map(|file| file.filename.as_str()).collect(),
+            statuses: Some(files.iter().map(|file| file.status).collect()),
+        }
+    }
+
+    /// Keeps the changed files with a selected status, values without a status are all kept.
+    fn select(&self, files: &FilesContent) -> Self {
+        let Some(statuses) = &self.statuses else {
+            return self.clone();
+        };
+        let (values, statuses) = self
+            .values
+            .iter()
+            .zip(statuses)
+            .filter(|(_, status)| files.selects(**status))
+            .unzip();
+        Self {
+            name: self.name,
+            values,
+            statuses: Some(statuses),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.iter().all(|value| value.trim().is_empty())
+    }
+}
+
+/// Picks the configured message for failures, the generated one otherwise.
+fn outcome(passed: bool, message: Option<&MessageClause>, description: String) -> Outcome {
+    match (passed, message) {
+        (true, _) => Outcome::pass(description),
+        (false, Some(message)) => Outcome::fail(message.as_str()),
+        (false, None) => Outcome::fail(description),
+    }
+}
+
+/// Whether the login belongs to a member of the team, by `org/team_slug`.
+pub(crate) fn membership(context: &Context, name: &str, login: &str, team: &str) -> Outcome {
+    match context.team_members(team) {
+        Ok(members)
+            if members
+                .iter()
+                .any(|member| member.eq_ignore_ascii_case(login)) =>
+        {
+            Outcome::pass(format!("{name} is a member of {team}"))
+        }
+        Ok(_) => Outcome::fail(format!("{name} must be a member of {team}")),
+        Err(err) => err,
+    }
+}
+
+impl RegexClause {
+    pub(crate) fn patterns(&self) -> Vec<&str> {
+        match self {
+            RegexClause::Long { regex, .. } => regex.iter().map(String::as_str).collect(),
+            RegexClause::Short(regex) => vec![regex],
+        }
+    }
+
+    pub(crate) fn message(&self) -> Option<&MessageClause> {
+        match self {
+            RegexClause::Long { message, .. } => message.as_ref(),
+            RegexClause::Short(_) => None,
+        }
+    }
+
+    /// Compiles all patterns honoring `regex_flag`, mergeable matches case insensitive by default.
+    pub(crate) fn compile(&self) -> Result<Vec<Regex>, Outcome> {
+        let flags = match self {
+            RegexClause::Long {
+                regex_flag: Some(flags),
+                ..
+            } if flags == "none" => "",
+            RegexClause::Long {
+                regex_flag: Some(flags),
+                ..
+            } => flags.as_str(),
+            _ => "i",
+        };
+        self.patterns()
+            .into_iter()
+            .map(|pattern| {
+                let mut builder = RegexBuilder::new(pattern);
+                for flag in flags.chars() {
+                    match flag {
+                        'i' => builder.case_insensitive(true),
+                        'm' => builder.multi_line(true),
+                        's' => builder.dot_matches_new_line(true),
+                        'x' => builder.ignore_whitespace(true),
+                        'u' => builder.unicode(true),
+                        // global matching has no meaning for a boolean match
+                        'g' => &mut builder,
+                        unknown => {
+                            return Err(Outcome::error(format!(
+                                "unsupported regex_flag '{unknown}' for '{pattern}'"
+                            )))
+                        }
+                    };
+                }
+                builder
+                    .build()
+                    .map_err(|err| Outcome::error(format!("invalid regex '{pattern}': {err}")))
+            })
+            .collect()
+    }
+}
+
+impl MatchClause {
+    fn patterns(&self) -> Vec<&str> {
+        match self {
+            MatchClause::Long { match_clause, .. } => {
+                match_clause.iter().map(String::as_str).collect()
+            }
+            MatchClause::Short(pattern) => vec![pattern],
+        }
+    }
+
+    fn message(&self) -> Option<&MessageClause> {
+        match self {
+            MatchClause::Long { message, .. } => message.as_ref(),
+            MatchClause::Short(_) => None,
+        }
+    }
+}
+
+impl MustInclude {
+    /// Every configured regex has to match at least one value.
+    pub(crate) fn evaluate(&self, input: &Input) -> Outcome {
+        let regexes = match self.0.compile() {
+            Ok(regexes) => regexes,
+            Err(err) => return err,
+        };
+        let passed = regexes
+            .iter()
+            .all(|regex| input.values.iter().any(|value| regex.is_match(value)));
+        let patterns = self.0.patterns().join("', '");
+        let description = if passed {
+            format!("{} does include '{patterns}'", input.name)
+        } else {
+            format!("{} must include '{patterns}'", input.name)
+        };
+        outcome(passed, self.0.message(), description)
+    }
+}
+
+impl MustExclude {
+    /// No configured regex may match any value.
+    pub(crate) fn evaluate(&self, input: &Input) -> Outcome {
+        let regexes = match self.0.compile() {
+            Ok(regexes) => regexes,
+            Err(err) => return err,
+        };
+        let passed = !regexes
+            .iter()
+            .any(|regex| input.values.iter().any(|value| regex.is_match(value)));
+        let patterns = self.0.patterns().join("', '");
+        let description = if passed {
+            format!("{} does exclude '{patterns}'", input.name)
+        } else {
+            format!("{} must exclude '{patterns}'", input.name)
+        };
+        outcome(passed, self.0.message(), description)
+    }
+}
+
+impl BeginsWith {
+    /// At least one value has to begin with any of the configured prefixes.
+    pub(crate) fn evaluate(&self, input: &Input) -> Outcome {
+        let prefixes = self.0.patterns();
+        let passed = input
+            .values
+            .iter()
+            .any(|value| prefixes.iter().any(|prefix| value.starts_with(prefix)));
+        let prefixes = prefixes.join("', '");
+        let description = if passed {
+            format!("{} does begin with '{prefixes}'", input.name)
+        } else {
+            format!("{} must begin with '{prefixes}'", input.name)
+        };
+        outcome(passed, self.0.message(), description)
+    }
+}
+
+impl EndsWith {
+    /// At least one value has to end with any of the configured suffixes.
+    pub(crate) fn evaluate(&self, input: &Input) -> Outcome {
+        let suffixes = self.0.patterns();
+        let passed = input
+            .values
+            .iter()
+            .any(|value| suffixes.iter().any(|suffix| value.ends_with(suffix)));
+        let suffixes = suffixes.join("', '");
+        let description = if passed {
+            format!("{} does end with '{suffixes}'", input.name)
+        } else {
+            format!("{} must end with '{suffixes}'", input.name)
+        };
+        outcome(passed, self.0.message(), description)
+    }
+}
+
+impl Min {
+    /// The number of values has to be at least `count`.
+    pub(crate) fn evaluate(&self, input: &Input) -> Outcome {
+        let count = input.values.len();
+        let passed = count as u64 >= self.0.count;
+        let description = if passed {
+            format!("{} count is {count}", input.name)
+        } else {
+            format!(
+                "{} count is {count}, expected at least {}",
+                input.name, self.0.count
+            )
+        };
+        outcome(passed, self.0.message.as_ref(), description)
+    }
+}
+
+impl Max {
+    /// The number of values has to be at most `count`.
+    pub(crate) fn evaluate(&self, input: &Input) -> Outcome {
+        let count = input.values.len();
+        let passed = count as u64 <= self.0.count;
+        let description = if passed {
+            format!("{} count is {count}", input.name)
+        } else {
+            format!(
+                "{} count is {count}, expected at most {}",
+                input.name, self.0.count
+            )
+        };
+        outcome(passed, self.0.message.as_ref(), description)
+    }
+}
+
+impl NoEmpty {
+    pub(crate) fn evaluate(&self, input: &Input) -> Option<Outcome> {
+        if !self.enabled {
+            return None;
+        }
+        let passed = !input.is_empty();
+        let description = if passed {
+            format!("{} is not empty", input.name)
+        } else {
+            format!("{} can't be empty", input.name)
+        };
+        Some(outcome(passed, self.message.as_ref(), description))
+    }
+}
+
+impl<K> Chain<K> {
+    /// All options of a clause have to pass, `and` and `or` nest further clauses.
+    ///
+    /// `files` narrows changed files to the selected statuses for the clause and its nested ones.
+    pub(crate) fn evaluate(&self, input: &Input) -> Option<Outcome> {
+        let selected;
+        let input = match &self.files {
+            Some(files) => {
+                selected = input.select(files);
+                &selected
+            }
+            None => input,
+        };
+        let and = self.and.as_ref().and_then(|clauses| {
+            Outcome::all(clauses.iter().filter_map(|clause| clause.evaluate(input)))
+        });
+        let or = self.or.as_ref().and_then(|clauses| {
+            Outcome::any(clauses.iter().filter_map(|clause| clause.evaluate(input)))
+        });
+        let include = self.include.as_ref().map(|include| include.evaluate(input));
+        let exclude = self.exclude.as_ref().map(|exclude| exclude.evaluate(input));
+        let begins_with = self
+            .begins_with
+            .as_ref()
+            .map(|begins| begins.evaluate(input));
+        let ends_with = self.ends_with.as_ref().map(|ends| ends.evaluate(input));
+        let min = self.min.as_ref().map(|min| min.evaluate(input));
+        let max = self.max.as_ref().map(|max| max.evaluate(input));
+        Outcome::all(
+            [and, or, include, exclude, begins_with, ends_with, min, max]
+                .into_iter()
+                .flatten(),
+        )
+    }
+}
+
+impl FilesContent {
+    /// Renamed, copied and otherwise changed files count as modified.
+    fn selects(&self, status: FileStatus) -> bool {
+        let option = match status {
+            FileStatus::Added => self.added,
+            FileStatus::Removed => self.removed,
+            FileStatus::Modified
+            | FileStatus::Renamed
+            | FileStatus::Copied
+            | FileStatus::Changed
+            | FileStatus::Unchanged => self.modified,
+        };
+        option == Some(true)
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::BTreeSet;
+
+use crate::configuration::{
+    filter::{Filter, FilterAnd, FilterNot, FilterOr},
+    options::Jira,
+    validate::{Validator, ValidatorAnd, ValidatorNot, ValidatorOr},
+    Rule,
+};
+
+use super::context::Context;
+
+/// Data beyond the webhook payload that evaluating rules needs, for the caller to fetch into
+/// the [`Context`](super::context::Context).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Requirements {
+    /// The reviews of the pull request.
+    pub reviews: bool,
+    /// The files the pull request changes.
+    pub files: bool,
+    /// The changed files and the tree of the pull request, and the contents of
+    /// [`Rule::content_paths`].
+    pub contents: bool,
+    /// The commits of the pull request.
+    pub commits: bool,
+    /// The code owners of the changed files, and the members of the teams among them.
+    pub code_owners: bool,
+    /// The projects of the issue or pull request.
+    pub projects: bool,
+    /// The members of these teams, by `org/team_slug`.
+    pub teams: BTreeSet<String>,
+}
+
+impl Requirements {
+    /// Combines the requirements of several rules.
+    pub fn extend(&mut self, other: Requirements) {
+        self.reviews |= other.reviews;
+        self.files |= other.files;
+        self.contents |= other.contents;
+        self.commits |= other.commits;
+        self.code_owners |= other.code_owners;
+        self.projects |= other.projects;
+        self.teams.extend(other.teams);
+    }
+}
+
+impl Rule {
+    pub fn requirements(&self) -> Requirements {
+        let mut requirements = Requirements::default();
+        for filter in self.filter.iter().flatten() {
+            filter.require(&mut requirements);
+        }
+        for validator in self.validate.iter().flatten() {
+            validator.require(&mut requirements);
+        }
+        requirements
+    }
+
+    /// The GitHub search queries whose results validators like `stale` need, for the caller
+    /// to run into [`Context::search_results`].
+    pub fn searches(&self, context: &Context) -> BTreeSet<String> {
+        let mut searches = BTreeSet::new();
+        for validator in self.validate.iter().flatten() {
+            validator.search(context, &mut searches);
+        }
+        searches
+    }
+
+    /// The files contents validators read, for the caller to fetch as of the head of the pull
+    /// request into [`PullRequest::contents`](super::context::PullRequest::contents).
+    pub fn content_paths(&self, context: &Context) -> BTreeSet<String> {
+        let mut paths = BTreeSet::new();
+        for validator in self.validate.iter().flatten() {
+            validator.content_paths(context, &mut paths);
+        }
+        paths
+    }
+
+    /// The issue keys validators extract, for the caller to verify against Jira into
+    /// [`Context::jira_issues`]. Commit validators extract from all commits.
+    pub fn jira_keys(&self, context: &Context) -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        for validator in self.validate.iter().flatten() {
+            validator.jira_keys(context, &mut keys);
+        }
+        keys
+    }
+}
+
+impl Validator {
+    fn require(&self, requirements: &mut Requirements) {
+        match self {
+            Validator::Approvals(approvals) => {
+                requirements.reviews = true;
+                let owners = [
+                    approvals
+                        .required
+                        .as_ref()
+                        .and_then(|required| required.owners),
+                    approvals.limit.as_ref().and_then(|limit| limit.owners),
+                ];
+                requirements.code_owners |= owners.contains(&Some(true));
+                let teams = approvals
+                    .limit
+                    .iter()
+                    .flat_map(|limit| limit.teams.iter().flatten());
+                requirements.teams.extend(teams.cloned());
+            }
+            Validator::Author(author) => requirements.teams.extend(author.team.clone()),
+            Validator::ChangeSet(_) | Validator::Dependent(_) | Validator::Size(_) => {
+                requirements.files = true
+            }
+            Validator::Commit(_) => requirements.commits = true,
+            Validator::Contents(_) => requirements.contents = true,
+            Validator::Project(_) => requirements.projects = true,
+            Validator::And(ValidatorAnd(stack))
+            | Validator::Or(ValidatorOr(stack))
+            | Validator::Not(ValidatorNot(stack)) => {
+                for validator in &stack.validate {
+                    validator.require(requirements);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Invalid options are left for the evaluation to report.
+    fn search(&self, context: &Context, searches: &mut BTreeSet<String>) {
+        match self {
+            Validator::Stale(stale) if stale.is_due(context.now()) => {
+                searches.extend(stale.query(context).ok())
+            }
+            Validator::And(ValidatorAnd(stack))
+            | Validator::Or(ValidatorOr(stack))
+            | Validator::Not(ValidatorNot(stack)) => {
+                for validator in &stack.validate {
+                    validator.search(context, searches);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Invalid globs are left for the evaluation to report.
+    fn content_paths(&self, context: &Context, paths: &mut BTreeSet<String>) {
+        match self {
+            Validator::Contents(contents) => {
+                let pull_request = context
+                    .subject
+                    .as_ref()
+                    .and_then(|subject| subject.pull_request());
+                if let Some(Ok(selection)) =
+                    pull_request.map(|pull_request| contents.select(pull_request))
+                {
+                    paths.extend(selection.read.into_iter().map(str::to_owned));
+                }
+            }
+            Validator::And(ValidatorAnd(stack))
+            | Validator::Or(ValidatorOr(stack))
+            | Validator::Not(ValidatorNot(stack)) => {
+                for validator in &stack.validate {
+                    validator.content_paths(context, paths);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Invalid regexes are left for the evaluation to report.
+    fn jira_keys(&self, context: &Context, keys: &mut BTreeSet<String>) {
+        let Some(subject) = &context.subject else {
+            return;
+        };
+        let issue = subject.issue();
+        let pull_request = subject.pull_request();
+        let (jira, values): (&Option<Jira>, Vec<&str>) = match self {
+            Validator::Title(title) => (&title.jira, vec![&issue.title]),
+            Validator::Description(description) => (
+                &description.jira,
+                issue.body.as_deref().into_iter().collect(),
+            ),
+            Validator::Label(label) => (
+                &label.jira,
+                issue.labels.iter().map(String::as_str).collect(),
+            ),
+            Validator::Milestone(milestone) => (
+                &milestone.jira,
+                issue.milestone.as_deref().into_iter().collect(),
+            ),
+            Validator::BaseRef(base_ref) => (
+                &base_ref.jira,
+                pull_request
+                    .map(|pull_request| pull_request.base_ref.as_str())
+                    .into_iter()
+                    .collect(),
+            ),
+            Validator::HeadRef(head_ref) => (
+                &head_ref.jira,
+                pull_request
+                    .map(|pull_request| pull_request.head_ref.as_str())
+                    .into_iter()
+                    .collect(),
+            ),
+            Validator::Commit(commit) => (
+                &commit.jira,
+                pull_request
+                    .and_then(|pull_request| pull_request.commits.as_deref())
+                    .into_iter()
+                    .flatten()
+                    .map(|commit| commit.message.as_str())
+                    .collect(),
+            ),
+            Validator::And(ValidatorAnd(stack))
+            | Validator::Or(ValidatorOr(stack))
+            | Validator::Not(ValidatorNot(stack)) => {
+                for validator in &stack.validate {
+                    validator.jira_keys(context, keys);
+                }
+                return;
+            }
+            _ => return,
+        };
+        if let Some(jira) = jira {
+            keys.extend(jira.keys(values).unwrap_or_default());
+        }
+    }
+}
+
+impl Filter {
+    fn require(&self, requirements: &mut Requirements) {
+        match self {
+            Filter::Author(author) => requirements.teams.extend(author.team.clone()),
+            Filter::And(FilterAnd(stack))
+            | Filter::Or(FilterOr(stack))
+            | Filter::Not(FilterNot(stack)) => {
+                for filter in &stack.filter {
+                    filter.require(requirements);
+                }
+            }
+            Filter::Repository(_) | Filter::Payload(_) => {}
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+
+use crate::configuration::{
+    basics::TimeClause,
+    validate::{
+        TheAge, TheAssignee, TheAuthor, TheDescription, TheLabel, TheMilestone, TheTitle,
+        Validator, ValidatorStack,
+    },
+};
+
+use super::{
+    context::{Context, Subject},
+    matcher::{membership, Input},
+    Outcome, Status, Verdict,
+};
+
+mod approvals;
+mod changeset;
+mod commit;
+mod contents;
+mod dependent;
+mod jira;
+mod project;
+mod refs;
+mod size;
+mod stale;
+
+impl Validator {
+    /// The `do:` name of the validator.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Validator::Age(_) => "age",
+            Validator::Approvals(_) => "approvals",
+            Validator::Assignee(_) => "assignee",
+            Validator::Author(_) => "author",
+            Validator::BaseRef(_) => "baseRef",
+            Validator::ChangeSet(_) => "changeset",
+            Validator::Commit(_) => "commit",
+            Validator::Contents(_) => "contents",
+            Validator::Dependent(_) => "dependent",
+            Validator::Description(_) => "description",
+            Validator::HeadRef(_) => "headRef",
+            Validator::Label(_) => "label",
+            Validator::Milestone(_) => "milestone",
+            Validator::Project(_) => "project",
+            Validator::Size(_) => "size",
+            Validator::Stale(_) => "stale",
+            Validator::Title(_) => "title",
+            Validator::And(_) => "and",
+            Validator::Or(_) => "or",
+            Validator::Not(_) => "not",
+        }
+    }
+
+    pub fn evaluate(&self, context: &Context) -> Verdict {
+        let kind = self.kind();
+        let subject = || {
+            context.subject.as_ref().ok_or_else(|| {
+                Outcome::error(format!(
+                    "the {kind} validator requires a pull request or issue"
+                ))
+            })
+        };
+        let outcome = match self {
+            Validator::Approvals(approvals) => approvals.evaluate(context),
+            Validator::Author(author) => subject().map(|subject| author.evaluate(subject, context)),
+            Validator::BaseRef(base_ref) => base_ref.evaluate(context),
+            Validator::ChangeSet(changeset) => changeset.evaluate(context),
+            Validator::Commit(commit) => commit.evaluate(context),
+            Validator::Contents(contents) => contents.evaluate(context),
+            Validator::Dependent(dependent) => dependent.evaluate(context),
+            Validator::HeadRef(head_ref) => head_ref.evaluate(context),
+            Validator::Size(size) => size.evaluate(context),
+            Validator::Stale(stale) => return stale.evaluate(context),
+            Validator::Age(age) => subject().map(|subject| age.evaluate(subject, context.now())),
+            Validator::Assignee(assignee) => subject().map(|subject| assignee.evaluate(subject)),
+            Validator::Title(title) => subject().map(|subject| title.evaluate(subject, context)),
+            Validator::Description(description) => {
+                subject().map(|subject| description.evaluate(subject, context))
+            }
+            Validator::Label(label) => subject().map(|subject| label.evaluate(subject, context)),
+            Validator::Milestone(milestone) => {
+                subject().map(|subject| milestone.evaluate(subject, context))
+            }
+            Validator::Project(project) => subject().and_then(|subject| project.evaluate(subject)),
+            Validator::And(and) => return and.0.evaluate(kind, context, Outcome::all),
+            Validator::Or(or) => return or.0.evaluate(kind, context, Outcome::any),
+            Validator::Not(not) => {
+                let mut verdict = not.0.evaluate(kind, context, Outcome::all);
+                verdict.status = match verdict.status {
+                    Status::Pass => Status::Fail,
+                    Status::Fail => Status::Pass,
+                    Status::Error => Status::Error,
+                };
+                return verdict;
+            }
+        };
+        Verdict::new(kind, outcome.unwrap_or_else(|err| err))
+    }
+}
+
+impl ValidatorStack {
+    fn evaluate(
+        &self,
+        kind: &'static str,
+        context: &Context,
+        combine: fn(Vec<Outcome>) -> Option<Outcome>,
+    ) -> Verdict {
+        let details: Vec<_> = self
+            .validate
+            .iter()
+            .map(|validator| validator.evaluate(context))
+            .collect();
+        let outcomes = details
+            .iter()
+            .map(|verdict| Outcome {
+                status: verdict.status,
+                message: verdict.message.clone(),
+            })
+            .collect();
+        let outcome = combine(outcomes)
+            .unwrap_or_else(|| Outcome::error(format!("empty `{kind}` validator")));
+        Verdict {
+            details,
+            ..Verdict::new(kind, outcome)
+        }
+    }
+}
+
+/// Validators without any configured option have nothing to complain about.
+fn nothing_to_validate(kind: &str) -> Outcome {
+    Outcome::pass(format!("{kind} has no checks configured"))
+}
+
+impl TheAge {
+    fn evaluate(&self, subject: &Subject, now: DateTime<Utc>) -> Outcome {
+        let issue = subject.issue();
+        Outcome::all([
+            self.created_at
+                .evaluate("created_at", issue.created_at, now),
+            self.updated_at
+                .evaluate("updated_at", issue.updated_at, now),
+        ])
+        .unwrap_or_else(|| nothing_to_validate("age"))
+    }
+}
+
+impl TimeClause {
+    /// Passes if at least `days` whole days have passed since the timestamp.
+    ///
+    /// Timestamps are compared as instants, so offsets in the payload don't shift the count.
+    fn evaluate(
+        &self,
+        name: &str,
+        timestamp: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Outcome {
+        let Some(timestamp) = timestamp else {
+            return Outcome::error(format!("{name} is unknown"));
+        };
+        let days = (now - timestamp).num_days();
+        if days >= i64::from(self.days) {
+            return Outcome::pass(format!("{name} is {days} day(s) ago"));
+        }
+        let message = self.message.as_ref().map_or_else(
+            || {
+                format!(
+                    "{name} is {days} day(s) ago, expected at least {} day(s)",
+                    self.days
+                )
+            },
+            |message| message.as_str().to_owned(),
+        );
+        Outcome::fail(message)
+    }
+}
+
+impl TheAssignee {
+    fn evaluate(&self, subject: &Subject) -> Outcome {
+        let assignees = Input::many(
+            "assignees",
+            subject.issue().assignees.iter().map(String::as_str),
+        );
+        let min = self.min.as_ref().map(|min| min.evaluate(&assignees));
+        let max = self.max.as_ref().map(|max| max.evaluate(&assignees));
+        Outcome::all([min, max].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("assignee"))
+    }
+}
+
+impl TheAuthor {
+    fn evaluate(&self, subject: &Subject, context: &Context) -> Outcome {
+        let login = &subject.issue().author;
+        let chain = self.author.evaluate(&Input::single("author", login));
+        let team = self
+            .team
+            .as_ref()
+            .map(|team| membership(context, "author", login, team));
+        Outcome::all([chain, team].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("author"))
+    }
+}
+
+impl TheTitle {
+    fn evaluate(&self, subject: &Subject, context: &Context) -> Outcome {
+        let title = Input::single("title", &subject.issue().title);
+        let no_empty = self
+            .no_empty
+            .as_ref()
+            .and_then(|no_empty| no_empty.evaluate(&title));
+        let chain = self.title.evaluate(&title);
+        let jira = self
+            .jira
+            .as_ref()
+            .map(|jira| jira.evaluate_input(&title, context));
+        Outcome::all([no_empty, chain, jira].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("title"))
+    }
+}
+
+impl TheDescription {
+    fn evaluate(&self, subject: &Subject, context: &Context) -> Outcome {
+        let body = subject.issue().body.as_deref().unwrap_or_default();
+        let description = Input::single("description", body);
+        let no_empty = self
+            .no_empty
+            .as_ref()
+            .and_then(|no_empty| no_empty.evaluate(&description));
+        let chain = self
+            .description
+            .as_ref()
+            .and_then(|chain| chain.evaluate(&description));
+        let jira = self
+            .jira
+            .as_ref()
+            .map(|jira| jira.evaluate_input(&description, context));
+        Outcome::all([no_empty, chain, jira].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("description"))
+    }
+}
+
+impl TheLabel {
+    fn evaluate(&self, subject: &Subject, context: &Context) -> Outcome {
+        let labels = Input::many("labels", subject.issue().labels.iter().map(String::as_str));
+        let no_empty = self
+            .no_empty
+            .as_ref()
+            .and_then(|no_empty| no_empty.evaluate(&labels));
+        let chain = self.label.evaluate(&labels);
+        let jira = self
+            .jira
+            .as_ref()
+            .map(|jira| jira.evaluate_input(&labels, context));
+        Outcome::all([no_empty, chain, jira].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("label"))
+    }
+}
+
+impl TheMilestone {
+    fn evaluate(&self, subject: &Subject, context: &Context) -> Outcome {
+        let milestone = Input::many("milestone", subject.issue().milestone.as_deref());
+        let no_empty = self
+            .no_empty
+            .as_ref()
+            .and_then(|no_empty| no_empty.evaluate(&milestone));
+        let chain = self.milestone.evaluate(&milestone);
+        let jira = self
+            .jira
+            .as_ref()
+            .map(|jira| jira.evaluate_input(&milestone, context));
+        Outcome::all([no_empty, chain, jira].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("milestone"))
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    codeowners::Owner,
+    configuration::{
+        basics::MessageClause,
+        options::Required,
+        validate::{ApprovalsBlock, ApprovalsLimit, TheApprovals},
+    },
+};
+
+use super::super::{
+    context::{Context, PullRequest, Review, ReviewState},
+    Outcome,
+};
+
+impl TheApprovals {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let pull_request = context
+            .subject
+            .as_ref()
+            .and_then(|subject| subject.pull_request())
+            .ok_or_else(|| Outcome::error("the approvals validator requires a pull request"))?;
+        let reviews = pull_request
+            .reviews
+            .as_deref()
+            .ok_or_else(|| Outcome::error("the reviews of the pull request are unavailable"))?;
+        let excluded = self
+            .exclude
+            .iter()
+            .flat_map(|exclude| exclude.users.iter().flatten());
+        let latest = LatestReviews::new(reviews, excluded);
+
+        let counted = match &self.limit {
+            Some(limit) => limit.filter(
+                context,
+                pull_request,
+                latest.with_state(ReviewState::Approved),
+            )?,
+            None => latest.with_state(ReviewState::Approved),
+        };
+        let min = &self.min.0;
+        let min = if counted.len() as u64 >= min.count {
+            Outcome::pass(format!("{} approval(s) counted", counted.len()))
+        } else {
+            Outcome::fail(message_or(min.message.as_ref(), || {
+                format!(
+                    "{} approval(s) counted, at least {} required",
+                    counted.len(),
+                    min.count
+                )
+            }))
+        };
+        let required = self
+            .required
+            .as_ref()
+            .map(|required| required.evaluate(context, pull_request, &latest))
+            .transpose()?;
+        let block = self
+            .block
+            .as_ref()
+            .and_then(|block| block.evaluate(&latest));
+        Ok(
+            Outcome::all([Some(min), required, block].into_iter().flatten())
+                .expect("the minimum is always evaluated"),
+        )
+    }
+}
+
+/// The latest state of each reviewer, comments don't change the state of a review.
+struct LatestReviews<'a> {
+    /// Reviewers by lowercase login, logins are case insensitive.
+    states: BTreeMap<String, (&'a str, ReviewState)>,
+}
+
+impl<'a> LatestReviews<'a> {
+    fn new(reviews: &'a [Review], excluded: impl Iterator<Item = &'a String>) -> Self {
+        let excluded: Vec<_> = excluded.map(|user| user.to_lowercase()).collect();
+        let mut states = BTreeMap::new();
+        for review in reviews {
+            let login = review.reviewer.to_lowercase();
+            let counts = !matches!(review.state, ReviewState::Commented | ReviewState::Pending);
+            if counts && !excluded.contains(&login) {
+                states.insert(login, (review.reviewer.as_str(), review.state));
+            }
+        }
+        Self { states }
+    }
+
+    fn with_state(&self, state: ReviewState) -> Vec<&'a str> {
+        self.states
+            .values()
+            .filter(|(_, latest)| *latest == state)
+            .map(|(login, _)| *login)
+            .collect()
+    }
+
+    fn has_approved(&self, login: &str) -> bool {
+        self.states
+            .get(&login.to_lowercase())
+            .is_some_and(|(_, state)| *state == ReviewState::Approved)
+    }
+}
+
+impl ApprovalsLimit {
+    /// Keeps the approvers the limit allows, an empty limit allows nobody.
+    fn filter<'a>(
+        &self,
+        context: &Context,
+        pull_request: &PullRequest,
+        approvers: Vec<&'a str>,
+    ) -> Result<Vec<&'a str>, Outcome> {
+        let mut allowed: Vec<String> = self
+            .users
+            .iter()
+            .flatten()
+            .map(|user| user.to_lowercase())
+            .collect();
+        let mut teams: Vec<&str> = self.teams.iter().flatten().map(String::as_str).collect();
+        if self.owners == Some(true) {
+            for owner in code_owners(pull_request)? {
+                match owner {
+                    Owner::User(login) => allowed.push(login.to_lowercase()),
+                    Owner::Team(team) => teams.push(team),
+                    Owner::Email(_) => {}
+                }
+            }
+        }
+        for team in teams {
+            let members = context.team_members(team)?;
+            allowed.extend(members.iter().map(|member| member.to_lowercase()));
+        }
+        Ok(approvers
+            .into_iter()
+            .filter(|approver| allowed.contains(&approver.to_lowercase()))
+            .collect())
+    }
+}
+
+impl Required {
+    fn evaluate(
+        &self,
+        context: &Context,
+        pull_request: &PullRequest,
+        latest: &LatestReviews,
+    ) -> Result<Outcome, Outcome> {
+        let mut required: Vec<&str> = self
+            .reviewers
+            .iter()
+            .flat_map(|reviewers| reviewers.iter())
+            .map(String::as_str)
+            .collect();
+        if self.assignees == Some(true) {
+            required.extend(pull_request.issue.assignees.iter().map(String::as_str));
+        }
+        if self.requested_reviewers == Some(true) {
+            // as in mergeable only the pending requests count: GitHub drops reviewers from the
+            // requested ones once they reviewed, so their approval isn't required afterwards
+            required.extend(pull_request.requested_reviewers.iter().map(String::as_str));
+        }
+        // a team owner is satisfied by the approval of any of its members
+        let mut teams: Vec<&str> = vec![];
+        if self.owners == Some(true) {
+            for owner in code_owners(pull_request)? {
+                match owner {
+                    Owner::User(login) => required.push(login),
+                    Owner::Team(team) => teams.push(team),
+                    // emails can't be told apart from the logins of reviewers
+                    Owner::Email(_) => {}
+                }
+            }
+        }
+        let author = &pull_request.issue.author;
+        let mut missing: Vec<&str> = vec![];
+        for login in required {
+            let duplicate = missing
+                .iter()
+                .any(|other| other.eq_ignore_ascii_case(login));
+            if !login.eq_ignore_ascii_case(author) && !duplicate && !latest.has_approved(login) {
+                missing.push(login);
+            }
+        }
+        for team in teams {
+            let members = context.team_members(team)?;
+            if !members.iter().any(|member| latest.has_approved(member)) {
+                missing.push(team);
+            }
+        }
+        if missing.is_empty() {
+            return Ok(Outcome::pass("all required reviewers approved"));
+        }
+        Ok(Outcome::fail(message_or(self.message.as_ref(), || {
+            format!("approval required from {}", missing.join(", "))
+        })))
+    }
+}
+
+impl ApprovalsBlock {
+    fn evaluate(&self, latest: &LatestReviews) -> Option<Outcome> {
+        if !self.changes_requested {
+            return None;
+        }
+        let requested = latest.with_state(ReviewState::ChangesRequested);
+        if requested.is_empty() {
+            return Some(Outcome::pass("no changes requested"));
+        }
+        Some(Outcome::fail(message_or(self.message.as_ref(), || {
+            format!("changes requested by {}", requested.join(", "))
+        })))
+    }
+}
+
+fn code_owners(pull_request: &PullRequest) -> Result<&[Owner], Outcome> {
+    pull_request
+        .code_owners
+        .as_deref()
+        .ok_or_else(|| Outcome::error("the code owners of the pull request are unavailable"))
+}
+
+fn message_or(message: Option<&MessageClause>, default: impl FnOnce() -> String) -> String {
+    message.map_or_else(default, |message| message.as_str().to_owned())
+}
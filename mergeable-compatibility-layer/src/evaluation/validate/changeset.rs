@@ -0,0 +1,29 @@
+use crate::configuration::validate::TheChangeset;
+
+use super::{
+    super::{context::Context, matcher::Input, Outcome},
+    nothing_to_validate,
+};
+
+impl TheChangeset {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let files = context
+            .subject
+            .as_ref()
+            .and_then(|subject| subject.pull_request())
+            .ok_or_else(|| Outcome::error("the changeset validator requires a pull request"))?
+            .files
+            .as_deref()
+            .ok_or_else(|| {
+                Outcome::error("the changed files of the pull request are unavailable")
+            })?;
+        let input = Input::files("changeset", files);
+        let no_empty = self
+            .no_empty
+            .as_ref()
+            .and_then(|no_empty| no_empty.evaluate(&input));
+        let chain = self.changeset.evaluate(&input);
+        Ok(Outcome::all([no_empty, chain].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("changeset")))
+    }
+}
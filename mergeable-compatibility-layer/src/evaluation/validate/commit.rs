@@ -0,0 +1,149 @@
+use regex::Regex;
+
+use crate::configuration::{
+    basics::RegexClause,
+    options::Jira,
+    validate::{Message, TheCommit},
+};
+
+use super::{
+    super::{
+        context::{Commit, Context},
+        Outcome,
+    },
+    nothing_to_validate,
+};
+
+impl TheCommit {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let commits = context
+            .subject
+            .as_ref()
+            .and_then(|subject| subject.pull_request())
+            .ok_or_else(|| Outcome::error("the commit validator requires a pull request"))?
+            .commits
+            .as_deref()
+            .ok_or_else(|| Outcome::error("the commits of the pull request are unavailable"))?;
+        let single_commit_only = self
+            .message
+            .as_ref()
+            .is_some_and(|message| message.single_commit_only == Some(true));
+        if single_commit_only && commits.len() != 1 {
+            return Ok(Outcome::pass(
+                "the pull request has more than one commit, the validation is skipped",
+            ));
+        }
+        let checked = select(self.message.as_ref(), commits)?;
+        let message = self
+            .message
+            .as_ref()
+            .map(|message| message.evaluate(&checked))
+            .transpose()?
+            .flatten();
+        let jira = self
+            .jira
+            .as_ref()
+            .map(|jira| jira.evaluate(&checked, context));
+        Ok(Outcome::all([message, jira].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("commit")))
+    }
+}
+
+/// The commits the options apply to with the part of their message that is checked.
+fn select<'a>(
+    options: Option<&Message>,
+    commits: &'a [Commit],
+) -> Result<Vec<(&'a str, &'a str)>, Outcome> {
+    let enabled = |option: fn(&Message) -> Option<bool>, default: bool| {
+        options.and_then(option).unwrap_or(default)
+    };
+    let mut selected: Vec<&Commit> = commits.iter().collect();
+    if enabled(|options| options.skip_merge, true) {
+        // merge commits are skipped unless `skip_merge` is false
+        selected.retain(|commit| commit.parents <= 1);
+    }
+    if enabled(|options| options.oldest_only, false) {
+        selected.truncate(1);
+    }
+    if enabled(|options| options.newest_only, false) {
+        selected = selected.pop().into_iter().collect();
+    }
+    let message_type = options.and_then(|options| options.message_type.as_deref());
+    selected
+        .into_iter()
+        .map(|commit| {
+            let (title, body) = commit
+                .message
+                .split_once('\n')
+                .unwrap_or((&commit.message, ""));
+            let checked = match message_type {
+                None | Some("") => commit.message.as_str(),
+                Some("title") => title,
+                Some("body") => body.trim_start_matches('\n'),
+                Some(unknown) => {
+                    return Err(Outcome::error(format!(
+                        "unknown message_type `{unknown}`, expected `title` or `body`"
+                    )))
+                }
+            };
+            Ok((commit.sha.as_str(), checked))
+        })
+        .collect()
+}
+
+impl Message {
+    /// Every checked commit has to match the regex.
+    fn evaluate(&self, checked: &[(&str, &str)]) -> Result<Option<Outcome>, Outcome> {
+        let Some(pattern) = &self.regex else {
+            return Ok(None);
+        };
+        let regexes = RegexClause::Short(pattern.clone()).compile()?;
+        let failed = failing(checked, |message| {
+            regexes.iter().all(|regex| regex.is_match(message))
+        });
+        if failed.is_empty() {
+            return Ok(Some(Outcome::pass(format!(
+                "commit messages match '{pattern}'"
+            ))));
+        }
+        Ok(Some(Outcome::fail(self.message.as_ref().map_or_else(
+            || format!("commit message(s) of {failed} must match '{pattern}'"),
+            |message| message.as_str().to_owned(),
+        ))))
+    }
+}
+
+impl Jira {
+    /// Every checked commit has to mention an issue key, which has to exist in an allowed status
+    /// if the caller verified the keys.
+    fn evaluate(&self, checked: &[(&str, &str)], context: &Context) -> Outcome {
+        let regexes: Vec<Regex> = match self.0.compile() {
+            Ok(regexes) => regexes,
+            Err(err) => return err,
+        };
+        let patterns = self.0.patterns().join("', '");
+        let failed = failing(checked, |message| {
+            regexes.iter().any(|regex| regex.is_match(message))
+        });
+        if !failed.is_empty() {
+            return self
+                .fail(|| format!("commit message(s) of {failed} must reference '{patterns}'"));
+        }
+        let keys = match self.keys(checked.iter().map(|(_, message)| *message)) {
+            Ok(keys) => keys,
+            Err(err) => return err,
+        };
+        self.verify(&keys, context)
+            .unwrap_or_else(|| Outcome::pass(format!("commit messages reference '{patterns}'")))
+    }
+}
+
+/// The abbreviated SHAs of the commits whose checked message doesn't pass.
+fn failing(checked: &[(&str, &str)], passes: impl Fn(&str) -> bool) -> String {
+    checked
+        .iter()
+        .filter(|(_, message)| !passes(message))
+        .map(|(sha, _)| sha.get(..7).unwrap_or(sha))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use crate::configuration::validate::{ContentsFilter, TheContents};
+
+use super::{
+    super::{
+        context::{Context, FileContent, FileStatus, PullRequest},
+        glob::Glob,
+        matcher::Input,
+        Outcome, Status,
+    },
+    nothing_to_validate,
+};
+
+/// Files skipped unless `files.ignore` is configured.
+const DEFAULT_IGNORE: &str = ".github/mergeable.yml";
+
+impl TheContents {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let pull_request = context
+            .subject
+            .as_ref()
+            .and_then(|subject| subject.pull_request())
+            .ok_or_else(|| Outcome::error("the contents validator requires a pull request"))?;
+        let selection = self.select(pull_request)?;
+        let contents: HashMap<_, _> = pull_request
+            .contents
+            .as_deref()
+            .ok_or_else(|| Outcome::error("the file contents of the pull request are unavailable"))?
+            .iter()
+            .map(|file| (file.path.as_str(), file.content.as_deref()))
+            .collect();
+
+        let mut checked = 0;
+        let mut unavailable = 0;
+        let mut problems = vec![];
+        for path in &selection.read {
+            let Some(Some(content)) = contents.get(path) else {
+                unavailable += 1;
+                continue;
+            };
+            let Some(outcome) = self.content.evaluate(&Input::single("content", content)) else {
+                return Ok(nothing_to_validate("contents"));
+            };
+            checked += 1;
+            if outcome.status != Status::Pass {
+                problems.push(Outcome {
+                    message: format!("`{path}`: {}", outcome.message),
+                    ..outcome
+                });
+            }
+        }
+        let mut outcome = Outcome::all(problems)
+            .unwrap_or_else(|| Outcome::pass(format!("the content of {checked} file(s) matches")));
+        if let Some(skipped) = selection.skipped(unavailable) {
+            outcome.message = format!("{}; {skipped}", outcome.message);
+        }
+        Ok(outcome)
+    }
+
+    /// The files at the head the validator reads: those the pull request changes with
+    /// `files.pr_diff`, all of them otherwise, less the ignored ones, the large ones and those
+    /// beyond [`FileContent::MAX_FILES`].
+    pub(crate) fn select<'a>(
+        &self,
+        pull_request: &'a PullRequest,
+    ) -> Result<Selection<'a>, Outcome> {
+        let files = self.files.as_ref();
+        let ignore = match files.and_then(|files| files.ignore.as_ref()) {
+            Some(ignore) => Glob::all(ignore)?,
+            None => vec![Glob::new(DEFAULT_IGNORE)?],
+        };
+        let tree = pull_request.tree.as_deref();
+        let mut paths: Vec<&str> = if files.is_some_and(|files| files.pr_diff == Some(true)) {
+            pull_request
+                .files
+                .as_deref()
+                .ok_or_else(|| {
+                    Outcome::error("the changed files of the pull request are unavailable")
+                })?
+                .iter()
+                .filter(|file| !matches!(file.status, FileStatus::Removed | FileStatus::Unchanged))
+                .map(|file| file.filename.as_str())
+                .collect()
+        } else {
+            tree.ok_or_else(|| Outcome::error("the files of the repository are unavailable"))?
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect()
+        };
+        paths.retain(|path| !ignore.iter().any(|glob| glob.is_match(path)));
+        paths.sort_unstable();
+        paths.dedup();
+
+        // files missing from the tree are left for the download to tell about
+        let sizes: HashMap<&str, u64> = tree
+            .into_iter()
+            .flatten()
+            .map(|entry| (entry.path.as_str(), entry.size))
+            .collect();
+        let before = paths.len();
+        paths.retain(|path| {
+            sizes
+                .get(path)
+                .map_or(true, |size| *size <= FileContent::MAX_SIZE)
+        });
+        let large = before - paths.len();
+        let beyond_limit = paths.len().saturating_sub(FileContent::MAX_FILES);
+        paths.truncate(FileContent::MAX_FILES);
+        Ok(Selection {
+            read: paths,
+            large,
+            beyond_limit,
+        })
+    }
+}
+
+/// The files a contents validator reads, and how many it skips.
+pub(crate) struct Selection<'a> {
+    pub(crate) read: Vec<&'a str>,
+    large: usize,
+    beyond_limit: usize,
+}
+
+impl Selection<'_> {
+    /// Tells about the skipped files, including those read without content.
+    fn skipped(&self, unavailable: usize) -> Option<String> {
+        let mut skipped = vec![];
+        if self.large > 0 {
+            skipped.push(format!(
+                "{} file(s) larger than {} MiB",
+                self.large,
+                FileContent::MAX_SIZE / (1024 * 1024)
+            ));
+        }
+        if self.beyond_limit > 0 {
+            skipped.push(format!(
+                "{} file(s) beyond the first {}",
+                self.beyond_limit,
+                FileContent::MAX_FILES
+            ));
+        }
+        if unavailable > 0 {
+            skipped.push(format!("{unavailable} file(s) without content"));
+        }
+        (!skipped.is_empty()).then(|| format!("skipped {}", skipped.join(", ")))
+    }
+}
+
+impl ContentsFilter {
+    fn evaluate(&self, input: &Input) -> Option<Outcome> {
+        let include = self.include.as_ref().map(|include| include.evaluate(input));
+        let exclude = self.exclude.as_ref().map(|exclude| exclude.evaluate(input));
+        let begins_with = self
+            .begins_with
+            .as_ref()
+            .map(|begins| begins.evaluate(input));
+        let ends_with = self.ends_with.as_ref().map(|ends| ends.evaluate(input));
+        Outcome::all(
+            [include, exclude, begins_with, ends_with]
+                .into_iter()
+                .flatten(),
+        )
+    }
+}
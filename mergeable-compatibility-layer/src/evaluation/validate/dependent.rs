@@ -0,0 +1,92 @@
+use crate::configuration::validate::{ChangedFiles, TheDependent};
+
+use super::{
+    super::{context::Context, glob::Glob, Outcome},
+    nothing_to_validate,
+};
+
+impl TheDependent {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let files = context
+            .subject
+            .as_ref()
+            .and_then(|subject| subject.pull_request())
+            .ok_or_else(|| Outcome::error("the dependent validator requires a pull request"))?
+            .files
+            .as_deref()
+            .ok_or_else(|| {
+                Outcome::error("the changed files of the pull request are unavailable")
+            })?;
+        let changed: Vec<&str> = files.iter().map(|file| file.filename.as_str()).collect();
+        let changes = |pattern: &String| -> Result<bool, Outcome> {
+            let glob = Glob::new(pattern)?;
+            Ok(changed.iter().any(|file| glob.is_match(file)))
+        };
+
+        // a change to any of `files` requires changes to all of them
+        let files = match &self.files {
+            Some(files) => {
+                let (triggers, missing) = partition(files, changes)?;
+                Some(self.outcome(&triggers, &missing))
+            }
+            None => None,
+        };
+        // a change to `changed.file` requires changes to all of `changed.files`
+        let changed = match &self.changed {
+            Some(ChangedFiles {
+                file: Some(file),
+                files: dependents,
+            }) => Some(if changes(file)? {
+                let (_, missing) = partition(dependents.iter().flatten(), changes)?;
+                self.outcome(&[file], &missing)
+            } else {
+                Outcome::pass(format!("`{file}` is unchanged"))
+            }),
+            _ => None,
+        };
+        Ok(Outcome::all([files, changed].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("dependent")))
+    }
+
+    fn outcome(&self, triggers: &[&String], missing: &[&String]) -> Outcome {
+        if triggers.is_empty() {
+            return Outcome::pass("no dependent files changed");
+        }
+        if missing.is_empty() {
+            return Outcome::pass(format!("{} changed together", quoted(triggers)));
+        }
+        Outcome::fail(match &self.message {
+            Some(message) => format!("{}: {}", message.as_str(), quoted(missing)),
+            None => format!(
+                "{} must change along with {}",
+                quoted(missing),
+                quoted(triggers)
+            ),
+        })
+    }
+}
+
+/// Splits the patterns into the changed and unchanged ones.
+fn partition<'a>(
+    patterns: impl IntoIterator<Item = &'a String>,
+    changes: impl Fn(&String) -> Result<bool, Outcome>,
+) -> Result<(Vec<&'a String>, Vec<&'a String>), Outcome> {
+    let mut changed = vec![];
+    let mut unchanged = vec![];
+    for pattern in patterns {
+        if changes(pattern)? {
+            changed.push(pattern);
+        } else {
+            unchanged.push(pattern);
+        }
+    }
+    Ok((changed, unchanged))
+}
+
+fn quoted(patterns: &[&String]) -> String {
+    patterns
+        .iter()
+        .map(|pattern| format!("`{pattern}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
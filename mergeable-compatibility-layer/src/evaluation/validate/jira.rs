@@ -0,0 +1,66 @@
+use crate::configuration::options::Jira;
+
+use super::super::{
+    context::{Context, JiraIssue},
+    matcher::Input,
+    Outcome,
+};
+
+impl Jira {
+    /// The issue keys the regexes find in the values, in order of appearance.
+    pub(crate) fn keys<'a>(
+        &self,
+        values: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<String>, Outcome> {
+        let regexes = self.0.compile()?;
+        let mut keys: Vec<String> = vec![];
+        for value in values {
+            for regex in &regexes {
+                for found in regex.find_iter(value) {
+                    if !keys.iter().any(|key| key == found.as_str()) {
+                        keys.push(found.as_str().to_owned());
+                    }
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// The values have to mention an issue key, which has to exist in an allowed status if the
+    /// caller verified the keys.
+    pub(super) fn evaluate_input(&self, input: &Input, context: &Context) -> Outcome {
+        let keys = match self.keys(input.values.iter().copied()) {
+            Ok(keys) => keys,
+            Err(err) => return err,
+        };
+        let patterns = self.0.patterns().join("', '");
+        if keys.is_empty() {
+            return self.fail(|| format!("{} must reference '{patterns}'", input.name));
+        }
+        self.verify(&keys, context)
+            .unwrap_or_else(|| Outcome::pass(format!("{} references '{patterns}'", input.name)))
+    }
+
+    /// `None` unless the caller verified the keys against Jira.
+    pub(super) fn verify(&self, keys: &[String], context: &Context) -> Option<Outcome> {
+        let issues = context.jira_issues.as_ref()?;
+        let outcomes = keys.iter().map(|key| match issues.get(key) {
+            Some(JiraIssue::Valid) => Outcome::pass(format!("{key} exists in Jira")),
+            Some(JiraIssue::Missing) => self.fail(|| format!("{key} doesn't exist in Jira")),
+            Some(JiraIssue::Disallowed { status }) => {
+                self.fail(|| format!("{key} is {status} in Jira"))
+            }
+            None => Outcome::error(format!("{key} couldn't be verified in Jira")),
+        });
+        Outcome::all(outcomes)
+    }
+
+    /// Fails with the configured message if there is one.
+    pub(super) fn fail(&self, default: impl FnOnce() -> String) -> Outcome {
+        Outcome::fail(
+            self.0
+                .message()
+                .map_or_else(default, |message| message.as_str().to_owned()),
+        )
+    }
+}
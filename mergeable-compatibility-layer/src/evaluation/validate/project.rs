@@ -0,0 +1,30 @@
+use std::iter;
+
+use crate::configuration::validate::TheProject;
+
+use super::{
+    super::{context::Subject, matcher::Input, Outcome},
+    nothing_to_validate,
+};
+
+impl TheProject {
+    /// The regexes apply to the titles of the projects and the columns the item is in.
+    pub(super) fn evaluate(&self, subject: &Subject) -> Result<Outcome, Outcome> {
+        let projects = subject
+            .issue()
+            .projects
+            .as_deref()
+            .ok_or_else(|| Outcome::error("the projects are unavailable"))?;
+        let input = Input::many(
+            "projects",
+            projects.iter().flat_map(|project| {
+                iter::once(project.title.as_str()).chain(project.column.as_deref())
+            }),
+        );
+        Ok(self
+            .include
+            .as_ref()
+            .map(|include| include.evaluate(&input))
+            .unwrap_or_else(|| nothing_to_validate("project")))
+    }
+}
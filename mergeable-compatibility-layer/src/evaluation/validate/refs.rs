@@ -0,0 +1,60 @@
+use crate::configuration::validate::{TheBaseRef, TheHeadRef};
+
+use super::{
+    super::{
+        context::{Context, PullRequest},
+        matcher::Input,
+        Outcome,
+    },
+    nothing_to_validate,
+};
+
+impl TheBaseRef {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let pull_request = pull_request(context, "baseRef")?;
+        let base_ref = Input::single("base ref", &pull_request.base_ref);
+        let include = self
+            .include
+            .as_ref()
+            .map(|include| include.evaluate(&base_ref));
+        let exclude = self
+            .exclude
+            .as_ref()
+            .map(|exclude| exclude.evaluate(&base_ref));
+        let jira = self
+            .jira
+            .as_ref()
+            .map(|jira| jira.evaluate_input(&base_ref, context));
+        Ok(Outcome::all([include, exclude, jira].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("baseRef")))
+    }
+}
+
+impl TheHeadRef {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let pull_request = pull_request(context, "headRef")?;
+        let head_ref = Input::single("head ref", &pull_request.head_ref);
+        let include = self
+            .include
+            .as_ref()
+            .map(|include| include.evaluate(&head_ref));
+        let exclude = self
+            .exclude
+            .as_ref()
+            .map(|exclude| exclude.evaluate(&head_ref));
+        let jira = self
+            .jira
+            .as_ref()
+            .map(|jira| jira.evaluate_input(&head_ref, context));
+        Ok(Outcome::all([include, exclude, jira].into_iter().flatten())
+            .unwrap_or_else(|| nothing_to_validate("headRef")))
+    }
+}
+
+fn pull_request<'a>(context: &'a Context, kind: &str) -> Result<&'a PullRequest, Outcome> {
+    context
+        .subject
+        .as_ref()
+        .and_then(|subject| subject.pull_request())
+        .ok_or_else(|| Outcome::error(format!("the {kind} validator requires a pull request")))
+}
@@ -0,0 +1,105 @@
+use crate::configuration::{
+    basics::CountClause,
+    validate::{LinesChain, TheSize},
+};
+
+use self::comments::Syntax;
+use super::{
+    super::{
+        context::{ChangedFile, Context},
+        glob::Glob,
+        Outcome,
+    },
+    nothing_to_validate,
+};
+
+mod comments;
+
+impl TheSize {
+    pub(super) fn evaluate(&self, context: &Context) -> Result<Outcome, Outcome> {
+        let files = context
+            .subject
+            .as_ref()
+            .and_then(|subject| subject.pull_request())
+            .ok_or_else(|| Outcome::error("the size validator requires a pull request"))?
+            .files
+            .as_deref()
+            .ok_or_else(|| {
+                Outcome::error("the changed files of the pull request are unavailable")
+            })?;
+        let Some(lines) = &self.lines else {
+            return Ok(nothing_to_validate("size"));
+        };
+        let matched = self.r#match.as_ref().map(Glob::all).transpose()?;
+        let ignored = Glob::all(self.ignore.iter().flatten())?;
+        let ignore_comments = lines.ignore_comments == Some(true);
+
+        let mut count = Count::default();
+        for file in files {
+            let counted = matched.as_ref().map_or(true, |globs| {
+                globs.iter().any(|glob| glob.is_match(&file.filename))
+            }) && !ignored.iter().any(|glob| glob.is_match(&file.filename));
+            if counted {
+                count.add(Count::of(file, ignore_comments));
+            }
+        }
+        Ok(lines
+            .evaluate(count)
+            .unwrap_or_else(|| nothing_to_validate("size")))
+    }
+}
+
+/// Changed lines of one or more files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Count {
+    additions: u64,
+    deletions: u64,
+}
+
+impl Count {
+    /// Comments are only recognized in patches of known languages, other files count fully.
+    fn of(file: &ChangedFile, ignore_comments: bool) -> Self {
+        match (ignore_comments, &file.patch, Syntax::of(&file.filename)) {
+            (true, Some(patch), Some(syntax)) => syntax.count(patch),
+            _ => Count {
+                additions: file.additions,
+                deletions: file.deletions,
+            },
+        }
+    }
+
+    fn add(&mut self, other: Count) {
+        self.additions += other.additions;
+        self.deletions += other.deletions;
+    }
+}
+
+impl LinesChain {
+    fn evaluate(&self, count: Count) -> Option<Outcome> {
+        let total = count.additions + count.deletions;
+        // `max` is the older spelling of `total`
+        let clauses = [
+            ("total", self.total.as_ref(), total),
+            ("total", self.max.as_ref().map(|max| &max.0), total),
+            ("additions", self.additions.as_ref(), count.additions),
+            ("deletions", self.deletions.as_ref(), count.deletions),
+        ];
+        Outcome::all(
+            clauses
+                .into_iter()
+                .filter_map(|(name, clause, lines)| Some(clause?.at_most(name, lines))),
+        )
+    }
+}
+
+impl CountClause {
+    fn at_most(&self, name: &str, lines: u64) -> Outcome {
+        if lines <= self.count {
+            return Outcome::pass(format!("{name} is {lines} line(s)"));
+        }
+        Outcome::fail(self.message.as_ref().map_or_else(
+            || format!("{name} is {lines} line(s), expected at most {}", self.count),
+            |message| message.as_str().to_owned(),
+        ))
+    }
+}
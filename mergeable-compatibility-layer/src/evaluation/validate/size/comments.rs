@@ -0,0 +1,89 @@
+use super::Count;
+
+/// How comments are written in a language.
+pub(super) struct Syntax {
+    line: &'static str,
+    block: Option<(&'static str, &'static str)>,
+}
+
+const C_LIKE: Syntax = Syntax {
+    line: "//",
+    block: Some(("/*", "*/")),
+};
+
+const HASH: Syntax = Syntax {
+    line: "#",
+    block: None,
+};
+
+impl Syntax {
+    /// The syntax of a file by its extension, `None` for unknown languages.
+    pub(super) fn of(path: &str) -> Option<&'static Syntax> {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let (_, extension) = name.rsplit_once('.')?;
+        match extension {
+            "rs" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Some(&C_LIKE),
+            "py" | "yml" | "yaml" | "sh" | "bash" | "zsh" => Some(&HASH),
+            _ => None,
+        }
+    }
+
+    /// Counts the added and removed lines of a unified diff that hold more than comments.
+    pub(super) fn count(&self, patch: &str) -> Count {
+        let mut count = Count::default();
+        // whether the line before is within a block comment, on either side of the diff
+        let (mut added, mut removed) = (false, false);
+        for line in patch.lines() {
+            if line.starts_with("@@") {
+                (added, removed) = (false, false);
+            } else if let Some(code) = line.strip_prefix('+') {
+                if !self.is_comment(code, &mut added) {
+                    count.additions += 1;
+                }
+            } else if let Some(code) = line.strip_prefix('-') {
+                if !self.is_comment(code, &mut removed) {
+                    count.deletions += 1;
+                }
+            } else if let Some(code) = line.strip_prefix(' ') {
+                self.is_comment(code, &mut added);
+                self.is_comment(code, &mut removed);
+            }
+        }
+        count
+    }
+
+    /// Whether the line holds nothing but comments, tracking whether a block comment is open.
+    ///
+    /// Blank lines are comments within block comments only. Comment markers within strings
+    /// aren't told apart.
+    fn is_comment(&self, line: &str, in_block: &mut bool) -> bool {
+        let mut rest = line.trim();
+        if rest.is_empty() {
+            return *in_block;
+        }
+        let mut code = false;
+        loop {
+            if *in_block {
+                let (_, end) = self.block.expect("only block comments are opened");
+                let Some(index) = rest.find(end) else {
+                    return !code;
+                };
+                *in_block = false;
+                rest = &rest[index + end.len()..];
+            }
+            let line_comment = rest.find(self.line);
+            let block_comment = self
+                .block
+                .and_then(|(start, _)| Some((rest.find(start)?, start.len())));
+            match (line_comment, block_comment) {
+                (_, Some((index, length))) if line_comment.map_or(true, |line| index < line) => {
+                    code |= !rest[..index].trim().is_empty();
+                    *in_block = true;
+                    rest = &rest[index + length..];
+                }
+                (Some(index), _) => return !code && rest[..index].trim().is_empty(),
+                (None, _) => return !code && rest.trim().is_empty(),
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use chrono_tz::Tz;
+
+use crate::configuration::{
+    basics::OneOrMany,
+    validate::{DayOfWeek, HoursBetween, TheStale, TimeConstraint},
+};
+
+use super::super::{context::Context, Outcome, Verdict};
+
+/// The number of days without updates after which items are stale, as in mergeable.
+const DEFAULT_DAYS: u32 = 20;
+
+impl TheStale {
+    /// Passes with the stale issues and pull requests as the items the actions of the rule
+    /// apply to, the search itself is up to the caller.
+    pub(super) fn evaluate(&self, context: &Context) -> Verdict {
+        let query = match self.query(context) {
+            Ok(query) => query,
+            Err(err) => return Verdict::new("stale", err),
+        };
+        if !self.is_due(context.now()) {
+            let outcome = Outcome::pass("outside of the time constraint, the search is skipped");
+            return Verdict {
+                found: Some(vec![]),
+                ..Verdict::new("stale", outcome)
+            };
+        }
+        let Some(results) = context.search_results.get(&query) else {
+            return Verdict::error("stale", "the results of the stale search are unavailable");
+        };
+        let found: Vec<u64> = results
+            .iter()
+            .map(|subject| subject.issue().number)
+            .collect();
+        let outcome = Outcome::pass(format!(
+            "{} {} inactive for at least {} day(s)",
+            found.len(),
+            self.items()
+                .ok()
+                .flatten()
+                .map_or("issue(s) and pull request(s)", |(items, _)| items),
+            self.days(),
+        ));
+        Verdict {
+            found: Some(found),
+            ..Verdict::new("stale", outcome)
+        }
+    }
+
+    /// The GitHub search query for the open issues and pull requests of the repository that
+    /// weren't updated within `days`.
+    pub(crate) fn query(&self, context: &Context) -> Result<String, Outcome> {
+        let repository = &context.repository;
+        let updated = context.now() - Duration::days(i64::from(self.days()));
+        let mut query = format!(
+            "repo:{}/{} is:open updated:<{}",
+            repository.owner,
+            repository.name,
+            updated.format("%Y-%m-%d")
+        );
+        if let Some((_, qualifier)) = self.items()? {
+            query.push(' ');
+            query.push_str(qualifier);
+        }
+        let options = [
+            (self.ignore_drafts, "-is:draft"),
+            (self.ignore_milestones, "no:milestone"),
+            (self.ignore_projects, "no:project"),
+        ];
+        for (enabled, qualifier) in options {
+            if enabled == Some(true) {
+                query.push(' ');
+                query.push_str(qualifier);
+            }
+        }
+        if let Some(label) = &self.label {
+            let quoted = |label: &String| format!("\"{}\"", label.replace('"', ""));
+            let labels: Vec<String> = label.r#match.iter().flatten().map(quoted).collect();
+            if !labels.is_empty() {
+                query.push_str(&format!(" label:{}", labels.join(",")));
+            }
+            for ignored in label.ignore.iter().flatten() {
+                query.push_str(&format!(" -label:{}", quoted(ignored)));
+            }
+        }
+        Ok(query)
+    }
+
+    /// Outside of the time constraint there is nothing to search for.
+    pub(crate) fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.time_constraint
+            .as_ref()
+            .map_or(true, |constraint| constraint.allows(now))
+    }
+
+    fn days(&self) -> u32 {
+        self.days.unwrap_or(DEFAULT_DAYS)
+    }
+
+    /// The kind of items searched for with its qualifier, `None` for both kinds.
+    ///
+    /// `type` is a list or a comma separated string of `pull_request` and `issues`.
+    fn items(&self) -> Result<Option<(&'static str, &'static str)>, Outcome> {
+        let (mut pull_requests, mut issues) = (false, false);
+        for types in self.r#type.iter().flat_map(OneOrMany::iter) {
+            for r#type in types.split(',').map(str::trim) {
+                match r#type {
+                    "pull_request" => pull_requests = true,
+                    "issues" => issues = true,
+                    unknown => {
+                        return Err(Outcome::error(format!(
+                            "unknown type `{unknown}`, expected `pull_request` or `issues`"
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(match (pull_requests, issues) {
+            (true, false) => Some(("pull request(s)", "is:pr")),
+            (false, true) => Some(("issue(s)", "is:issue")),
+            _ => None,
+        })
+    }
+}
+
+impl TimeConstraint {
+    /// Whether the local time falls on one of the days and into the hours of the window.
+    fn allows(&self, now: DateTime<Utc>) -> bool {
+        let time_zone = self.time_zone.map_or(Tz::UTC, |time_zone| time_zone.0);
+        let local = now.with_timezone(&time_zone);
+        let hour = local.hour();
+        let hours = self
+            .hours_between
+            .map_or(true, |HoursBetween { start, end }| {
+                if start <= end {
+                    start <= hour && hour <= end
+                } else {
+                    start <= hour || hour <= end
+                }
+            });
+        let days = self
+            .days_of_week
+            .as_ref()
+            .map_or(true, |days| days.contains(&DayOfWeek(local.weekday())));
+        hours && days
+    }
+}
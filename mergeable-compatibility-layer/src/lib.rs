@@ -1 +1,3 @@
+pub mod codeowners;
 pub mod configuration;
+pub mod evaluation;
@@ -0,0 +1,244 @@
+use mergeable_compatibility_layer::{
+    codeowners::Owner,
+    configuration::Configuration,
+    evaluation::{
+        context::{Context, Issue, PullRequest, Review, ReviewState, Subject},
+        requirements::Requirements,
+        Status,
+    },
+};
+
+fn review(reviewer: &str, state: ReviewState) -> Review {
+    Review {
+        reviewer: reviewer.to_owned(),
+        state,
+    }
+}
+
+fn context(reviews: Vec<Review>) -> Context {
+    Context {
+        event: "pull_request_review".to_owned(),
+        action: Some("submitted".to_owned()),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            issue: Issue {
+                number: 1,
+                author: "octocat".to_owned(),
+                assignees: vec!["hubot".to_owned()],
+                ..Default::default()
+            },
+            requested_reviewers: vec!["monalisa".to_owned()],
+            reviews: Some(reviews),
+            ..Default::default()
+        }))),
+        ..Default::default()
+    }
+}
+
+fn evaluate(yaml: &str, context: &Context) -> (Status, String) {
+    let configuration: Configuration =
+        format!("version: 2\nmergeable:\n  - when: pull_request_review.*\n    validate:\n{yaml}")
+            .parse()
+            .unwrap();
+    let verdict = configuration.rules()[0].evaluate(context);
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+#[test]
+fn counts_the_latest_review_of_each_reviewer() {
+    let yaml = r#"
+      - do: approvals
+        min:
+          count: 2
+        exclude:
+          users: [dependabot]
+"#;
+    let reviews = vec![
+        review("alice", ReviewState::Approved),
+        review("bob", ReviewState::ChangesRequested),
+        review("Alice", ReviewState::Commented),
+        review("dependabot", ReviewState::Approved),
+        review("bob", ReviewState::Approved),
+        review("carol", ReviewState::Approved),
+        review("carol", ReviewState::Dismissed),
+    ];
+
+    assert_eq!(
+        evaluate(yaml, &context(reviews)),
+        (Status::Pass, "2 approval(s) counted".to_owned())
+    );
+    assert_eq!(
+        evaluate(
+            yaml,
+            &context(vec![review("dependabot", ReviewState::Approved)])
+        ),
+        (
+            Status::Fail,
+            "0 approval(s) counted, at least 2 required".to_owned()
+        )
+    );
+}
+
+#[test]
+fn names_missing_required_approvers() {
+    let yaml = r#"
+      - do: approvals
+        min:
+          count: 1
+        required:
+          reviewers: [alice, octocat]
+          assignees: true
+          requested_reviewers: true
+"#;
+    let reviews = vec![
+        review("alice", ReviewState::Approved),
+        review("bob", ReviewState::ChangesRequested),
+    ];
+
+    assert_eq!(
+        evaluate(yaml, &context(reviews)),
+        (
+            Status::Fail,
+            "approval required from hubot, monalisa".to_owned()
+        )
+    );
+}
+
+#[test]
+fn requires_the_pending_review_requests_only() {
+    let yaml = r#"
+      - do: approvals
+        min:
+          count: 0
+        required:
+          requested_reviewers: true
+"#;
+    // bob reviewed without being requested, monalisa was requested
+    let mut context = context(vec![review("bob", ReviewState::ChangesRequested)]);
+    assert_eq!(
+        evaluate(yaml, &context),
+        (Status::Fail, "approval required from monalisa".to_owned())
+    );
+
+    // once monalisa reviewed, GitHub no longer lists her as requested
+    let Some(Subject::PullRequest(pull_request)) = &mut context.subject else {
+        unreachable!("the context is built for a pull request")
+    };
+    pull_request.requested_reviewers.clear();
+    pull_request
+        .reviews
+        .as_mut()
+        .unwrap()
+        .push(review("monalisa", ReviewState::Commented));
+    assert_eq!(
+        evaluate(yaml, &context),
+        (
+            Status::Pass,
+            "0 approval(s) counted; all required reviewers approved".to_owned()
+        )
+    );
+}
+
+#[test]
+fn applies_limits_and_blocks() {
+    let yaml = r#"
+      - do: approvals
+        min:
+          count: 2
+          message: 'Two core approvals please'
+        limit:
+          teams: [octo/core]
+          users: [carol]
+        block:
+          changes_requested: true
+"#;
+    let reviews = vec![
+        review("alice", ReviewState::Approved),
+        review("bob", ReviewState::Approved),
+        review("dave", ReviewState::ChangesRequested),
+    ];
+    let mut context = context(reviews);
+
+    assert_eq!(evaluate(yaml, &context).0, Status::Error);
+
+    context
+        .teams
+        .insert("octo/core".to_owned(), vec!["Alice".to_owned()]);
+    assert_eq!(
+        evaluate(yaml, &context),
+        (
+            Status::Fail,
+            "Two core approvals please; changes requested by dave".to_owned()
+        )
+    );
+}
+
+#[test]
+fn requires_reviews_and_limiting_teams() {
+    let configuration: Configuration = r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: not
+        validate:
+          - do: approvals
+            min:
+              count: 1
+            limit:
+              teams: [octo/core]
+              owners: true
+"#
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        configuration.rules()[0].requirements(),
+        Requirements {
+            reviews: true,
+            code_owners: true,
+            teams: ["octo/core".to_owned()].into(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn requires_code_owner_approvals() {
+    let yaml = r#"
+      - do: approvals
+        min:
+          count: 1
+        required:
+          owners: true
+"#;
+    let mut context = context(vec![
+        review("alice", ReviewState::Approved),
+        review("dave", ReviewState::Approved),
+    ]);
+
+    assert_eq!(evaluate(yaml, &context).0, Status::Error);
+
+    let Some(Subject::PullRequest(pull_request)) = &mut context.subject else {
+        unreachable!()
+    };
+    pull_request.code_owners = Some(vec![
+        Owner::User("alice".to_owned()),
+        Owner::User("octocat".to_owned()),
+        Owner::Team("octo/docs".to_owned()),
+        Owner::Team("octo/core".to_owned()),
+        Owner::Email("eve@example.com".to_owned()),
+    ]);
+    context
+        .teams
+        .insert("octo/docs".to_owned(), vec!["Dave".to_owned()]);
+    assert_eq!(evaluate(yaml, &context).0, Status::Error);
+
+    context
+        .teams
+        .insert("octo/core".to_owned(), vec!["bob".to_owned()]);
+    assert_eq!(
+        evaluate(yaml, &context),
+        (Status::Fail, "approval required from octo/core".to_owned())
+    );
+}
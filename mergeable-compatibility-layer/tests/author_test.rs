@@ -0,0 +1,118 @@
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{Context, Issue, PullRequest, Subject},
+        Status,
+    },
+};
+
+const CONFIG: &str = r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    filter:
+      - do: author
+        must_exclude:
+          regex: '\[bot\]$'
+        team: org/staff
+    validate:
+      - do: author
+        team: org/backend
+      - do: assignee
+        min:
+          count: 1
+        max:
+          count: 2
+"#;
+
+fn context(author: &str, assignees: &[&str]) -> Context {
+    let mut context = Context {
+        event: "pull_request".to_owned(),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            issue: Issue {
+                author: author.to_owned(),
+                assignees: assignees.iter().map(|login| login.to_string()).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }))),
+        ..Default::default()
+    };
+    context.teams.insert(
+        "org/staff".to_owned(),
+        vec!["Octocat".to_owned(), "monalisa".to_owned()],
+    );
+    context
+        .teams
+        .insert("org/backend".to_owned(), vec!["octocat".to_owned()]);
+    context
+}
+
+fn messages(context: &Context) -> (bool, Vec<(Status, String)>) {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let verdict = configuration.rules()[0].evaluate(context);
+    let validators = verdict
+        .validators
+        .iter()
+        .map(|validator| (validator.status, validator.message.clone()))
+        .collect();
+    (verdict.is_applicable(), validators)
+}
+
+#[test]
+fn requires_team_membership_of_the_author() {
+    assert_eq!(
+        messages(&context("octocat", &["octocat"])),
+        (
+            true,
+            vec![
+                (Status::Pass, "author is a member of org/backend".to_owned()),
+                (Status::Pass, "assignees count is 1".to_owned()),
+            ]
+        )
+    );
+
+    let (applicable, validators) = messages(&context("monalisa", &[]));
+    assert!(applicable);
+    assert_eq!(
+        validators,
+        [
+            (
+                Status::Fail,
+                "author must be a member of org/backend".to_owned()
+            ),
+            (
+                Status::Fail,
+                "assignees count is 0, expected at least 1".to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn filters_authors_by_team() {
+    let (applicable, _) = messages(&context("hubot", &[]));
+    assert!(!applicable);
+
+    let mut unknown = context("octocat", &[]);
+    unknown.teams.clear();
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let verdict = configuration.rules()[0].evaluate(&unknown);
+    assert_eq!(verdict.filters[0].status, Status::Error);
+    assert_eq!(
+        verdict.filters[0].message,
+        "the members of team `org/staff` are unknown"
+    );
+}
+
+#[test]
+fn requires_the_members_of_referenced_teams() {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let teams: Vec<_> = configuration.rules()[0]
+        .requirements()
+        .teams
+        .into_iter()
+        .collect();
+
+    assert_eq!(teams, ["org/backend", "org/staff"]);
+}
@@ -0,0 +1,132 @@
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{ChangedFile, Context, FileStatus, PullRequest, Subject},
+        Status,
+    },
+};
+
+fn file(filename: &str, status: FileStatus) -> ChangedFile {
+    ChangedFile {
+        filename: filename.to_owned(),
+        status,
+        additions: 1,
+        deletions: 0,
+        patch: None,
+    }
+}
+
+fn context(files: Option<Vec<ChangedFile>>) -> Context {
+    Context {
+        event: "pull_request".to_owned(),
+        action: Some("synchronize".to_owned()),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            files,
+            ..Default::default()
+        }))),
+        ..Default::default()
+    }
+}
+
+fn evaluate(yaml: &str, context: &Context) -> (Status, String) {
+    let configuration: Configuration =
+        format!("version: 2\nmergeable:\n  - when: pull_request.*\n    validate:\n{yaml}")
+            .parse()
+            .unwrap();
+    let verdict = configuration.rules()[0].evaluate(context);
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+#[test]
+fn migrations_must_come_with_docs() {
+    let yaml = r#"
+      - do: changeset
+        or:
+          - must_exclude:
+              regex: ^migrations/
+          - must_include:
+              regex: ^docs/
+              message: 'Document the migration'
+"#;
+    let migration = file("migrations/0042_users.sql", FileStatus::Added);
+
+    assert_eq!(
+        evaluate(yaml, &context(Some(vec![migration.clone()]))).0,
+        Status::Fail
+    );
+    assert_eq!(
+        evaluate(
+            yaml,
+            &context(Some(vec![
+                migration,
+                file("docs/migrations.md", FileStatus::Modified)
+            ]))
+        )
+        .0,
+        Status::Pass
+    );
+    assert_eq!(
+        evaluate(
+            yaml,
+            &context(Some(vec![file("src/lib.rs", FileStatus::Modified)]))
+        )
+        .0,
+        Status::Pass
+    );
+}
+
+#[test]
+fn selects_files_by_status() {
+    let yaml = r#"
+      - do: changeset
+        files:
+          added: true
+          removed: true
+        must_exclude:
+          regex: \.lock$
+        max:
+          count: 1
+"#;
+    let files = vec![
+        file("Cargo.lock", FileStatus::Modified),
+        file("src/new.rs", FileStatus::Added),
+        file("src/renamed.rs", FileStatus::Renamed),
+    ];
+
+    assert_eq!(
+        evaluate(yaml, &context(Some(files.clone()))),
+        (
+            Status::Pass,
+            "changeset does exclude '\\.lock$'; changeset count is 1".to_owned()
+        )
+    );
+
+    let mut removed = files;
+    removed.push(file("src/old.rs", FileStatus::Removed));
+    assert_eq!(
+        evaluate(yaml, &context(Some(removed))),
+        (
+            Status::Fail,
+            "changeset count is 2, expected at most 1".to_owned()
+        )
+    );
+}
+
+#[test]
+fn requires_the_changed_files() {
+    let yaml = r#"
+      - do: changeset
+        min:
+          count: 1
+"#;
+
+    assert_eq!(evaluate(yaml, &context(None)).0, Status::Error);
+    assert_eq!(
+        evaluate(yaml, &context(Some(vec![]))),
+        (
+            Status::Fail,
+            "changeset count is 0, expected at least 1".to_owned()
+        )
+    );
+}
@@ -0,0 +1,96 @@
+use mergeable_compatibility_layer::codeowners::{CodeOwners, Owner};
+
+fn user(login: &str) -> Owner {
+    Owner::User(login.to_owned())
+}
+
+fn owners_of(codeowners: &str, path: &str) -> Vec<Owner> {
+    CodeOwners::parse(codeowners).owners_of(path).to_vec()
+}
+
+#[test]
+fn the_last_matching_rule_wins() {
+    let codeowners = r#"
+# the default owners
+*       @global-owner
+*.js    @js-owner  # inline comment
+/build/logs/ @doctocat
+docs/*  docs@example.com
+apps/   @octocat
+/scripts/ @doctocat @octo-org/scripts
+/apps/github
+\#notes @hash
+"#;
+
+    assert_eq!(owners_of(codeowners, "README.md"), [user("global-owner")]);
+    assert_eq!(owners_of(codeowners, "src/index.js"), [user("js-owner")]);
+    assert_eq!(
+        owners_of(codeowners, "build/logs/2024/run.log"),
+        [user("doctocat")]
+    );
+    assert_eq!(
+        owners_of(codeowners, "nested/build/logs/run.log"),
+        [user("global-owner")]
+    );
+    assert_eq!(
+        owners_of(codeowners, "docs/getting-started.md"),
+        [Owner::Email("docs@example.com".to_owned())]
+    );
+    assert_eq!(
+        owners_of(codeowners, "docs/build-app/troubleshooting.md"),
+        [user("global-owner")]
+    );
+    assert_eq!(owners_of(codeowners, "lib/apps/main.js"), [user("octocat")]);
+    assert_eq!(
+        owners_of(codeowners, "scripts/deploy.sh"),
+        [user("doctocat"), Owner::Team("octo-org/scripts".to_owned())]
+    );
+    assert_eq!(owners_of(codeowners, "apps/github/app.js"), []);
+    assert_eq!(owners_of(codeowners, "#notes"), [user("hash")]);
+}
+
+#[test]
+fn matches_double_asterisks_and_wildcards() {
+    let codeowners = r#"
+**/logs       @logs
+src/**/test?.rs @tests
+/vendor/**    @vendor
+"#;
+
+    assert_eq!(owners_of(codeowners, "logs/a.log"), [user("logs")]);
+    assert_eq!(owners_of(codeowners, "deep/er/logs/a.log"), [user("logs")]);
+    assert_eq!(owners_of(codeowners, "src/test1.rs"), [user("tests")]);
+    assert_eq!(owners_of(codeowners, "src/a/b/test2.rs"), [user("tests")]);
+    assert_eq!(owners_of(codeowners, "src/a/test12.rs"), []);
+    assert_eq!(owners_of(codeowners, "vendor/x/y.rs"), [user("vendor")]);
+    assert_eq!(owners_of(codeowners, "src/vendor/y.rs"), []);
+}
+
+#[test]
+fn skips_invalid_lines() {
+    let codeowners = r#"
+*            @fallback
+!excluded    @nobody
+[ab].txt     @nobody
+*.txt        not-an-owner
+*.md         @@broken
+"#;
+
+    for path in ["excluded", "a.txt", "README.md"] {
+        assert_eq!(owners_of(codeowners, path), [user("fallback")], "{path}");
+    }
+}
+
+#[test]
+fn collects_the_owners_of_changed_files() {
+    let codeowners = CodeOwners::parse("*.rs @rust @octo/core\n*.md @docs @rust\n");
+
+    assert_eq!(
+        codeowners.owners_for(["src/lib.rs", "README.md", "Cargo.toml"]),
+        [
+            user("rust"),
+            Owner::Team("octo/core".to_owned()),
+            user("docs")
+        ]
+    );
+}
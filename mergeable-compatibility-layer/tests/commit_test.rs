@@ -0,0 +1,158 @@
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{Commit, Context, PullRequest, Subject},
+        Status,
+    },
+};
+
+fn commit(sha: &str, message: &str) -> Commit {
+    Commit {
+        sha: sha.to_owned(),
+        message: message.to_owned(),
+        parents: 1,
+    }
+}
+
+fn merge(sha: &str, message: &str) -> Commit {
+    Commit {
+        parents: 2,
+        ..commit(sha, message)
+    }
+}
+
+fn context(commits: Vec<Commit>) -> Context {
+    Context {
+        event: "pull_request".to_owned(),
+        action: Some("synchronize".to_owned()),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            commits: Some(commits),
+            ..Default::default()
+        }))),
+        ..Default::default()
+    }
+}
+
+fn evaluate(yaml: &str, commits: Vec<Commit>) -> (Status, String) {
+    let configuration: Configuration =
+        format!("version: 2\nmergeable:\n  - when: pull_request.*\n    validate:\n{yaml}")
+            .parse()
+            .unwrap();
+    let verdict = configuration.rules()[0].evaluate(&context(commits));
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+fn commits() -> Vec<Commit> {
+    vec![
+        commit("1111111aaaa", "feat: first\n\nrefs ABC-1"),
+        merge("2222222bbbb", "Merge branch 'main' into feature"),
+        commit("3333333cccc", "wip"),
+    ]
+}
+
+#[test]
+fn reports_the_commits_not_matching() {
+    let yaml = r#"
+      - do: commit
+        message:
+          regex: ^(feat|fix)
+"#;
+
+    assert_eq!(
+        evaluate(yaml, commits()),
+        (
+            Status::Fail,
+            "commit message(s) of 3333333 must match '^(feat|fix)'".to_owned()
+        )
+    );
+    assert_eq!(
+        evaluate(
+            &yaml.replace("regex", "skip_merge: false\n          regex"),
+            commits()
+        )
+        .1,
+        "commit message(s) of 2222222, 3333333 must match '^(feat|fix)'"
+    );
+}
+
+#[test]
+fn skips_merge_commits_by_their_parents() {
+    let yaml = r#"
+      - do: commit
+        message:
+          regex: ^(feat|fix)
+"#;
+    let commits = vec![
+        commit("1111111aaaa", "Merge sort helper"),
+        merge("2222222bbbb", "fix: conflicts"),
+        merge("3333333cccc", "Merge pull request #1 from feature"),
+    ];
+
+    assert_eq!(
+        evaluate(yaml, commits),
+        (
+            Status::Fail,
+            "commit message(s) of 1111111 must match '^(feat|fix)'".to_owned()
+        )
+    );
+}
+
+#[test]
+fn selects_the_oldest_or_newest_commit() {
+    let yaml = |option: &str| {
+        format!(
+            r#"
+      - do: commit
+        message:
+          regex: ^feat
+          message: Use conventional commits
+          {option}: true
+"#
+        )
+    };
+
+    assert_eq!(evaluate(&yaml("oldest_only"), commits()).0, Status::Pass);
+    assert_eq!(
+        evaluate(&yaml("newest_only"), commits()),
+        (Status::Fail, "Use conventional commits".to_owned())
+    );
+    assert_eq!(
+        evaluate(&yaml("single_commit_only"), commits()).0,
+        Status::Pass
+    );
+    assert_eq!(
+        evaluate(&yaml("single_commit_only"), vec![commit("4444444", "wip")]).0,
+        Status::Fail
+    );
+}
+
+#[test]
+fn checks_the_title_or_body_and_jira_keys() {
+    let yaml = r#"
+      - do: commit
+        message:
+          regex: ^refs
+          message_type: body
+        jira:
+          regex: '[A-Z]+-\d+'
+          regex_flag: none
+"#;
+
+    assert_eq!(
+        evaluate(yaml, vec![commits().remove(0)]),
+        (
+            Status::Pass,
+            "commit messages match '^refs'; commit messages reference '[A-Z]+-\\d+'".to_owned()
+        )
+    );
+    assert_eq!(
+        evaluate(yaml, commits()).1,
+        "commit message(s) of 3333333 must match '^refs'; \
+         commit message(s) of 3333333 must reference '[A-Z]+-\\d+'"
+    );
+    assert_eq!(
+        evaluate(&yaml.replace("body", "subject"), commits()).0,
+        Status::Error
+    );
+}
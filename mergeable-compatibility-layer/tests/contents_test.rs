@@ -0,0 +1,240 @@
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{ChangedFile, Context, FileContent, FileStatus, PullRequest, Subject, TreeEntry},
+        Status,
+    },
+};
+
+fn file(path: &str, content: Option<&str>) -> FileContent {
+    FileContent {
+        path: path.to_owned(),
+        content: content.map(str::to_owned),
+    }
+}
+
+fn changed(filename: &str, status: FileStatus) -> ChangedFile {
+    ChangedFile {
+        filename: filename.to_owned(),
+        status,
+        additions: 0,
+        deletions: 0,
+        patch: None,
+    }
+}
+
+fn tree(paths: &[&str]) -> Vec<TreeEntry> {
+    paths
+        .iter()
+        .map(|path| TreeEntry {
+            path: (*path).to_owned(),
+            size: 100,
+        })
+        .collect()
+}
+
+fn configuration(yaml: &str) -> Configuration {
+    format!("version: 2\nmergeable:\n  - when: pull_request.*\n    validate:\n{yaml}")
+        .parse()
+        .unwrap()
+}
+
+/// A pull request modifying the files, which are all the files of the repository.
+fn context(contents: Vec<FileContent>) -> Context {
+    let paths: Vec<_> = contents.iter().map(|file| file.path.as_str()).collect();
+    Context {
+        event: "pull_request".to_owned(),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            files: Some(
+                paths
+                    .iter()
+                    .map(|path| changed(path, FileStatus::Modified))
+                    .collect(),
+            ),
+            tree: Some(tree(&paths)),
+            contents: Some(contents),
+            ..Default::default()
+        }))),
+        ..Default::default()
+    }
+}
+
+fn pull_request(context: &mut Context) -> &mut PullRequest {
+    match &mut context.subject {
+        Some(Subject::PullRequest(pull_request)) => pull_request,
+        _ => unreachable!(),
+    }
+}
+
+fn evaluate(yaml: &str, contents: Vec<FileContent>) -> (Status, String) {
+    let verdict = configuration(yaml).rules()[0].evaluate(&context(contents));
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+#[test]
+fn reports_the_files_failing() {
+    let yaml = r#"
+      - do: contents
+        files:
+          pr_diff: true
+        must_exclude:
+          regex: 'console\.log'
+        ends_with: "\n"
+"#;
+    let contents = vec![
+        file(".github/mergeable.yml", Some("console.log")),
+        file("src/ok.js", Some("export {};\n")),
+        file("src/debug.js", Some("console.log(1);\n")),
+        file("src/last.js", Some("export {};")),
+    ];
+
+    assert_eq!(
+        evaluate(yaml, contents),
+        (
+            Status::Fail,
+            "`src/debug.js`: content must exclude 'console\\.log'; \
+             `src/last.js`: content must end with '\n'"
+                .to_owned()
+        )
+    );
+}
+
+#[test]
+fn skips_ignored_files() {
+    let yaml = r#"
+      - do: contents
+        files:
+          pr_diff: true
+          ignore: ['**/*.lock', 'vendor/**', 'docs/{a,b}.md']
+        must_include:
+          regex: license
+"#;
+    let contents = vec![
+        file("Cargo.lock", Some("")),
+        file("crates/x/Cargo.lock", Some("")),
+        file("vendor/deep/lib.rs", None),
+        file("docs/a.md", Some("")),
+        file("src/lib.rs", Some("// License: MIT")),
+    ];
+
+    assert_eq!(
+        evaluate(yaml, contents.clone()),
+        (Status::Pass, "the content of 1 file(s) matches".to_owned())
+    );
+
+    let mut unavailable = contents;
+    unavailable.push(file("docs/c.md", None));
+    assert_eq!(
+        evaluate(yaml, unavailable),
+        (
+            Status::Pass,
+            "the content of 1 file(s) matches; skipped 1 file(s) without content".to_owned()
+        )
+    );
+}
+
+#[test]
+fn reads_the_files_of_the_repository_without_pr_diff() {
+    let yaml = r#"
+      - do: contents
+        must_include:
+          regex: license
+"#;
+    let configuration = configuration(yaml);
+    let rule = &configuration.rules()[0];
+    let mut context = context(vec![file("src/lib.rs", Some("// license"))]);
+    pull_request(&mut context).tree =
+        Some(tree(&[".github/mergeable.yml", "README.md", "src/lib.rs"]));
+
+    assert_eq!(
+        rule.content_paths(&context).into_iter().collect::<Vec<_>>(),
+        ["README.md", "src/lib.rs"]
+    );
+    let verdict = rule.evaluate(&context);
+    assert_eq!(verdict.status(), Status::Pass);
+    assert_eq!(
+        verdict.validators[0].message,
+        "the content of 1 file(s) matches; skipped 1 file(s) without content"
+    );
+
+    pull_request(&mut context).contents = Some(vec![
+        file("README.md", Some("MIT license")),
+        file("src/lib.rs", Some("// license")),
+    ]);
+    assert_eq!(
+        rule.evaluate(&context).validators[0].message,
+        "the content of 2 file(s) matches"
+    );
+
+    pull_request(&mut context).tree = None;
+    assert_eq!(
+        rule.evaluate(&context).validators[0].message,
+        "the files of the repository are unavailable"
+    );
+}
+
+#[test]
+fn reads_renamed_files_of_the_diff() {
+    let yaml = r#"
+      - do: contents
+        files:
+          pr_diff: true
+        must_include:
+          regex: license
+"#;
+    let configuration = configuration(yaml);
+    let mut context = context(vec![]);
+    pull_request(&mut context).files = Some(vec![
+        changed("src/new.rs", FileStatus::Renamed),
+        changed("src/added.rs", FileStatus::Added),
+        changed("src/gone.rs", FileStatus::Removed),
+    ]);
+
+    assert_eq!(
+        configuration.rules()[0]
+            .content_paths(&context)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        ["src/added.rs", "src/new.rs"]
+    );
+}
+
+#[test]
+fn skips_large_files_and_those_beyond_the_limit() {
+    let yaml = r#"
+      - do: contents
+        must_include:
+          regex: license
+"#;
+    let configuration = configuration(yaml);
+    let rule = &configuration.rules()[0];
+    let paths: Vec<String> = (0..FileContent::MAX_FILES + 20)
+        .map(|index| format!("src/{index:03}.rs"))
+        .collect();
+    let mut entries = tree(&paths.iter().map(String::as_str).collect::<Vec<_>>());
+    entries.push(TreeEntry {
+        path: "assets/huge.txt".to_owned(),
+        size: FileContent::MAX_SIZE + 1,
+    });
+    let mut context = context(vec![]);
+    pull_request(&mut context).tree = Some(entries);
+
+    let read = rule.content_paths(&context);
+    assert_eq!(read.len(), FileContent::MAX_FILES);
+    assert!(!read.contains("assets/huge.txt"));
+    assert!(!read.contains(&paths[FileContent::MAX_FILES]));
+
+    pull_request(&mut context).contents = Some(
+        read.iter()
+            .map(|path| file(path, Some("// license")))
+            .collect(),
+    );
+    let verdict = rule.evaluate(&context);
+    assert_eq!(verdict.status(), Status::Pass);
+    assert_eq!(
+        verdict.validators[0].message,
+        "the content of 100 file(s) matches; \
+         skipped 1 file(s) larger than 1 MiB, 20 file(s) beyond the first 100"
+    );
+}
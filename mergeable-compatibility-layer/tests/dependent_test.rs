@@ -0,0 +1,79 @@
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{ChangedFile, Context, FileStatus, PullRequest, Subject},
+        Status,
+    },
+};
+
+fn evaluate(yaml: &str, changed: &[&str]) -> (Status, String) {
+    let configuration: Configuration =
+        format!("version: 2\nmergeable:\n  - when: pull_request.*\n    validate:\n{yaml}")
+            .parse()
+            .unwrap();
+    let files = changed
+        .iter()
+        .map(|filename| ChangedFile {
+            filename: filename.to_string(),
+            status: FileStatus::Modified,
+            additions: 1,
+            deletions: 1,
+            patch: None,
+        })
+        .collect();
+    let context = Context {
+        event: "pull_request".to_owned(),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            files: Some(files),
+            ..Default::default()
+        }))),
+        ..Default::default()
+    };
+    let verdict = configuration.rules()[0].evaluate(&context);
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+#[test]
+fn requires_all_dependent_files_to_change_together() {
+    let yaml = r#"
+      - do: dependent
+        files: [Cargo.toml, Cargo.lock, CHANGELOG.md]
+"#;
+
+    assert_eq!(
+        evaluate(yaml, &["Cargo.toml", "src/lib.rs"]),
+        (
+            Status::Fail,
+            "`Cargo.lock`, `CHANGELOG.md` must change along with `Cargo.toml`".to_owned()
+        )
+    );
+    assert_eq!(evaluate(yaml, &["src/lib.rs"]).0, Status::Pass);
+    assert_eq!(
+        evaluate(yaml, &["CHANGELOG.md", "Cargo.lock", "Cargo.toml"]).0,
+        Status::Pass
+    );
+}
+
+#[test]
+fn requires_the_dependents_of_a_changed_file() {
+    let yaml = r#"
+      - do: dependent
+        changed:
+          file: '**/package.json'
+          files: [package-lock.json, docs/*.md]
+        message: Update the lock file and docs
+"#;
+
+    assert_eq!(
+        evaluate(yaml, &["web/package.json", "docs/setup.md"]),
+        (
+            Status::Fail,
+            "Update the lock file and docs: `package-lock.json`".to_owned()
+        )
+    );
+    assert_eq!(
+        evaluate(yaml, &["package-lock.json"]),
+        (Status::Pass, "`**/package.json` is unchanged".to_owned())
+    );
+}
@@ -0,0 +1,270 @@
+use mergeable_compatibility_layer::configuration::{
+    diagnostics::{Diagnostic, Severity},
+    parse::Location,
+    Configuration,
+};
+
+fn diagnostics(yaml: &str) -> Vec<Diagnostic> {
+    yaml.parse::<Configuration>().unwrap().validate()
+}
+
+#[test]
+fn accepts_sound_configurations() {
+    let found = diagnostics(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.opened, pull_request_review.*
+    name: titles
+    validate:
+      - do: title
+        must_exclude:
+          regex: ^wip
+      - do: or
+        validate:
+          - do: label
+            must_include:
+              regex: [ready, "^ok$"]
+              regex_flag: none
+    pass:
+      - do: merge
+        merge_method: squash
+"#,
+    );
+
+    assert_eq!(found, []);
+}
+
+#[test]
+fn reports_versions_regexes_and_merge_methods() {
+    let found = diagnostics(
+        r#"
+version: 3
+mergeable:
+  - when: pull_request.*
+    name: broken
+    validate:
+      - do: and
+        validate:
+          - do: title
+            must_include:
+              regex: "(unclosed"
+    pass:
+      - do: merge
+        merge_method: fast-forward
+"#,
+    );
+
+    assert_eq!(found.len(), 3, "{found:#?}");
+    assert!(found
+        .iter()
+        .all(|found| found.severity() == Severity::Error));
+    assert_eq!(found[0].key(), Some("version"));
+    assert_eq!(found[0].rule_index(), None);
+    assert_eq!(
+        (found[1].kind(), found[1].key()),
+        (Some("title"), Some("must_include"))
+    );
+    assert_eq!(found[1].rule_name(), Some("broken"));
+    assert_eq!(
+        found[2].to_string(),
+        "error, rule 0 `broken`, do: merge, key `merge_method`: \
+         unknown merge method `fast-forward`, expected one of `merge`, `squash`, `rebase`"
+    );
+}
+
+#[test]
+fn reports_regexes_wherever_they_are_held() {
+    let found = diagnostics(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    filter:
+      - do: repository
+        name:
+          or:
+            - must_include:
+                regex: "[unclosed"
+    validate:
+      - do: commit
+        message:
+          regex: "(unclosed"
+      - do: headRef
+        jira:
+          regex: "*"
+      - do: label
+        must_include:
+          regex: ready
+          regex_flag: q
+"#,
+    );
+
+    let keys: Vec<_> = found
+        .iter()
+        .map(|found| (found.severity(), found.kind(), found.key()))
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            (Severity::Error, Some("repository"), Some("must_include")),
+            (Severity::Error, Some("commit"), Some("regex")),
+            (Severity::Error, Some("headRef"), Some("jira")),
+            (Severity::Error, Some("label"), Some("must_include")),
+        ]
+    );
+    assert!(found[3].message().contains("unsupported regex_flag 'q'"));
+}
+
+#[test]
+fn rejects_payload_filters() {
+    let found = diagnostics(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    filter:
+      - do: not
+        filter:
+          - do: payload
+            pull_request:
+              draft:
+                boolean:
+                  match: true
+    validate:
+      - do: title
+        must_exclude:
+          regex: wip
+"#,
+    );
+
+    assert_eq!(found.len(), 1, "{found:#?}");
+    assert_eq!(
+        found[0].to_string(),
+        "error, rule 0, do: payload, key `do`: \
+         the `payload` filter isn't supported, the rule never applies"
+    );
+}
+
+#[test]
+fn warns_about_unsupported_events() {
+    let found = diagnostics(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*, schedule.repository
+    validate:
+      - do: stale
+        days: 20
+      - do: not
+        validate:
+          - do: title
+            must_include:
+              regex: feat
+    fail:
+      - do: request_review
+        reviewers: [octocat]
+  - validate:
+      - do: size
+        lines:
+          max:
+            count: 500
+"#,
+    );
+
+    let messages: Vec<_> = found
+        .iter()
+        .map(|found| (found.rule_index(), found.kind(), found.severity()))
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            (Some(0), Some("stale"), Severity::Warning),
+            (Some(0), Some("title"), Severity::Warning),
+            (Some(0), Some("request_review"), Severity::Warning),
+            (Some(1), None, Severity::Warning),
+        ]
+    );
+    assert_eq!(
+        found[0].message(),
+        "`stale` doesn't support `pull_request.*`, only `schedule.repository`"
+    );
+}
+
+#[test]
+fn warns_about_approvals_requiring_nobody() {
+    let found = diagnostics(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: approvals
+        min:
+          count: 1
+        required:
+          reviewers: []
+          owners: false
+"#,
+    );
+
+    let keys: Vec<_> = found.iter().map(Diagnostic::key).collect();
+    assert_eq!(keys, [Some("reviewers"), Some("required")]);
+    assert!(found
+        .iter()
+        .all(|found| found.severity() == Severity::Warning));
+}
+
+#[test]
+fn locates_diagnostics_in_the_source() {
+    let yaml = r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: and
+        validate:
+          - do: title
+            must_include:
+              regex: "(unclosed"
+      - do: stale
+        days: 20
+    pass:
+      - do: merge
+        merge_method: fast-forward
+  - name: never
+"#;
+    let found = diagnostics(yaml);
+
+    let lines: Vec<_> = found
+        .iter()
+        .map(|found| found.locate(yaml).map(|location| location.line))
+        .collect();
+    assert_eq!(lines, [Some(9), Some(11), Some(15), Some(16)]);
+    assert_eq!(
+        found[2].locate(yaml),
+        Some(Location {
+            line: 15,
+            column: 9
+        })
+    );
+}
+
+#[test]
+fn locates_diagnostics_in_flow_collections() {
+    let yaml = r#"
+version: 2
+mergeable:
+  - { when: "pull_request.*", validate: [{ do: label }, { do: title, jira: "(" }] }
+"#;
+    let found = diagnostics(yaml);
+
+    assert_eq!(found.len(), 1, "{found:#?}");
+    assert_eq!(
+        found[0].locate(yaml),
+        Some(Location {
+            line: 4,
+            column: 70
+        })
+    );
+}
@@ -0,0 +1,176 @@
+use chrono::{DateTime, Utc};
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{Context, Issue, PullRequest, Repository, Subject},
+        Status,
+    },
+};
+
+const CONFIG: &str = r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    name: conventional pull requests
+    filter:
+      - do: repository
+        visibility: public
+    validate:
+      - do: title
+        must_exclude:
+          regex: '^wip'
+          message: 'Work in progress!'
+        begins_with:
+          match: ['feat', 'fix']
+      - do: label
+        no_empty:
+          enabled: true
+      - do: or
+        validate:
+          - do: description
+            must_include:
+              regex: 'closes #\d+'
+          - do: milestone
+            no_empty:
+              enabled: true
+"#;
+
+fn pull_request(title: &str, labels: &[&str]) -> Context {
+    Context {
+        event: "pull_request".to_owned(),
+        action: Some("opened".to_owned()),
+        repository: Repository {
+            owner: "octo".to_owned(),
+            name: "warden".to_owned(),
+            private: false,
+            topics: vec![],
+        },
+        sender: Some("octocat".to_owned()),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            issue: Issue {
+                number: 1,
+                title: title.to_owned(),
+                body: Some("Closes #12".to_owned()),
+                author: "octocat".to_owned(),
+                labels: labels.iter().map(|label| label.to_string()).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }))),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn passing_rule() {
+    let config: Configuration = serde_yaml::from_str(CONFIG).unwrap();
+    let verdict = config.rules()[0].evaluate(&pull_request("feat: evaluate", &["enhancement"]));
+
+    assert!(verdict.is_applicable());
+    assert_eq!(verdict.status(), Status::Pass, "{verdict:#?}");
+}
+
+#[test]
+fn failing_validators_render_configured_messages() {
+    let config: Configuration = serde_yaml::from_str(CONFIG).unwrap();
+    let verdict = config.rules()[0].evaluate(&pull_request("WIP feat: evaluate", &[]));
+
+    assert_eq!(verdict.status(), Status::Fail);
+    let statuses: Vec<_> = verdict
+        .validators
+        .iter()
+        .map(|validator| (validator.kind, validator.status, validator.message.as_str()))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            (
+                "title",
+                Status::Fail,
+                "Work in progress!; title must begin with 'feat', 'fix'"
+            ),
+            ("label", Status::Fail, "labels can't be empty"),
+            (
+                "or",
+                Status::Pass,
+                "description does include 'closes #\\d+'"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn failing_filter_makes_rule_inapplicable() {
+    let config: Configuration = serde_yaml::from_str(CONFIG).unwrap();
+    let mut context = pull_request("feat: evaluate", &["enhancement"]);
+    context.repository.private = true;
+
+    assert!(!config.rules()[0].evaluate(&context).is_applicable());
+}
+
+#[test]
+fn validators_report_an_error_without_their_data() {
+    let config: Configuration = serde_yaml::from_str(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: project
+        must_include:
+          regex: 'type|chore|wont'
+"#,
+    )
+    .unwrap();
+    let verdict = config.rules()[0].evaluate(&pull_request("feat: evaluate", &[]));
+
+    assert_eq!(verdict.status(), Status::Error);
+    assert_eq!(
+        verdict.validators[0].message,
+        "the projects are unavailable"
+    );
+}
+
+#[test]
+fn age_counts_whole_days_between_instants() {
+    let config: Configuration = serde_yaml::from_str(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: age
+        created_at:
+          days: 14
+        updated_at:
+          days: 2
+          message: 'Let the pull request rest for a while'
+"#,
+    )
+    .unwrap();
+    let timestamp = |rfc3339| {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let mut context = pull_request("feat: evaluate", &[]);
+    context.now = Some(timestamp("2024-03-31T01:30:00+01:00"));
+    let Some(Subject::PullRequest(pull_request)) = &mut context.subject else {
+        unreachable!()
+    };
+    // 14 days and 30 minutes before now, despite the differing offsets
+    pull_request.issue.created_at = Some(timestamp("2024-03-17T02:00:00+02:00"));
+    // a minute short of two days
+    pull_request.issue.updated_at = Some(timestamp("2024-03-29T00:31:00Z"));
+
+    let verdict = config.rules()[0].evaluate(&context);
+
+    assert_eq!(verdict.status(), Status::Fail);
+    assert_eq!(
+        verdict.validators[0].message,
+        "Let the pull request rest for a while"
+    );
+
+    context.now = Some(timestamp("2024-03-31T00:31:00Z"));
+    assert_eq!(config.rules()[0].evaluate(&context).status(), Status::Pass);
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{Commit, Context, Issue, JiraIssue, PullRequest, Subject},
+        Status,
+    },
+};
+
+const CONFIG: &str = r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: title
+        jira:
+          regex: '[A-Z][A-Z0-9]+-\d+'
+          regex_flag: none
+      - do: commit
+        jira:
+          regex: '[A-Z][A-Z0-9]+-\d+'
+          regex_flag: none
+          message: 'The Jira ticket does not exist'
+"#;
+
+fn context(title: &str, commits: &[&str]) -> Context {
+    Context {
+        event: "pull_request".to_owned(),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            issue: Issue {
+                title: title.to_owned(),
+                ..Default::default()
+            },
+            commits: Some(
+                commits
+                    .iter()
+                    .enumerate()
+                    .map(|(index, message)| Commit {
+                        sha: format!("{index:07}"),
+                        message: message.to_string(),
+                        // the merge commits of these tests say so
+                        parents: if message.starts_with("Merge") { 2 } else { 1 },
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }))),
+        ..Default::default()
+    }
+}
+
+fn messages(context: &Context) -> Vec<(Status, String)> {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    configuration.rules()[0]
+        .evaluate(context)
+        .validators
+        .iter()
+        .map(|validator| (validator.status, validator.message.clone()))
+        .collect()
+}
+
+#[test]
+fn requires_issue_keys_without_verification() {
+    assert_eq!(
+        messages(&context(
+            "OPS-12: rotate keys",
+            &["OPS-12 rotate", "Merge main"]
+        )),
+        [
+            (
+                Status::Pass,
+                "title references '[A-Z][A-Z0-9]+-\\d+'".to_owned()
+            ),
+            (
+                Status::Pass,
+                "commit messages reference '[A-Z][A-Z0-9]+-\\d+'".to_owned()
+            ),
+        ]
+    );
+    assert_eq!(
+        messages(&context("rotate keys", &["ops-12 rotate"])),
+        [
+            (
+                Status::Fail,
+                "title must reference '[A-Z][A-Z0-9]+-\\d+'".to_owned()
+            ),
+            (Status::Fail, "The Jira ticket does not exist".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn verifies_the_extracted_keys() {
+    let mut context = context("OPS-12, OPS-13: rotate keys", &["OPS-14 rotate"]);
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let keys: Vec<_> = configuration.rules()[0]
+        .jira_keys(&context)
+        .into_iter()
+        .collect();
+    assert_eq!(keys, ["OPS-12", "OPS-13", "OPS-14"]);
+
+    context.jira_issues = Some(HashMap::from([
+        ("OPS-12".to_owned(), JiraIssue::Valid),
+        (
+            "OPS-13".to_owned(),
+            JiraIssue::Disallowed {
+                status: "Done".to_owned(),
+            },
+        ),
+        ("OPS-14".to_owned(), JiraIssue::Missing),
+    ]));
+    assert_eq!(
+        messages(&context),
+        [
+            (Status::Fail, "OPS-13 is Done in Jira".to_owned()),
+            (Status::Fail, "The Jira ticket does not exist".to_owned()),
+        ]
+    );
+
+    context.jira_issues = Some(HashMap::from([("OPS-14".to_owned(), JiraIssue::Valid)]));
+    assert_eq!(
+        messages(&context),
+        [
+            (
+                Status::Error,
+                "OPS-12 couldn't be verified in Jira; OPS-13 couldn't be verified in Jira"
+                    .to_owned()
+            ),
+            (Status::Pass, "OPS-14 exists in Jira".to_owned()),
+        ]
+    );
+}
@@ -0,0 +1,204 @@
+use mergeable_compatibility_layer::configuration::{
+    parse::{Location, ParseError},
+    Configuration,
+};
+
+fn parse_error(yaml: &str) -> ParseError {
+    yaml.parse::<Configuration>().unwrap_err()
+}
+
+#[test]
+fn points_at_invalid_values_of_validators() {
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    name: reviews
+    validate:
+      - do: approvals
+        min:
+          count: many
+"#,
+    );
+
+    assert_eq!(
+        error.location(),
+        Some(Location {
+            line: 9,
+            column: 18
+        })
+    );
+    assert_eq!(error.rule_index(), Some(0));
+    assert_eq!(error.rule_name(), Some("reviews"));
+    assert_eq!(error.kind(), Some("approvals"));
+    assert_eq!(error.key(), Some("count"));
+    assert_eq!(
+        error.to_string(),
+        "line 9 column 18, rule 0 `reviews`, do: approvals, key `count`: \
+         invalid type: string \"many\", expected u64"
+    );
+}
+
+#[test]
+fn names_unknown_keys_of_clauses() {
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    name: titles
+    validate:
+      - do: label
+        must_include:
+          regex: wip
+      - do: title
+        must_exclude:
+          regexp: ^wip
+"#,
+    );
+
+    // flattened clauses are attributed to the validator holding them
+    assert_eq!(
+        error.location(),
+        Some(Location {
+            line: 10,
+            column: 9
+        })
+    );
+    assert_eq!(error.kind(), Some("title"));
+    assert_eq!(error.key(), Some("regexp"));
+    assert_eq!(
+        error.message(),
+        "unknown field `regexp`, expected one of `regex`, `regex_flag`, `message`"
+    );
+}
+
+#[test]
+fn restricts_chains_to_the_options_of_their_kind() {
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: author
+        and:
+          - must_include:
+              regex: octocat
+          - begins_with:
+              match: octo
+"#,
+    );
+
+    assert_eq!(error.kind(), Some("author"));
+    assert_eq!(error.key(), Some("begins_with"));
+    assert_eq!(
+        error.message(),
+        "unknown field `begins_with`, expected one of `and`, `or`, `must_include`, `must_exclude`"
+    );
+}
+
+#[test]
+fn names_unknown_validators_and_invalid_events() {
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: titel
+"#,
+    );
+    assert_eq!(error.rule_index(), Some(0));
+    assert_eq!(error.rule_name(), None);
+    assert_eq!(error.kind(), Some("titel"));
+    assert_eq!(error.key(), Some("do"));
+    assert!(error.message().starts_with("unknown variant `titel`"));
+
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.opened
+    name: first
+  - when: pull_request..opened
+    name: second
+"#,
+    );
+    assert_eq!(
+        error.location(),
+        Some(Location {
+            line: 6,
+            column: 11
+        })
+    );
+    assert_eq!(error.rule_index(), Some(1));
+    assert_eq!(error.rule_name(), Some("second"));
+    assert_eq!(error.kind(), None);
+    assert_eq!(error.key(), Some("when"));
+}
+
+#[test]
+fn reports_the_location_of_yaml_syntax_errors() {
+    let error = parse_error("version: 2\n\tmergeable: []\n");
+
+    assert_eq!(error.location(), Some(Location { line: 2, column: 1 }));
+    assert_eq!(error.rule_index(), None);
+    assert_eq!(error.key(), None);
+}
+
+#[test]
+fn checks_time_constraints_when_loading() {
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        time_constraint:
+          time_zone: 'America/Springfield'
+"#,
+    );
+    assert_eq!(error.key(), Some("time_zone"));
+    assert_eq!(
+        error.message(),
+        "invalid value: string \"America/Springfield\", \
+         expected an IANA time zone name like `America/Los_Angeles`"
+    );
+
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        time_constraint:
+          hours_between: ['9', '25']
+"#,
+    );
+    assert_eq!(error.key(), Some("hours_between"));
+    assert_eq!(
+        error.message(),
+        "invalid value: string \"25\", expected an hour between 0 and 23"
+    );
+
+    let error = parse_error(
+        r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        time_constraint:
+          days_of_week: ['Mon', 'Caturday']
+"#,
+    );
+    assert_eq!(error.key(), Some("days_of_week"));
+    assert_eq!(
+        error.message(),
+        "invalid value: string \"Caturday\", expected a weekday like `Mon`"
+    );
+}
@@ -0,0 +1,100 @@
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{Context, Issue, Project, PullRequest, Subject},
+        Status,
+    },
+};
+
+const CONFIG: &str = r#"
+version: 2
+mergeable:
+  - when: pull_request.*, issues.*
+    validate:
+      - do: project
+        must_include:
+          regex: '^Sprint \d+$'
+          message: 'add it to the sprint board'
+"#;
+
+fn pull_request(projects: Option<Vec<Project>>) -> Context {
+    Context {
+        event: "pull_request".to_owned(),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            issue: Issue {
+                projects,
+                ..Default::default()
+            },
+            ..Default::default()
+        }))),
+        ..Default::default()
+    }
+}
+
+fn project(title: &str, column: Option<&str>) -> Project {
+    Project {
+        title: title.to_owned(),
+        column: column.map(str::to_owned),
+    }
+}
+
+fn evaluate(context: &Context) -> (Status, String) {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let verdict = configuration.rules()[0].evaluate(context);
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+#[test]
+fn requires_a_matching_project() {
+    let (status, _) = evaluate(&pull_request(Some(vec![
+        project("Roadmap", None),
+        project("Sprint 12", Some("In review")),
+    ])));
+    assert_eq!(status, Status::Pass);
+
+    assert_eq!(
+        evaluate(&pull_request(Some(vec![project("Roadmap", Some("Later"))]))),
+        (Status::Fail, "add it to the sprint board".to_owned())
+    );
+    assert_eq!(evaluate(&pull_request(Some(vec![]))).0, Status::Fail);
+}
+
+#[test]
+fn matches_the_columns_of_projects() {
+    let configuration: Configuration = r#"
+version: 2
+mergeable:
+  - when: issues.*
+    validate:
+      - do: project
+        must_include:
+          regex: '^Ready$'
+"#
+    .parse()
+    .unwrap();
+    let context = Context {
+        event: "issues".to_owned(),
+        subject: Some(Subject::Issue(Issue {
+            projects: Some(vec![project("Backlog", Some("Ready"))]),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        configuration.rules()[0].evaluate(&context).status(),
+        Status::Pass
+    );
+}
+
+#[test]
+fn requires_the_projects_of_the_subject() {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    assert!(configuration.rules()[0].requirements().projects);
+
+    assert_eq!(
+        evaluate(&pull_request(None)),
+        (Status::Error, "the projects are unavailable".to_owned())
+    );
+}
@@ -0,0 +1,163 @@
+use mergeable_compatibility_layer::{
+    configuration::{diagnostics::Severity, Configuration},
+    evaluation::{
+        context::{Context, Issue, PullRequest, Subject},
+        Status,
+    },
+};
+
+fn evaluate(yaml: &str, subject: Option<Subject>) -> (Status, String) {
+    let configuration: Configuration =
+        format!("version: 2\nmergeable:\n  - when: check_suite.*\n    validate:\n{yaml}")
+            .parse()
+            .unwrap();
+    let context = Context {
+        event: "check_suite".to_owned(),
+        subject,
+        ..Default::default()
+    };
+    let verdict = configuration.rules()[0].evaluate(&context);
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+fn pull_request(base_ref: &str, head_ref: &str) -> Option<Subject> {
+    Some(Subject::PullRequest(Box::new(PullRequest {
+        base_ref: base_ref.to_owned(),
+        head_ref: head_ref.to_owned(),
+        ..Default::default()
+    })))
+}
+
+#[test]
+fn matches_the_base_ref() {
+    let yaml = r#"
+      - do: baseRef
+        must_exclude:
+          regex: '^release/'
+          message: 'Merge releases through the release process'
+        mediaType:
+          previews: [groot]
+"#;
+
+    assert_eq!(
+        evaluate(yaml, pull_request("main", "feature")),
+        (Status::Pass, "base ref does exclude '^release/'".to_owned())
+    );
+    assert_eq!(
+        evaluate(yaml, pull_request("release/1.0", "feature")),
+        (
+            Status::Fail,
+            "Merge releases through the release process".to_owned()
+        )
+    );
+    assert_eq!(
+        evaluate(
+            yaml,
+            Some(Subject::Issue(Issue {
+                number: 1,
+                ..Default::default()
+            }))
+        ),
+        (
+            Status::Error,
+            "the baseRef validator requires a pull request".to_owned()
+        )
+    );
+}
+
+#[test]
+fn matches_the_head_ref_and_its_issue_key() {
+    let yaml = r#"
+      - do: headRef
+        must_include:
+          regex: '^(feature|hotfix)/'
+        jira:
+          regex: '[A-Z][A-Z0-9]+-\d+'
+          regex_flag: none
+"#;
+
+    assert_eq!(
+        evaluate(yaml, pull_request("main", "feature/CORE-12-login")),
+        (
+            Status::Pass,
+            "head ref does include '^(feature|hotfix)/'; head ref references '[A-Z][A-Z0-9]+-\\d+'"
+                .to_owned()
+        )
+    );
+    assert_eq!(
+        evaluate(yaml, pull_request("main", "feature/login")),
+        (
+            Status::Fail,
+            "head ref must reference '[A-Z][A-Z0-9]+-\\d+'".to_owned()
+        )
+    );
+}
+
+#[test]
+fn matches_the_issue_key_of_the_base_ref() {
+    let yaml = r#"
+      - do: baseRef
+        jira:
+          regex: '[A-Z][A-Z0-9]+-\d+'
+          regex_flag: none
+"#;
+
+    assert_eq!(
+        evaluate(yaml, pull_request("release/CORE-3", "feature")),
+        (
+            Status::Pass,
+            "base ref references '[A-Z][A-Z0-9]+-\\d+'".to_owned()
+        )
+    );
+    assert_eq!(
+        evaluate(yaml, pull_request("main", "feature")).0,
+        Status::Fail
+    );
+}
+
+#[test]
+fn warns_that_media_types_have_no_effect() {
+    let configuration: Configuration = r#"
+version: 2
+mergeable:
+  - when: status.*
+    validate:
+      - do: baseRef
+        must_include:
+          regex: main
+        mediaType:
+          previews: [groot]
+"#
+    .parse()
+    .unwrap();
+    let found = configuration.validate();
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].severity(), Severity::Warning);
+    assert_eq!(found[0].key(), Some("mediaType"));
+    assert_eq!(
+        found[0].message(),
+        "`mediaType` has no effect, GitHub graduated the API previews"
+    );
+}
+
+#[test]
+fn rejects_untyped_media_types() {
+    let error = r#"
+version: 2
+mergeable:
+  - when: status.*
+    validate:
+      - do: baseRef
+        mediaType:
+          preview: groot
+"#
+    .parse::<Configuration>()
+    .unwrap_err();
+
+    assert!(
+        error.to_string().contains("unknown field `preview`"),
+        "{error}"
+    );
+}
@@ -0,0 +1,146 @@
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{ChangedFile, Context, FileStatus, PullRequest, Subject},
+        Status,
+    },
+};
+
+fn file(filename: &str, additions: u64, deletions: u64, patch: Option<&str>) -> ChangedFile {
+    ChangedFile {
+        filename: filename.to_owned(),
+        status: FileStatus::Modified,
+        additions,
+        deletions,
+        patch: patch.map(str::to_owned),
+    }
+}
+
+fn evaluate(yaml: &str, files: Vec<ChangedFile>) -> (Status, String) {
+    let configuration: Configuration =
+        format!("version: 2\nmergeable:\n  - when: pull_request.*\n    validate:\n{yaml}")
+            .parse()
+            .unwrap();
+    let context = Context {
+        event: "pull_request".to_owned(),
+        subject: Some(Subject::PullRequest(Box::new(PullRequest {
+            files: Some(files),
+            ..Default::default()
+        }))),
+        ..Default::default()
+    };
+    let verdict = configuration.rules()[0].evaluate(&context);
+    let validator = &verdict.validators[0];
+    (validator.status, validator.message.clone())
+}
+
+#[test]
+fn sums_the_lines_of_matched_files() {
+    let yaml = r#"
+      - do: size
+        match: ['src/**']
+        ignore: ['src/**/*.snap']
+        lines:
+          total:
+            count: 100
+            message: Split the change
+          additions:
+            count: 60
+          deletions:
+            count: 50
+"#;
+    let files = vec![
+        file("src/lib.rs", 40, 10, None),
+        file("src/ui/view.rs", 30, 5, None),
+        file("src/ui/__snapshots__/view.snap", 500, 500, None),
+        file("Cargo.lock", 900, 0, None),
+    ];
+
+    assert_eq!(
+        evaluate(yaml, files.clone()),
+        (
+            Status::Fail,
+            "additions is 70 line(s), expected at most 60".to_owned()
+        )
+    );
+
+    let mut large = files;
+    large.push(file("src/big.rs", 0, 30, None));
+    assert_eq!(
+        evaluate(yaml, large).1,
+        "Split the change; additions is 70 line(s), expected at most 60"
+    );
+}
+
+#[test]
+fn ignores_comment_only_lines() {
+    let yaml = |ignore_comments: bool| {
+        format!(
+            r#"
+      - do: size
+        lines:
+          max:
+            count: 5
+          ignore_comments: {ignore_comments}
+"#
+        )
+    };
+    let rust = "@@ -1,4 +1,8 @@
+ fn main() {
++    // a comment
++    /* a block
++
++       spanning lines */
++    let x = 1; // trailing
+-    /// removed doc
+-    println!();
+ }
+\\ No newline at end of file";
+    let python = "@@ -1 +1,2 @@
++# comment
++value = 1";
+    let files = vec![
+        file("src/main.rs", 5, 2, Some(rust)),
+        file("tool.py", 2, 0, Some(python)),
+        file("notes.txt", 1, 0, Some("@@ -0,0 +1 @@\n+# not a comment")),
+        file("src/huge.rs", 1, 0, None),
+    ];
+
+    assert_eq!(
+        evaluate(&yaml(false), files.clone()),
+        (
+            Status::Fail,
+            "total is 11 line(s), expected at most 5".to_owned()
+        )
+    );
+    assert_eq!(
+        evaluate(&yaml(true), files),
+        (Status::Pass, "total is 5 line(s)".to_owned())
+    );
+}
+
+#[test]
+fn tracks_block_comments_opened_after_code() {
+    let yaml = r#"
+      - do: size
+        lines:
+          max:
+            count: 5
+          ignore_comments: true
+"#;
+    let rust = "@@ -1,2 +1,7 @@
+ fn main() {
++    let x = 1; /* opened after code
++       still a comment
++       closed before code */ let y = 2;
++    let z = 3; /* closed */ // trailing
++    /* one */ /* two */
++    // a comment /* not opening a block
+ }";
+    let files = vec![file("src/main.rs", 6, 0, Some(rust))];
+
+    assert_eq!(
+        evaluate(yaml, files),
+        (Status::Pass, "total is 3 line(s)".to_owned())
+    );
+}
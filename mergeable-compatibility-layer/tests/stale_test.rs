@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use mergeable_compatibility_layer::{
+    configuration::Configuration,
+    evaluation::{
+        context::{Context, Issue, Repository, Subject},
+        Status,
+    },
+};
+
+const CONFIG: &str = r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        days: 10
+        type: pull_request, issues
+        ignore_drafts: true
+        label:
+          match: ['bug', 'needs info']
+          ignore: ['pinned']
+    pass:
+      - do: comment
+        payload:
+          body: This is stale
+"#;
+
+fn context() -> Context {
+    Context {
+        event: "schedule".to_owned(),
+        action: Some("repository".to_owned()),
+        repository: Repository {
+            owner: "octo".to_owned(),
+            name: "warden".to_owned(),
+            ..Default::default()
+        },
+        now: Some("2024-03-11T12:00:00Z".parse::<DateTime<Utc>>().unwrap()),
+        ..Default::default()
+    }
+}
+
+fn issue(number: u64) -> Subject {
+    Subject::Issue(Issue {
+        number,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn searches_for_inactive_items() {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let searches: Vec<_> = configuration.rules()[0]
+        .searches(&context())
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        searches,
+        [
+            r#"repo:octo/warden is:open updated:<2024-03-01 -is:draft label:"bug","needs info" -label:"pinned""#
+        ]
+    );
+}
+
+#[test]
+fn applies_the_actions_to_the_found_items() {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let rule = &configuration.rules()[0];
+    let mut context = context();
+    let query = rule.searches(&context).pop_first().unwrap();
+    context
+        .search_results
+        .insert(query, vec![issue(7), issue(3)]);
+
+    let verdict = rule.evaluate(&context);
+    assert_eq!(verdict.status(), Status::Pass);
+    assert_eq!(
+        verdict.validators[0].message,
+        "2 issue(s) and pull request(s) inactive for at least 10 day(s)"
+    );
+    assert_eq!(verdict.found(), Some(vec![3, 7]));
+}
+
+#[test]
+fn requires_the_search_results() {
+    let configuration: Configuration = CONFIG.parse().unwrap();
+    let verdict = configuration.rules()[0].evaluate(&context());
+
+    assert_eq!(verdict.status(), Status::Error);
+    assert_eq!(verdict.found(), None);
+
+    let configuration: Configuration = r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        type: discussions
+"#
+    .parse()
+    .unwrap();
+    let verdict = configuration.rules()[0].evaluate(&context());
+    assert_eq!(
+        verdict.validators[0].message,
+        "unknown type `discussions`, expected `pull_request` or `issues`"
+    );
+}
+
+#[test]
+fn searches_within_the_time_constraint_only() {
+    let configuration: Configuration = r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        time_constraint:
+          time_zone: 'America/Los_Angeles'
+          hours_between: ['9', '17']
+          days_of_week: ['Mon', 'Tue', 'Wed', 'Thu', 'Fri']
+"#
+    .parse()
+    .unwrap();
+    let rule = &configuration.rules()[0];
+    let at = |now: &str| Context {
+        now: Some(now.parse::<DateTime<Utc>>().unwrap()),
+        ..context()
+    };
+
+    // 05:00 and 10:00 on a Monday in Los Angeles
+    assert!(rule.searches(&at("2024-03-11T12:00:00Z")).is_empty());
+    assert_eq!(rule.searches(&at("2024-03-11T17:00:00Z")).len(), 1);
+    // 10:00 on a Sunday
+    assert!(rule.searches(&at("2024-03-10T17:00:00Z")).is_empty());
+
+    let verdict = rule.evaluate(&at("2024-03-11T12:00:00Z"));
+    assert_eq!(verdict.status(), Status::Pass);
+    assert_eq!(
+        verdict.validators[0].message,
+        "outside of the time constraint, the search is skipped"
+    );
+    assert_eq!(verdict.found(), Some(vec![]));
+}
+
+#[test]
+fn agrees_on_the_time_constraint_at_an_hour_boundary() {
+    let configuration: Configuration = r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        time_constraint:
+          time_zone: 'America/Los_Angeles'
+          hours_between: ['9', '17']
+"#
+    .parse()
+    .unwrap();
+    let rule = &configuration.rules()[0];
+
+    // the last instant of 17:00 in Los Angeles, then the first one of 18:00
+    let mut context = Context {
+        now: Some("2024-03-12T00:59:59.999Z".parse::<DateTime<Utc>>().unwrap()),
+        ..context()
+    };
+    let query = rule.searches(&context).pop_first().unwrap();
+    context.search_results.insert(query, vec![issue(5)]);
+    let verdict = rule.evaluate(&context);
+    assert_eq!(verdict.status(), Status::Pass);
+    assert_eq!(verdict.found(), Some(vec![5]));
+
+    let context = Context {
+        now: Some("2024-03-12T01:00:00Z".parse::<DateTime<Utc>>().unwrap()),
+        ..context
+    };
+    assert!(rule.searches(&context).is_empty());
+    assert_eq!(rule.evaluate(&context).found(), Some(vec![]));
+}
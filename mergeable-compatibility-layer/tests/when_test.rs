@@ -0,0 +1,58 @@
+use mergeable_compatibility_layer::configuration::{when::When, Configuration};
+
+#[test]
+fn matches_events_actions_and_wildcards() {
+    let when: When = "pull_request.opened, issues.*, status".parse().unwrap();
+
+    assert!(when.matches("pull_request", Some("opened")));
+    assert!(!when.matches("pull_request", Some("closed")));
+    assert!(when.matches("issues", Some("edited")));
+    assert!(when.matches("status", None));
+    assert!(!when.matches("push", None));
+
+    let any: When = "*.opened".parse().unwrap();
+    assert!(any.matches("issues", Some("opened")));
+    assert!(!any.matches("issues", None));
+}
+
+#[test]
+fn rejects_malformed_entries() {
+    assert!("pull_request.".parse::<When>().is_err());
+    assert!("pull_request.opened,".parse::<When>().is_err());
+    assert!("Pull-Request".parse::<When>().is_err());
+
+    let config = serde_yaml::from_str::<Configuration>(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request..opened
+"#,
+    );
+    assert!(config.is_err());
+}
+
+#[test]
+fn selects_triggered_rules() {
+    let config: Configuration = serde_yaml::from_str(
+        r#"
+version: 2
+mergeable:
+  - when: pull_request.opened, pull_request.edited
+    name: pull requests
+  - when: issues.*
+    name: issues
+  - name: never
+"#,
+    )
+    .unwrap();
+
+    let names = |event, action| {
+        config
+            .rules_triggered_by(event, action)
+            .map(|rule| rule.name().unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names("pull_request", Some("edited")), ["pull requests"]);
+    assert_eq!(names("issues", Some("opened")), ["issues"]);
+    assert!(names("pull_request", Some("closed")).is_empty());
+}
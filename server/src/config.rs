@@ -4,6 +4,7 @@ use hyper::Uri;
 use jsonwebtoken::EncodingKey;
 use octocrab::models::AppId;
 use orion::{errors::UnknownCryptoError, hazardous::mac::hmac::sha256::SecretKey};
+use secrecy::SecretString;
 use thiserror::Error;
 
 pub fn load_github_app_config() -> Result<GitHubAppConfiguration, ConfigurationError> {
@@ -17,6 +18,13 @@ pub fn load_github_app_config() -> Result<GitHubAppConfiguration, ConfigurationE
         github_app_identifier: u64,
         #[serde(default)]
         github_uri: String,
+        #[serde(default)]
+        jira_base_url: String,
+        #[serde(default)]
+        jira_token: String,
+        /// Comma separated.
+        #[serde(default)]
+        jira_allowed_statuses: String,
     }
 
     let raw_config: ApplicationRawConfig = {
@@ -29,12 +37,30 @@ pub fn load_github_app_config() -> Result<GitHubAppConfiguration, ConfigurationE
     let app_identifier = AppId(raw_config.github_app_identifier);
     let app_key = EncodingKey::from_rsa_pem(raw_config.github_private_key.as_bytes())?;
     let uri = Uri::try_from(raw_config.github_uri)?;
+    let jira = if raw_config.jira_base_url.is_empty() {
+        None
+    } else {
+        Some(JiraConfiguration {
+            base_url: Uri::try_from(raw_config.jira_base_url)?,
+            token: Some(raw_config.jira_token)
+                .filter(|token| !token.is_empty())
+                .map(SecretString::new),
+            allowed_statuses: raw_config
+                .jira_allowed_statuses
+                .split(',')
+                .map(str::trim)
+                .filter(|status| !status.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        })
+    };
 
     Ok(GitHubAppConfiguration {
         webhook_secret,
         app_identifier,
         app_key,
         uri,
+        jira,
     })
 }
 
@@ -43,6 +69,17 @@ pub struct GitHubAppConfiguration {
     pub app_identifier: AppId,
     pub app_key: EncodingKey,
     pub uri: Uri,
+    /// Verification of Jira issue keys, disabled if unset.
+    pub jira: Option<JiraConfiguration>,
+}
+
+pub struct JiraConfiguration {
+    /// The REST API is expected below `/rest/api/2`.
+    pub base_url: Uri,
+    /// A personal access token sent as bearer token.
+    pub token: Option<SecretString>,
+    /// The statuses issues may be in, compared case insensitive. Any status if empty.
+    pub allowed_statuses: Vec<String>,
 }
 
 #[derive(Debug, Error)]
@@ -16,9 +16,11 @@ where
     C::Error: 'static,
     C::Next: 'static,
 {
+    let event_handler = routes::event_handler::router::<C>(app_config)?;
+    tokio::spawn(event_handler.scheduler.run());
     let routes = Router::new()
         .merge(routes::ui::router())
-        .merge(routes::event_handler::router::<C>(app_config)?)
+        .merge(event_handler.router)
         .route_layer(from_fn(track_metrics));
 
     let listener = {
@@ -49,5 +49,6 @@ fn create_dummy_config() -> GitHubAppConfiguration {
             EncodingKey::from_rsa_pem(cert_pem_str.as_bytes()).unwrap()
         },
         uri: Uri::from_static("https://github.local"),
+        jira: None,
     }
 }
@@ -5,39 +5,110 @@ use axum::{extract::State, response::IntoResponse, routing::any, Router};
 use axum_core::extract::FromRef;
 use orion::hazardous::mac::hmac::sha256::SecretKey;
 
-use crate::{config::GitHubAppConfiguration, routes::event_handler::remote::GitHubActionalbe};
-
-use self::extractors::GitHubEvent;
+use mergeable_compatibility_layer::{configuration::Rule, evaluation::context::Context};
+use octocrab::models::{
+    webhook_events::{EventInstallation, WebhookEvent, WebhookEventPayload},
+    Repository,
+};
+
+use crate::config::GitHubAppConfiguration;
+
+use self::{
+    configuration::{LoadedConfiguration, RepositoryConfigurations},
+    dispatch::EventDescriptor,
+    extractors::GitHubEvent,
+    jira::JiraIssues,
+    remote::GitHubOperations,
+    teams::TeamMemberships,
+};
 
 pub use authentication::{AuthenticatedClient, GitHubAuthenticator, InstallationAuthenticator};
 
+mod actions;
 mod authentication;
+mod configuration;
+mod context;
+mod dispatch;
 mod extractors;
+mod jira;
+mod lint;
 mod remote;
+mod schedule;
+mod teams;
+
+/// The webhook routes and the scheduler of `schedule.repository` events, which share their
+/// caches. Nothing is scheduled until the scheduler runs.
+pub struct EventHandler<C: InstallationAuthenticator> {
+    pub router: Router,
+    pub scheduler: Scheduler<C>,
+}
 
 pub fn router<C: GitHubAuthenticator>(
     config: GitHubAppConfiguration,
-) -> Result<Router, Box<dyn std::error::Error>>
+) -> Result<EventHandler<C::Next>, Box<dyn std::error::Error>>
 where
     C::Error: 'static,
     C::Next: 'static,
 {
     let client =
         authentication::authenticate::<C>(config.uri, config.app_identifier, config.app_key)?;
+    let scheduler = Scheduler {
+        client: client.client.clone(),
+        configurations: RepositoryConfigurations::default(),
+        teams: TeamMemberships::default(),
+        jira: JiraIssues::new(config.jira),
+    };
+    let router = routes(
+        config.webhook_secret,
+        client,
+        scheduler.configurations.clone(),
+        scheduler.teams.clone(),
+        scheduler.jira.clone(),
+    );
+    Ok(EventHandler { router, scheduler })
+}
+
+/// Emits a `schedule.repository` event for every installed repository once per hour.
+pub struct Scheduler<C: InstallationAuthenticator> {
+    client: C,
+    configurations: RepositoryConfigurations,
+    teams: TeamMemberships,
+    jira: JiraIssues,
+}
+
+impl<C: InstallationAuthenticator> Scheduler<C> {
+    pub async fn run(self) {
+        schedule::run(self.client, self.configurations, self.teams, self.jira).await
+    }
+}
+
+fn routes<C: InstallationAuthenticator + 'static>(
+    webhook_secret: SecretKey,
+    client: AuthenticatedClient<C>,
+    configurations: RepositoryConfigurations,
+    teams: TeamMemberships,
+    jira: JiraIssues,
+) -> Router {
     let signature_config = ConfigState {
-        webhook_secret: config.webhook_secret.into(),
+        webhook_secret: webhook_secret.into(),
         client,
+        configurations,
+        teams,
+        jira,
     };
-    Ok(Router::new().route(
+    Router::new().route(
         "/event_handler",
         any(handle_github_event).with_state(signature_config),
-    ))
+    )
 }
 
 #[derive(Clone)]
 struct ConfigState<C: InstallationAuthenticator + Clone> {
     webhook_secret: Arc<SecretKey>,
     client: AuthenticatedClient<C>,
+    configurations: RepositoryConfigurations,
+    teams: TeamMemberships,
+    jira: JiraIssues,
 }
 
 impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for Arc<SecretKey> {
@@ -52,75 +123,164 @@ impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for Authentic
     }
 }
 
+impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for RepositoryConfigurations {
+    fn from_ref(input: &ConfigState<C>) -> Self {
+        input.configurations.clone()
+    }
+}
+
+impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for TeamMemberships {
+    fn from_ref(input: &ConfigState<C>) -> Self {
+        input.teams.clone()
+    }
+}
+
+impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for JiraIssues {
+    fn from_ref(input: &ConfigState<C>) -> Self {
+        input.jira.clone()
+    }
+}
+
 async fn handle_github_event<C: InstallationAuthenticator + Clone>(
     State(AuthenticatedClient { client }): State<AuthenticatedClient<C>>,
+    State(configurations): State<RepositoryConfigurations>,
+    State(teams): State<TeamMemberships>,
+    State(jira): State<JiraIssues>,
     GitHubEvent(event): GitHubEvent,
 ) -> impl IntoResponse {
     tracing::error!(kind = ?event, "logic starts now");
-    if let Some(t) = event.installation {
-        let id = match t {
-            octocrab::models::webhook_events::EventInstallation::Full(install) => install.id,
-            octocrab::models::webhook_events::EventInstallation::Minimal(mini) => mini.id,
-        };
-        let client = client.for_installation(id);
-        client.post_message();
+    let descriptor = EventDescriptor::from(&event);
+    let (Some(installation), Some(repository)) = (&event.installation, &event.repository) else {
+        tracing::debug!(?descriptor, "event isn't about an installed repository");
+        return "hello world";
+    };
+    if let WebhookEventPayload::Push(push) = &event.specific {
+        configurations.observe_push(repository, push);
     }
+    let id = match installation {
+        EventInstallation::Full(install) => install.id,
+        EventInstallation::Minimal(mini) => mini.id,
+    };
+    let client = client.for_installation(id);
+    process_event(
+        &client,
+        &configurations,
+        &teams,
+        &jira,
+        &event,
+        &descriptor,
+        repository,
+    )
+    .await;
     "hello world"
 }
 
+/// Lints the configuration of the repository and applies the rules the event triggers.
+async fn process_event(
+    client: &impl GitHubOperations,
+    configurations: &RepositoryConfigurations,
+    teams: &TeamMemberships,
+    jira: &JiraIssues,
+    event: &WebhookEvent,
+    descriptor: &EventDescriptor,
+    repository: &Repository,
+) {
+    if let Err(error) = lint::lint_pull_request(client, event, descriptor).await {
+        tracing::warn!(repository = repository.id.0, %error, "failed to lint the configuration");
+    }
+    let configuration = match configurations.load(client, repository).await {
+        Ok(LoadedConfiguration::Valid(configuration)) => configuration,
+        Ok(LoadedConfiguration::Missing) => {
+            tracing::debug!(
+                repository = repository.id.0,
+                "repository has no configuration"
+            );
+            return;
+        }
+        Ok(LoadedConfiguration::Invalid(error)) => {
+            tracing::warn!(repository = repository.id.0, %error, "invalid configuration");
+            return;
+        }
+        Err(error) => {
+            tracing::warn!(repository = repository.id.0, %error, "failed to load the configuration");
+            return;
+        }
+    };
+    let rules: Vec<_> = dispatch::matching_rules(&configuration, descriptor).collect();
+    if rules.is_empty() {
+        tracing::debug!(?descriptor, "no rule is triggered by the event");
+        return;
+    }
+    tracing::info!(
+        ?descriptor,
+        rules = ?rules.iter().map(|rule| rule.name()).collect::<Vec<_>>(),
+        "rules triggered by the event"
+    );
+    let context = context::build_context(event, descriptor);
+    for mut context in context::pull_request_contexts(client, event, context).await {
+        context::fetch_requirements(client, teams, jira, &rules, &mut context).await;
+        apply_rules(client, &rules, &context).await;
+    }
+}
+
+/// Evaluates the triggered rules and applies the actions matching their outcome.
+async fn apply_rules(client: &impl GitHubOperations, rules: &[&Rule], context: &Context) {
+    for rule in rules {
+        let verdict = rule.evaluate(context);
+        if !verdict.is_applicable() {
+            tracing::debug!(?verdict, "rule doesn't apply");
+            continue;
+        }
+        // the actions of searching validators like `stale` apply to the items they found
+        let found = verdict
+            .found()
+            .map(|found| context::found_contexts(context, &found));
+        let targets = match &found {
+            Some(found) => found.iter().collect(),
+            None => vec![context],
+        };
+        for target in targets {
+            for action in rule.actions_for(verdict.status()) {
+                if let Err(error) = actions::execute(client, action, target, &verdict).await {
+                    tracing::warn!(rule = rule.name(), action = action.kind(), %error, "action failed");
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use axum::{body::Body, http::Request};
     use http_body_util::BodyExt;
     use hyper::{StatusCode, Uri};
+    use mergeable_compatibility_layer::{
+        configuration::Configuration,
+        evaluation::context::{Context, Issue, PullRequest, Repository, Subject},
+    };
     use orion::hazardous::mac::hmac::sha256::{HmacSha256, SecretKey};
     use rsa::RsaPublicKey;
     use serde_json::json;
-    use thiserror::Error;
     use tower::ServiceExt;
 
     use crate::config::GitHubAppConfiguration;
 
-    use super::{remote::GitHubActionalbe, GitHubAuthenticator, InstallationAuthenticator};
-
-    #[derive(Clone)]
-    struct TestClient;
-
-    #[derive(Debug, Error)]
-    enum TestError {}
-
-    struct NoOpActionable;
-
-    impl GitHubActionalbe for NoOpActionable {
-        fn post_message(&self) {
-            todo!()
-        }
-    }
-
-    impl GitHubAuthenticator for TestClient {
-        type Next = TestClient;
-        type Error = TestError;
-
-        fn authenticate_app(
-            _uri: Uri,
-            _app_id: octocrab::models::AppId,
-            _app_key: jsonwebtoken::EncodingKey,
-        ) -> Result<Self::Next, Self::Error> {
-            Ok(TestClient)
-        }
-    }
-
-    impl InstallationAuthenticator for TestClient {
-        fn for_installation(&self, _id: octocrab::models::InstallationId) -> impl GitHubActionalbe {
-            NoOpActionable
-        }
-    }
+    use super::{
+        configuration::{RepositoryConfigurations, CONFIGURATION_PATH},
+        jira::JiraIssues,
+        remote::{
+            fake::{Call, FakeGitHub, FakePullRequest},
+            CheckConclusion,
+        },
+        teams::TeamMemberships,
+        AuthenticatedClient,
+    };
 
     #[tracing_test::traced_test]
     #[tokio::test]
     async fn test_happy_path() {
         let (config, _, secret) = create_test_config();
-        let app = super::router::<TestClient>(config).unwrap();
+        let app = super::router::<FakeGitHub>(config).unwrap().router;
 
         let body = serde_json::to_vec(&json!({"hello": "world"})).unwrap();
         let body_hmac = calc_hmac_for_body(&secret, &body);
@@ -139,11 +299,89 @@ mod test {
         assert_eq!(parts.status, StatusCode::OK);
     }
 
+    #[tracing_test::traced_test]
+    #[tokio::test]
+    async fn test_pull_request_flow() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.add_pull_request(FakePullRequest {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 5,
+            title: "WIP: new feature".to_owned(),
+            author: "octocat".to_owned(),
+            labels: vec!["feature".to_owned()],
+            head_ref: "feature".to_owned(),
+            base_ref: "main".to_owned(),
+            head_sha: "abc123".to_owned(),
+            ..Default::default()
+        });
+        github.commit_file(
+            "owner",
+            "repo",
+            CONFIGURATION_PATH,
+            Some(
+                r#"
+version: 2
+mergeable:
+  - when: pull_request.opened
+    name: work in progress
+    validate:
+      - do: title
+        must_exclude:
+          regex: ^wip
+    fail:
+      - do: labels
+        add: wip
+      - do: checks
+      - do: comment
+        payload:
+          body: "@author please finish your work first"
+"#,
+            ),
+        );
+        let app = super::routes(
+            SecretKey::from_slice(&[0; 32]).unwrap(),
+            AuthenticatedClient {
+                client: github.clone(),
+            },
+            RepositoryConfigurations::default(),
+            TeamMemberships::default(),
+            JiraIssues::default(),
+        );
+
+        let body = serde_json::to_vec(&github.pull_request_event("opened", "owner", "repo", 5, 42))
+            .unwrap();
+        let body_hmac = calc_hmac_for_body(&SecretKey::from_slice(&[0; 32]).unwrap(), &body);
+        let request = Request::builder()
+            .uri("/event_handler")
+            .header("X-GitHub-Event", "pull_request")
+            .header("x-hub-signature-256", format!("sha256={body_hmac}"))
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(github.state().installations, [42]);
+        let pull_request = github.pull_request("owner", "repo", 5);
+        assert_eq!(pull_request.labels, ["feature", "wip"]);
+        assert_eq!(pull_request.comments.len(), 1);
+        assert!(pull_request.comments[0]
+            .body
+            .ends_with("@octocat please finish your work first"));
+        let check_runs = github.state().check_runs.clone();
+        assert_eq!(check_runs.len(), 1);
+        assert_eq!(check_runs[0].1.name, "work in progress");
+        assert_eq!(check_runs[0].1.head_sha, "abc123");
+        assert_eq!(check_runs[0].1.conclusion, CheckConclusion::Failure);
+        assert!(!github.calls().contains(&Call::Close(5)));
+    }
+
     #[tracing_test::traced_test]
     #[tokio::test]
     async fn test_missing_signature() {
         let (config, _, _) = create_test_config();
-        let app = super::router::<TestClient>(config).unwrap();
+        let app = super::router::<FakeGitHub>(config).unwrap().router;
 
         let body = serde_json::to_vec(&json!({"hello": "world"})).unwrap();
         let request = Request::builder()
@@ -160,7 +398,7 @@ mod test {
     #[tokio::test]
     async fn test_wrong_signature() {
         let (config, _, _) = create_test_config();
-        let app = super::router::<TestClient>(config).unwrap();
+        let app = super::router::<FakeGitHub>(config).unwrap().router;
 
         let body = serde_json::to_vec(&json!({"hello": "world"})).unwrap();
         let request = Request::builder()
@@ -177,6 +415,63 @@ mod test {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn applies_actions_of_applicable_rules_only() {
+        let configuration: Configuration = serde_yaml::from_str(
+            r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    name: public only
+    filter:
+      - do: repository
+        visibility: public
+    validate:
+      - do: title
+        must_exclude:
+          regex: wip
+    fail:
+      - do: close
+  - when: pull_request.*
+    name: titles
+    validate:
+      - do: title
+        must_exclude:
+          regex: wip
+    fail:
+      - do: labels
+        add: wip
+      - do: close
+"#,
+        )
+        .unwrap();
+        let context = Context {
+            event: "pull_request".to_owned(),
+            repository: Repository {
+                owner: "owner".to_owned(),
+                name: "repo".to_owned(),
+                private: true,
+                ..Default::default()
+            },
+            subject: Some(Subject::PullRequest(Box::new(PullRequest {
+                issue: Issue {
+                    number: 3,
+                    title: "WIP".to_owned(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }))),
+            ..Default::default()
+        };
+        let client = FakeGitHub::default();
+        client.state().failing.insert("add_labels");
+
+        let rules: Vec<_> = configuration.rules().iter().collect();
+        super::apply_rules(&client, &rules, &context).await;
+
+        assert_eq!(client.calls(), [Call::Close(3)]);
+    }
+
     fn create_test_config() -> (GitHubAppConfiguration, RsaPublicKey, SecretKey) {
         use jsonwebtoken::EncodingKey;
         use octocrab::models::AppId;
@@ -199,6 +494,7 @@ mod test {
                 app_identifier: AppId(1),
                 app_key: { EncodingKey::from_rsa_pem(cert_pem_str.as_bytes()).unwrap() },
                 uri: Uri::from_static("https://github.local"),
+                jira: None,
             },
             pub_key,
             SecretKey::from_slice(&[0; 32]).unwrap(),
@@ -207,7 +503,7 @@ mod test {
 
     fn calc_hmac_for_body(secret: &SecretKey, data: &[u8]) -> String {
         hex::encode(
-            HmacSha256::hmac(&secret, data)
+            HmacSha256::hmac(secret, data)
                 .unwrap()
                 .unprotected_as_bytes(),
         )
@@ -0,0 +1,600 @@
+use mergeable_compatibility_layer::{
+    configuration::actions::{
+        Action, AssignAction, CheckAction, CommentAction, LabelsAction, MergeAction,
+        RequestReviewAction,
+    },
+    evaluation::{context::Context, RuleVerdict, Status},
+};
+use thiserror::Error;
+
+use super::{
+    context::issue_ref,
+    remote::{CheckConclusion, CheckRun, GitHubOperations, IssueRef, MergeMethod, RemoteError},
+};
+
+/// Marks comments created by the application for a rule so that outdated ones can be removed,
+/// followed by the name of the rule.
+const COMMENT_MARKER: &str = "<!-- source-warden:";
+/// Check run name for rules without a name.
+const DEFAULT_CHECK_NAME: &str = "source-warden";
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("{0}")]
+    Remote(#[from] RemoteError),
+    #[error("The {0} action requires a pull request or issue")]
+    MissingSubject(&'static str),
+    #[error("The {0} action requires a pull request")]
+    NotAPullRequest(&'static str),
+    #[error("Unsupported {option} '{value}'")]
+    InvalidOption { option: &'static str, value: String },
+}
+
+/// Applies an action of an evaluated rule to the subject of the event.
+pub(crate) async fn execute(
+    client: &impl GitHubOperations,
+    action: &Action,
+    context: &Context,
+    verdict: &RuleVerdict,
+) -> Result<(), ActionError> {
+    let kind = action.kind();
+    let issue = issue_ref(context).ok_or(ActionError::MissingSubject(kind))?;
+    let templates = Templates { context, verdict };
+    match action {
+        Action::Assign(action) => assign(client, action, &issue, &templates).await,
+        Action::Checks(action) => checks(client, action, &issue, &templates).await,
+        Action::Close(_) => Ok(client.close(&issue).await?),
+        Action::Comment(action) => comment(client, action, &issue, &templates).await,
+        Action::Merge(action) => {
+            if !templates.is_pull_request() {
+                return Err(ActionError::NotAPullRequest(kind));
+            }
+            merge(client, action, &issue).await
+        }
+        Action::Labels(action) => labels(client, action, &issue).await,
+        Action::RequestReview(action) => {
+            if !templates.is_pull_request() {
+                return Err(ActionError::NotAPullRequest(kind));
+            }
+            request_review(client, action, &issue).await
+        }
+    }
+}
+
+/// Renders the placeholders mergeable supports in action options.
+struct Templates<'a> {
+    context: &'a Context,
+    verdict: &'a RuleVerdict,
+}
+
+impl Templates<'_> {
+    fn is_pull_request(&self) -> bool {
+        self.context
+            .subject
+            .as_ref()
+            .is_some_and(|subject| subject.pull_request().is_some())
+    }
+
+    fn author(&self) -> &str {
+        self.context
+            .subject
+            .as_ref()
+            .map(|subject| subject.issue().author.as_str())
+            .unwrap_or_default()
+    }
+
+    fn sender(&self) -> &str {
+        self.context.sender.as_deref().unwrap_or_default()
+    }
+
+    /// Replaces a user placeholder by the login, e.g. for assignees.
+    fn user(&self, user: &str) -> String {
+        match user {
+            "@author" => self.author().to_owned(),
+            "@sender" => self.sender().to_owned(),
+            user => user.to_owned(),
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        let template = replace_mention(template, "@author", self.author());
+        replace_mention(&template, "@sender", self.sender())
+            .replace("{{validationStatus}}", self.verdict.status().as_str())
+            .replace("{{validationSuites}}", &self.validation_suites())
+    }
+
+    /// A markdown list of the validator outcomes.
+    fn validation_suites(&self) -> String {
+        self.verdict
+            .validators
+            .iter()
+            .map(|validator| {
+                format!(
+                    "- **{}** `{}`: {}",
+                    validator.kind,
+                    validator.status.as_str(),
+                    validator.message
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn rule_name(&self) -> &str {
+        self.verdict.name.as_deref().unwrap_or(DEFAULT_CHECK_NAME)
+    }
+
+    /// Marks the comments of the rule, `--` would end the HTML comment early.
+    fn comment_marker(&self) -> String {
+        format!(
+            "{COMMENT_MARKER} {} -->",
+            self.rule_name().replace("--", "- -")
+        )
+    }
+}
+
+/// Replaces the placeholder where it's a whole mention, unlike e.g. in `@authors`.
+fn replace_mention(template: &str, placeholder: &str, login: &str) -> String {
+    let is_login = |c: char| c.is_ascii_alphanumeric() || c == '-';
+    let mut rendered = String::with_capacity(template.len());
+    let mut copied = 0;
+    for (index, _) in template.match_indices(placeholder) {
+        let end = index + placeholder.len();
+        let before = template[..index].chars().next_back();
+        let after = template[end..].chars().next();
+        if before.is_some_and(is_login) || after.is_some_and(is_login) {
+            continue;
+        }
+        rendered.push_str(&template[copied..index]);
+        rendered.push('@');
+        rendered.push_str(login);
+        copied = end;
+    }
+    rendered.push_str(&template[copied..]);
+    rendered
+}
+
+async fn assign(
+    client: &impl GitHubOperations,
+    action: &AssignAction,
+    issue: &IssueRef,
+    templates: &Templates<'_>,
+) -> Result<(), ActionError> {
+    let assignees: Vec<_> = action
+        .assignees()
+        .map(|assignee| templates.user(assignee))
+        .filter(|assignee| !assignee.is_empty())
+        .collect();
+    if !assignees.is_empty() {
+        client.add_assignees(issue, &assignees).await?;
+    }
+    Ok(())
+}
+
+async fn checks(
+    client: &impl GitHubOperations,
+    action: &CheckAction,
+    issue: &IssueRef,
+    templates: &Templates<'_>,
+) -> Result<(), ActionError> {
+    let Some(head_sha) = templates
+        .context
+        .subject
+        .as_ref()
+        .and_then(|subject| subject.pull_request())
+        .map(|pull_request| pull_request.head_sha.clone())
+    else {
+        return Err(ActionError::NotAPullRequest("checks"));
+    };
+    let conclusion = match action.status() {
+        None => match templates.verdict.status() {
+            Status::Pass => CheckConclusion::Success,
+            Status::Fail => CheckConclusion::Failure,
+            Status::Error => CheckConclusion::Neutral,
+        },
+        Some("success") => CheckConclusion::Success,
+        Some("failure") => CheckConclusion::Failure,
+        Some("neutral") => CheckConclusion::Neutral,
+        Some("cancelled") => CheckConclusion::Cancelled,
+        Some("timed_out") => CheckConclusion::TimedOut,
+        Some("action_required") => CheckConclusion::ActionRequired,
+        Some(status) => {
+            return Err(ActionError::InvalidOption {
+                option: "status",
+                value: status.to_owned(),
+            })
+        }
+    };
+    let payload = action.payload();
+    let title = payload
+        .and_then(|payload| payload.title())
+        .map(|title| templates.render(title))
+        .unwrap_or_else(|| {
+            format!(
+                "{} has been evaluated: {}",
+                templates.rule_name(),
+                templates.verdict.status().as_str()
+            )
+        });
+    let summary = payload
+        .and_then(|payload| payload.summary())
+        .map(|summary| templates.render(summary))
+        .unwrap_or_else(|| templates.validation_suites());
+    let text = payload
+        .and_then(|payload| payload.text())
+        .map(|text| templates.render(text));
+    let check_run = CheckRun {
+        name: templates.rule_name().to_owned(),
+        head_sha,
+        conclusion,
+        title,
+        summary,
+        text,
+        annotations: vec![],
+    };
+    // each evaluation of the rule replaces the outcome of the previous one on the commit
+    let existing = client
+        .find_check_run(
+            &issue.owner,
+            &issue.repo,
+            &check_run.head_sha,
+            &check_run.name,
+        )
+        .await?;
+    match existing {
+        Some(check_run_id) => {
+            client
+                .update_check_run(&issue.owner, &issue.repo, check_run_id, &check_run)
+                .await?
+        }
+        None => {
+            client
+                .create_check_run(&issue.owner, &issue.repo, &check_run)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn comment(
+    client: &impl GitHubOperations,
+    action: &CommentAction,
+    issue: &IssueRef,
+    templates: &Templates<'_>,
+) -> Result<(), ActionError> {
+    let marker = templates.comment_marker();
+    let previous = if action.leave_old_comment() {
+        vec![]
+    } else {
+        client.list_comments(issue).await?
+    };
+    let body = action
+        .body()
+        .map(|body| templates.render(body))
+        .unwrap_or_else(|| templates.validation_suites());
+    let created = client
+        .create_comment(issue, &format!("{marker}\n{body}"))
+        .await?;
+    // the new comment tells the login of the app's bot, others may quote the marker
+    for comment in previous {
+        if comment.author == created.author && comment.body.starts_with(&marker) {
+            client.delete_comment(issue, comment.id).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn merge(
+    client: &impl GitHubOperations,
+    action: &MergeAction,
+    issue: &IssueRef,
+) -> Result<(), ActionError> {
+    let method = match action.merge_method() {
+        None | Some("merge") => MergeMethod::Merge,
+        Some("squash") => MergeMethod::Squash,
+        Some("rebase") => MergeMethod::Rebase,
+        Some(method) => {
+            return Err(ActionError::InvalidOption {
+                option: "merge_method",
+                value: method.to_owned(),
+            })
+        }
+    };
+    client
+        .merge(
+            issue,
+            method,
+            action.commit_title(),
+            action.commit_message(),
+        )
+        .await?;
+    Ok(())
+}
+
+async fn labels(
+    client: &impl GitHubOperations,
+    action: &LabelsAction,
+    issue: &IssueRef,
+) -> Result<(), ActionError> {
+    if let Some(replace) = action.replace() {
+        let labels: Vec<_> = replace.map(str::to_owned).collect();
+        client.replace_labels(issue, &labels).await?;
+    }
+    let add: Vec<_> = action.add().map(str::to_owned).collect();
+    if !add.is_empty() {
+        client.add_labels(issue, &add).await?;
+    }
+    for label in action.delete() {
+        match client.remove_label(issue, label).await {
+            // GitHub answers 404 for labels the issue doesn't have
+            Ok(()) | Err(RemoteError::NotFound(_)) => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Ok(())
+}
+
+async fn request_review(
+    client: &impl GitHubOperations,
+    action: &RequestReviewAction,
+    issue: &IssueRef,
+) -> Result<(), ActionError> {
+    let reviewers: Vec<_> = action.reviewers().map(str::to_owned).collect();
+    let teams: Vec<_> = action.teams().map(str::to_owned).collect();
+    if !reviewers.is_empty() || !teams.is_empty() {
+        client.request_reviewers(issue, &reviewers, &teams).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use mergeable_compatibility_layer::{
+        configuration::Configuration,
+        evaluation::context::{Context, Issue, PullRequest, Repository, Subject},
+    };
+
+    use super::{execute, ActionError};
+    use crate::routes::event_handler::remote::{
+        fake::{Call, FakeGitHub, FakePullRequest, BOT},
+        CheckConclusion, IssueComment, MergeMethod,
+    };
+
+    fn configuration(actions: &str) -> Configuration {
+        serde_yaml::from_str(&format!(
+            r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    name: titles
+    validate:
+      - do: title
+        must_exclude:
+          regex: wip
+{actions}
+"#
+        ))
+        .unwrap()
+    }
+
+    fn context(title: &str, pull_request: bool) -> Context {
+        let issue = Issue {
+            number: 7,
+            title: title.to_owned(),
+            author: "octocat".to_owned(),
+            ..Default::default()
+        };
+        Context {
+            event: "pull_request".to_owned(),
+            action: Some("opened".to_owned()),
+            repository: Repository {
+                owner: "owner".to_owned(),
+                name: "repo".to_owned(),
+                ..Default::default()
+            },
+            sender: Some("hubot".to_owned()),
+            subject: Some(if pull_request {
+                Subject::PullRequest(Box::new(PullRequest {
+                    issue,
+                    head_sha: "abc123".to_owned(),
+                    ..Default::default()
+                }))
+            } else {
+                Subject::Issue(issue)
+            }),
+            ..Default::default()
+        }
+    }
+
+    async fn run(
+        client: &FakeGitHub,
+        configuration: &Configuration,
+        context: &Context,
+    ) -> Vec<Result<(), ActionError>> {
+        let rule = &configuration.rules()[0];
+        let verdict = rule.evaluate(context);
+        let mut results = vec![];
+        for action in rule.actions_for(verdict.status()) {
+            results.push(execute(client, action, context, &verdict).await);
+        }
+        results
+    }
+
+    #[tokio::test]
+    async fn replaces_previous_comments_and_renders_placeholders() {
+        let configuration = configuration(
+            r#"    fail:
+      - do: comment
+        payload:
+          body: "@author the rule is {{validationStatus}}, cc @authors @sender-bot @sender"
+      - do: assign
+        assignees: ["@author", "reviewer"]"#,
+        );
+        let client = FakeGitHub::default();
+        client.add_pull_request(FakePullRequest {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 7,
+            comments: vec![
+                IssueComment {
+                    id: 100,
+                    body: "<!-- source-warden: titles -->\nold".to_owned(),
+                    author: BOT.to_owned(),
+                },
+                IssueComment {
+                    id: 101,
+                    body: "unrelated".to_owned(),
+                    author: "octocat".to_owned(),
+                },
+                IssueComment {
+                    id: 102,
+                    body: "<!-- source-warden: titles and labels -->\nother rule".to_owned(),
+                    author: BOT.to_owned(),
+                },
+                IssueComment {
+                    id: 103,
+                    body: "<!-- source-warden: titles -->\nquoted".to_owned(),
+                    author: "octocat".to_owned(),
+                },
+            ],
+            ..Default::default()
+        });
+
+        let results = run(&client, &configuration, &context("WIP: draft", true)).await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(
+            client.calls(),
+            [
+                Call::CreateComment(
+                    7,
+                    "<!-- source-warden: titles -->\n\
+                     @octocat the rule is fail, cc @authors @sender-bot @hubot"
+                        .to_owned()
+                ),
+                Call::DeleteComment(100),
+                Call::AddAssignees(7, vec!["octocat".to_owned(), "reviewer".to_owned()]),
+            ]
+        );
+        let comments = client.pull_request("owner", "repo", 7).comments;
+        assert_eq!(comments.len(), 4);
+        assert_eq!(comments[0].body, "unrelated");
+        assert!(comments[1].body.contains("other rule"));
+        assert!(comments[2].body.contains("quoted"));
+    }
+
+    #[tokio::test]
+    async fn creates_check_runs_with_the_rule_status() {
+        let configuration = configuration(
+            r#"    pass:
+      - do: checks
+        payload:
+          title: "{{validationStatus}}"
+      - do: labels
+        replace: ["ready"]
+        delete: "wip""#,
+        );
+        let client = FakeGitHub::default();
+
+        let results = run(&client, &configuration, &context("Add feature", true)).await;
+
+        assert!(results.iter().all(Result::is_ok));
+        let calls = client.calls();
+        let Call::CreateCheckRun(check_run) = &calls[0] else {
+            panic!("expected a check run, got {calls:?}");
+        };
+        assert_eq!(check_run.name, "titles");
+        assert_eq!(check_run.head_sha, "abc123");
+        assert_eq!(check_run.conclusion, CheckConclusion::Success);
+        assert_eq!(check_run.title, "pass");
+        assert_eq!(
+            calls[1..],
+            [
+                Call::ReplaceLabels(7, vec!["ready".to_owned()]),
+                Call::RemoveLabel(7, "wip".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_labels_the_pull_request_does_not_have() {
+        let configuration = configuration(
+            r#"    pass:
+      - do: labels
+        delete: ["wip", "blocked"]"#,
+        );
+        let client = FakeGitHub::default();
+        client.add_pull_request(FakePullRequest {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 7,
+            labels: vec!["blocked".to_owned()],
+            ..Default::default()
+        });
+
+        let results = run(&client, &configuration, &context("Add feature", true)).await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(
+            client.calls(),
+            [
+                Call::RemoveLabel(7, "wip".to_owned()),
+                Call::RemoveLabel(7, "blocked".to_owned()),
+            ]
+        );
+        assert!(client.pull_request("owner", "repo", 7).labels.is_empty());
+    }
+
+    #[tokio::test]
+    async fn updates_the_check_run_of_the_rule_on_the_commit() {
+        let configuration = configuration(
+            r#"    pass:
+      - do: checks
+    fail:
+      - do: checks"#,
+        );
+        let client = FakeGitHub::default();
+
+        run(&client, &configuration, &context("WIP: draft", true)).await;
+        run(&client, &configuration, &context("Add feature", true)).await;
+
+        let calls = client.calls();
+        let [Call::CreateCheckRun(created), Call::UpdateCheckRun(id, updated)] = &calls[..] else {
+            panic!("expected a check run to be created and updated, got {calls:?}");
+        };
+        assert_eq!(created.conclusion, CheckConclusion::Failure);
+        assert_eq!(updated.conclusion, CheckConclusion::Success);
+        let check_runs = client.state().check_runs.clone();
+        assert_eq!(check_runs.len(), 1);
+        assert_eq!(check_runs[0].0, *id);
+    }
+
+    #[tokio::test]
+    async fn rejects_pull_request_actions_on_issues_and_invalid_options() {
+        let configuration = configuration(
+            r#"    fail:
+      - do: merge
+        merge_method: fast-forward
+      - do: close"#,
+        );
+        let client = FakeGitHub::default();
+
+        let results = run(&client, &configuration, &context("WIP", false)).await;
+        assert!(matches!(
+            results[0],
+            Err(ActionError::NotAPullRequest("merge"))
+        ));
+        assert!(results[1].is_ok());
+
+        let results = run(&client, &configuration, &context("WIP", true)).await;
+        assert!(matches!(
+            results[0],
+            Err(ActionError::InvalidOption {
+                option: "merge_method",
+                ..
+            })
+        ));
+        assert_eq!(client.calls(), [Call::Close(7), Call::Close(7)]);
+        assert!(!client.calls().contains(&Call::Merge(7, MergeMethod::Merge)));
+    }
+}
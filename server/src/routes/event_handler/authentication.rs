@@ -1,12 +1,14 @@
+use std::future::Future;
+
 use hyper::http::Uri;
 use jsonwebtoken::EncodingKey;
 use octocrab::{
-    models::{AppId, InstallationId},
+    models::{AppId, Installation, InstallationId},
     Octocrab,
 };
 use thiserror::Error;
 
-use super::remote::GitHubActionalbe;
+use super::remote::{GitHubOperations, RemoteError};
 
 pub fn authenticate<C: GitHubAuthenticator>(
     github_uri: Uri,
@@ -34,7 +36,12 @@ pub trait GitHubAuthenticator {
 }
 
 pub trait InstallationAuthenticator: Clone + Send + Sync {
-    fn for_installation(&self, id: InstallationId) -> impl GitHubActionalbe;
+    fn for_installation(&self, id: InstallationId) -> impl GitHubOperations;
+
+    /// The installations of the application.
+    fn installations(
+        &self,
+    ) -> impl Future<Output = Result<Vec<InstallationId>, RemoteError>> + Send;
 }
 
 #[derive(Debug, Error)]
@@ -60,7 +67,16 @@ impl GitHubAuthenticator for Octocrab {
 }
 
 impl InstallationAuthenticator for Octocrab {
-    fn for_installation(&self, id: InstallationId) -> impl GitHubActionalbe {
+    fn for_installation(&self, id: InstallationId) -> impl GitHubOperations {
         self.installation(id)
     }
+
+    async fn installations(&self) -> Result<Vec<InstallationId>, RemoteError> {
+        let page = self.apps().installations().per_page(100).send().await?;
+        let installations = self.all_pages::<Installation>(page).await?;
+        Ok(installations
+            .into_iter()
+            .map(|installation| installation.id)
+            .collect())
+    }
 }
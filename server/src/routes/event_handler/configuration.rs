@@ -0,0 +1,298 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::{Duration, Instant},
+};
+
+use mergeable_compatibility_layer::configuration::{parse::ParseError, Configuration};
+use octocrab::models::{
+    webhook_events::payload::PushWebhookEventPayload, Repository, RepositoryId,
+};
+
+use super::remote::{GitHubOperations, RemoteError};
+
+/// Where repositories keep their mergeable configuration.
+pub(crate) const CONFIGURATION_PATH: &str = ".github/mergeable.yml";
+
+/// GitHub lists at most this many commits in a push payload.
+const MAX_PUSH_COMMITS: usize = 20;
+
+/// How long cached configurations are used before checking the default branch again, in case
+/// a push was missed.
+const CONFIGURATION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// The configuration of a repository as found on its default branch.
+#[derive(Debug, Clone)]
+pub(crate) enum LoadedConfiguration {
+    Missing,
+    Invalid(ParseError),
+    Valid(Arc<Configuration>),
+}
+
+#[derive(Debug)]
+struct CachedConfiguration {
+    /// The default branch commit the configuration was read at.
+    sha: String,
+    /// When the default branch was last seen at `sha`.
+    checked_at: Instant,
+    configuration: LoadedConfiguration,
+}
+
+/// The mergeable configurations of the repositories the application is installed on.
+///
+/// Entries follow the default branch through `push` events and are dropped once a push
+/// touches the configuration file or the cache can't tell whether it did. Expired entries
+/// are only fetched again if the default branch moved.
+#[derive(Clone, Default)]
+pub struct RepositoryConfigurations(Arc<RwLock<HashMap<RepositoryId, CachedConfiguration>>>);
+
+impl RepositoryConfigurations {
+    /// Returns the configuration of the repository, fetching it from the default branch
+    /// unless cached.
+    pub(crate) async fn load(
+        &self,
+        client: &impl GitHubOperations,
+        repository: &Repository,
+    ) -> Result<LoadedConfiguration, RemoteError> {
+        if let Some(cached) = self.read().get(&repository.id) {
+            if cached.checked_at.elapsed() < CONFIGURATION_TTL {
+                return Ok(cached.configuration.clone());
+            }
+        }
+        let owner = repository
+            .owner
+            .as_ref()
+            .map(|owner| owner.login.as_str())
+            .unwrap_or_default();
+        let branch = repository.default_branch.as_deref().unwrap_or("HEAD");
+        let sha = client.commit_sha(owner, &repository.name, branch).await?;
+        if let Some(cached) = self.write().get_mut(&repository.id) {
+            if cached.sha == sha {
+                cached.checked_at = Instant::now();
+                return Ok(cached.configuration.clone());
+            }
+        }
+        let content = client
+            .file_content(owner, &repository.name, CONFIGURATION_PATH, &sha)
+            .await?;
+        let configuration = match content {
+            None => LoadedConfiguration::Missing,
+            Some(content) => match content.parse() {
+                Ok(configuration) => LoadedConfiguration::Valid(Arc::new(configuration)),
+                Err(error) => LoadedConfiguration::Invalid(error),
+            },
+        };
+        tracing::debug!(repository = repository.id.0, sha, "configuration loaded");
+        self.write().insert(
+            repository.id,
+            CachedConfiguration {
+                sha,
+                checked_at: Instant::now(),
+                configuration: configuration.clone(),
+            },
+        );
+        Ok(configuration)
+    }
+
+    /// Keeps the cached configuration of the repository in sync with a push.
+    pub(crate) fn observe_push(&self, repository: &Repository, push: &PushWebhookEventPayload) {
+        let default_branch = repository
+            .default_branch
+            .as_ref()
+            .map(|branch| format!("refs/heads/{branch}"));
+        if default_branch.is_some_and(|branch| branch != push.r#ref) {
+            return;
+        }
+        let mut cache = self.write();
+        let Some(cached) = cache.get_mut(&repository.id) else {
+            return;
+        };
+        if cached.sha == push.before && !touches_configuration(push) {
+            cached.sha.clone_from(&push.after);
+            cached.checked_at = Instant::now();
+        } else {
+            tracing::debug!(repository = repository.id.0, "configuration invalidated");
+            cache.remove(&repository.id);
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<RepositoryId, CachedConfiguration>> {
+        self.0.read().expect("configuration lock poisoned")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<RepositoryId, CachedConfiguration>> {
+        self.0.write().expect("configuration lock poisoned")
+    }
+}
+
+/// Pushes listing too many commits to tell are assumed to touch the file.
+fn touches_configuration(push: &PushWebhookEventPayload) -> bool {
+    push.forced
+        || push.deleted
+        || push.commits.len() >= MAX_PUSH_COMMITS
+        || push.commits.iter().any(|commit| {
+            commit
+                .added
+                .iter()
+                .chain(&commit.modified)
+                .chain(&commit.removed)
+                .any(|path| path == CONFIGURATION_PATH)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use octocrab::models::{webhook_events::payload::PushWebhookEventPayload, Repository};
+
+    use std::time::Instant;
+
+    use super::{
+        LoadedConfiguration, RepositoryConfigurations, CONFIGURATION_PATH, CONFIGURATION_TTL,
+    };
+    use crate::routes::event_handler::remote::fake::FakeGitHub;
+
+    const CONFIGURATION: &str = r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    name: titles
+"#;
+
+    fn repository(github: &FakeGitHub) -> Repository {
+        serde_json::from_value(github.repository_json("owner", "repo")).unwrap()
+    }
+
+    fn push(github: &FakeGitHub, before: &str, modified: &[&str]) -> PushWebhookEventPayload {
+        serde_json::from_value(github.push_event("owner", "repo", before, modified, 42)).unwrap()
+    }
+
+    fn rule_count(configuration: LoadedConfiguration) -> usize {
+        match configuration {
+            LoadedConfiguration::Valid(configuration) => configuration.rules().len(),
+            other => panic!("expected a valid configuration, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_the_configuration_once_per_commit() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        let sha = github.commit_file("owner", "repo", CONFIGURATION_PATH, Some(CONFIGURATION));
+        let configurations = RepositoryConfigurations::default();
+
+        let first = configurations.load(&github, &repository(&github)).await;
+        let second = configurations.load(&github, &repository(&github)).await;
+
+        assert_eq!(rule_count(first.unwrap()), 1);
+        assert_eq!(rule_count(second.unwrap()), 1);
+        assert_eq!(
+            github.state().file_reads,
+            [(CONFIGURATION_PATH.to_owned(), sha)]
+        );
+    }
+
+    #[tokio::test]
+    async fn refetches_after_a_push_touching_the_configuration() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        let initial = github.commit_file("owner", "repo", CONFIGURATION_PATH, Some(CONFIGURATION));
+        let configurations = RepositoryConfigurations::default();
+        configurations
+            .load(&github, &repository(&github))
+            .await
+            .unwrap();
+
+        let unrelated = github.commit_file("owner", "repo", "README.md", Some("# repo"));
+        configurations.observe_push(
+            &repository(&github),
+            &push(&github, &initial, &["README.md"]),
+        );
+        configurations
+            .load(&github, &repository(&github))
+            .await
+            .unwrap();
+        assert_eq!(github.state().file_reads.len(), 1);
+
+        let extended = format!("{CONFIGURATION}  - when: issues.*\n    name: issues\n");
+        github.commit_file("owner", "repo", CONFIGURATION_PATH, Some(&extended));
+        configurations.observe_push(
+            &repository(&github),
+            &push(&github, &unrelated, &[CONFIGURATION_PATH]),
+        );
+        let reloaded = configurations.load(&github, &repository(&github)).await;
+
+        assert_eq!(rule_count(reloaded.unwrap()), 2);
+        assert_eq!(github.state().file_reads.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn drops_the_cache_when_a_push_was_missed() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.commit_file("owner", "repo", CONFIGURATION_PATH, Some(CONFIGURATION));
+        let configurations = RepositoryConfigurations::default();
+        configurations
+            .load(&github, &repository(&github))
+            .await
+            .unwrap();
+
+        let missed = github.commit_file("owner", "repo", CONFIGURATION_PATH, None);
+        github.commit_file("owner", "repo", "README.md", Some("# repo"));
+        configurations.observe_push(
+            &repository(&github),
+            &push(&github, &missed, &["README.md"]),
+        );
+        let reloaded = configurations.load(&github, &repository(&github)).await;
+
+        assert!(matches!(reloaded, Ok(LoadedConfiguration::Missing)));
+    }
+
+    #[tokio::test]
+    async fn reloads_expired_configurations_when_the_last_push_was_missed() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.commit_file("owner", "repo", CONFIGURATION_PATH, Some(CONFIGURATION));
+        let configurations = RepositoryConfigurations::default();
+        configurations
+            .load(&github, &repository(&github))
+            .await
+            .unwrap();
+        let expire = || {
+            for cached in configurations.write().values_mut() {
+                cached.checked_at = Instant::now() - CONFIGURATION_TTL;
+            }
+        };
+
+        // the default branch didn't move, only its head is checked
+        expire();
+        configurations
+            .load(&github, &repository(&github))
+            .await
+            .unwrap();
+        assert_eq!(github.state().file_reads.len(), 1);
+
+        // no push event arrives for this commit
+        github.commit_file("owner", "repo", CONFIGURATION_PATH, None);
+        let cached = configurations.load(&github, &repository(&github)).await;
+        assert_eq!(rule_count(cached.unwrap()), 1);
+
+        expire();
+        let reloaded = configurations.load(&github, &repository(&github)).await;
+        assert!(matches!(reloaded, Ok(LoadedConfiguration::Missing)));
+        assert_eq!(github.state().file_reads.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_configurations() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.commit_file("owner", "repo", CONFIGURATION_PATH, Some("version: two"));
+        let configurations = RepositoryConfigurations::default();
+
+        let loaded = configurations.load(&github, &repository(&github)).await;
+
+        assert!(
+            matches!(loaded, Ok(LoadedConfiguration::Invalid(error)) if error.key() == Some("version"))
+        );
+    }
+}
@@ -0,0 +1,950 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::Utc;
+use futures_util::{stream, StreamExt};
+
+use mergeable_compatibility_layer::{
+    codeowners::{CodeOwners, Owner, CODEOWNERS_PATHS},
+    configuration::Rule,
+    evaluation::{
+        context::{
+            ChangedFile, Commit, Context, FileContent, FileStatus, Issue, Project, PullRequest,
+            Repository, Review, ReviewState, Subject, TreeEntry,
+        },
+        requirements::Requirements,
+    },
+};
+use octocrab::models::{
+    issues, pulls,
+    webhook_events::{WebhookEvent, WebhookEventPayload},
+    Author,
+};
+
+use super::{
+    dispatch::EventDescriptor,
+    jira::JiraIssues,
+    remote::{
+        self, GitHubOperations, IssueRef, PullRequestReview, PullRequestReviewState, RemoteError,
+    },
+    teams::TeamMemberships,
+};
+
+/// File contents downloaded at the same time.
+const CONCURRENT_DOWNLOADS: usize = 8;
+
+/// Normalizes the webhook payload into the context rules are evaluated against.
+///
+/// The time is pinned once per event, the searches validators need and their evaluation
+/// must agree on it.
+pub(crate) fn build_context(event: &WebhookEvent, descriptor: &EventDescriptor) -> Context {
+    let repository = event
+        .repository
+        .as_ref()
+        .map(|repository| Repository {
+            owner: login(repository.owner.as_ref()),
+            name: repository.name.clone(),
+            private: repository.private.unwrap_or_default(),
+            topics: repository.topics.clone().unwrap_or_default(),
+        })
+        .unwrap_or_default();
+    let subject = match &event.specific {
+        WebhookEventPayload::PullRequest(payload) => Some(pull_request(&payload.pull_request)),
+        WebhookEventPayload::PullRequestReview(payload) => {
+            Some(pull_request(&payload.pull_request))
+        }
+        WebhookEventPayload::Issues(payload) => Some(issue(&payload.issue)),
+        _ => None,
+    };
+    Context {
+        event: descriptor.name.clone(),
+        action: descriptor.action.clone(),
+        repository,
+        sender: event.sender.as_ref().map(|sender| sender.login.clone()),
+        subject,
+        now: Some(Utc::now()),
+        ..Default::default()
+    }
+}
+
+/// The contexts to evaluate the rules against.
+///
+/// `check_suite` and `status` events are about a commit, rules are evaluated once for each
+/// open pull request it heads. Other events keep their context.
+pub(crate) async fn pull_request_contexts(
+    client: &impl GitHubOperations,
+    event: &WebhookEvent,
+    context: Context,
+) -> Vec<Context> {
+    let (sha, mut numbers) = match &event.specific {
+        WebhookEventPayload::CheckSuite(payload) => {
+            let check_suite = &payload.check_suite;
+            let numbers: Vec<u64> = check_suite["pull_requests"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|pull_request| pull_request["number"].as_u64())
+                .collect();
+            (
+                check_suite["head_sha"].as_str().unwrap_or_default(),
+                numbers,
+            )
+        }
+        WebhookEventPayload::Status(payload) => (payload.sha.as_str(), vec![]),
+        _ => return vec![context],
+    };
+    let Repository { owner, name, .. } = &context.repository;
+    // check suites of forks don't list their pull requests
+    if numbers.is_empty() {
+        match client.pull_requests_for_commit(owner, name, sha).await {
+            Ok(found) => numbers = found,
+            Err(error) => tracing::warn!(sha, %error, "failed to find the pull requests"),
+        }
+    }
+    let mut contexts = vec![];
+    for number in numbers {
+        let issue = IssueRef {
+            owner: owner.clone(),
+            repo: name.clone(),
+            number,
+        };
+        match client.get_pull_request(&issue).await {
+            Ok(found) => contexts.push(Context {
+                subject: Some(pull_request(&found)),
+                ..context.clone()
+            }),
+            Err(error) => tracing::warn!(number, %error, "failed to fetch the pull request"),
+        }
+    }
+    contexts
+}
+
+/// Fetches the data the rules need beyond the webhook payload into the context.
+///
+/// Data that can't be fetched is left out, the validators needing it report an error.
+pub(crate) async fn fetch_requirements(
+    client: &impl GitHubOperations,
+    teams: &TeamMemberships,
+    jira: &JiraIssues,
+    rules: &[&Rule],
+    context: &mut Context,
+) {
+    let mut requirements = Requirements::default();
+    for rule in rules {
+        requirements.extend(rule.requirements());
+    }
+    let issue = issue_ref(context);
+    let mut required_teams = requirements.teams;
+    if let (true, Some(issue), Some(subject)) =
+        (requirements.projects, &issue, &mut context.subject)
+    {
+        match client.project_items(issue).await {
+            Ok(items) => {
+                subject.issue_mut().projects = Some(items.into_iter().map(project).collect())
+            }
+            Err(error) => tracing::warn!(%error, "failed to fetch the projects"),
+        }
+    }
+    if let (Some(issue), Some(Subject::PullRequest(pull_request))) = (issue, &mut context.subject) {
+        if requirements.reviews {
+            match client.list_reviews(&issue).await {
+                Ok(reviews) => {
+                    pull_request.reviews = Some(reviews.into_iter().filter_map(review).collect());
+                }
+                Err(error) => tracing::warn!(%error, "failed to fetch the reviews"),
+            }
+        }
+        if requirements.commits {
+            match client.list_commits(&issue).await {
+                Ok(commits) => {
+                    pull_request.commits = Some(commits.into_iter().map(commit).collect());
+                }
+                Err(error) => tracing::warn!(%error, "failed to fetch the commits"),
+            }
+        }
+        let files = if requirements.files || requirements.contents || requirements.code_owners {
+            client
+                .list_files(&issue)
+                .await
+                .inspect_err(|error| tracing::warn!(%error, "failed to fetch the changed files"))
+                .ok()
+        } else {
+            None
+        };
+        if requirements.files || requirements.contents {
+            pull_request.files = files
+                .as_ref()
+                .map(|files| files.iter().map(changed_file).collect());
+        }
+        if let (true, Some(files)) = (requirements.code_owners, &files) {
+            match code_owners(client, &issue, &pull_request.base_ref, files).await {
+                Ok(owners) => {
+                    required_teams.extend(owners.iter().filter_map(|owner| match owner {
+                        Owner::Team(team) => Some(team.clone()),
+                        _ => None,
+                    }));
+                    pull_request.code_owners = Some(owners);
+                }
+                Err(error) => tracing::warn!(%error, "failed to resolve the code owners"),
+            }
+        }
+    }
+    if requirements.contents {
+        contents(client, rules, context).await;
+    }
+    let searches: BTreeSet<String> = rules
+        .iter()
+        .flat_map(|rule| rule.searches(context))
+        .collect();
+    for query in searches {
+        match client.search_issues(&query).await {
+            Ok(found) => {
+                let mut subjects = vec![];
+                for issue in &found {
+                    subjects.push(found_subject(client, &context.repository, issue).await);
+                }
+                context.search_results.insert(query, subjects);
+            }
+            Err(error) => tracing::warn!(query, %error, "failed to search issues"),
+        }
+    }
+    if let Some(jira) = jira.client() {
+        let keys: BTreeSet<String> = rules
+            .iter()
+            .flat_map(|rule| rule.jira_keys(context))
+            .collect();
+        let mut issues = HashMap::new();
+        for key in keys {
+            match jira.verify(&key).await {
+                Ok(issue) => {
+                    issues.insert(key, issue);
+                }
+                Err(error) => tracing::warn!(key, %error, "failed to verify the Jira issue"),
+            }
+        }
+        context.jira_issues = Some(issues);
+    }
+    for team in required_teams {
+        let Some((org, slug)) = team.split_once('/') else {
+            tracing::warn!(team, "teams are referred to as `org/team_slug`");
+            continue;
+        };
+        match teams.members(client, org, slug).await {
+            Ok(members) => {
+                context.teams.insert(team, members);
+            }
+            Err(error) => tracing::warn!(team, %error, "failed to fetch the team members"),
+        }
+    }
+}
+
+/// Fetches the tree of the pull request's head and the contents of the files rules read.
+///
+/// Rules pick the files to read from the tree, skipping large ones and those beyond their cap.
+/// Files that can't be fetched are left without content.
+async fn contents(client: &impl GitHubOperations, rules: &[&Rule], context: &mut Context) {
+    let Some(issue) = issue_ref(context) else {
+        return;
+    };
+    let Some(Subject::PullRequest(pull_request)) = &mut context.subject else {
+        return;
+    };
+    let head_sha = pull_request.head_sha.clone();
+    let tree = match client.tree(&issue.owner, &issue.repo, &head_sha).await {
+        Ok(tree) => tree,
+        Err(error) => {
+            tracing::warn!(%error, "failed to fetch the tree");
+            return;
+        }
+    };
+    pull_request.tree = Some(
+        tree.into_iter()
+            .map(|entry| TreeEntry {
+                path: entry.path,
+                size: entry.size,
+            })
+            .collect(),
+    );
+    let paths: BTreeSet<String> = rules
+        .iter()
+        .flat_map(|rule| rule.content_paths(context))
+        .collect();
+    let contents = stream::iter(paths)
+        .map(|path| async {
+            let content = client
+                .file_content(&issue.owner, &issue.repo, &path, &head_sha)
+                .await
+                .inspect_err(
+                    |error| tracing::warn!(file = path, %error, "failed to fetch the content"),
+                )
+                .ok()
+                .flatten();
+            FileContent { path, content }
+        })
+        .buffered(CONCURRENT_DOWNLOADS)
+        .collect()
+        .await;
+    if let Some(Subject::PullRequest(pull_request)) = &mut context.subject {
+        pull_request.contents = Some(contents);
+    }
+}
+
+/// The owners of the files a pull request changes, per the CODEOWNERS file of its base branch.
+///
+/// Renamed files are owned by the owners of both their paths.
+async fn code_owners(
+    client: &impl GitHubOperations,
+    pull_request: &IssueRef,
+    base_ref: &str,
+    files: &[remote::ChangedFile],
+) -> Result<Vec<Owner>, RemoteError> {
+    let mut codeowners = None;
+    for path in CODEOWNERS_PATHS {
+        let content = client
+            .file_content(&pull_request.owner, &pull_request.repo, path, base_ref)
+            .await?;
+        if let Some(content) = content {
+            codeowners = Some(CodeOwners::parse(&content));
+            break;
+        }
+    }
+    let Some(codeowners) = codeowners else {
+        return Ok(vec![]);
+    };
+    let paths = files.iter().flat_map(|file| {
+        std::iter::once(file.filename.as_str()).chain(file.previous_filename.as_deref())
+    });
+    Ok(codeowners.owners_for(paths))
+}
+
+fn commit(commit: remote::Commit) -> Commit {
+    Commit {
+        sha: commit.sha,
+        message: commit.message,
+        parents: commit.parents,
+    }
+}
+
+fn project(item: remote::ProjectItem) -> Project {
+    Project {
+        title: item.title,
+        column: item.status,
+    }
+}
+
+fn changed_file(file: &remote::ChangedFile) -> ChangedFile {
+    let status = match file.status {
+        remote::FileStatus::Added => FileStatus::Added,
+        remote::FileStatus::Modified => FileStatus::Modified,
+        remote::FileStatus::Removed => FileStatus::Removed,
+        remote::FileStatus::Renamed => FileStatus::Renamed,
+        remote::FileStatus::Copied => FileStatus::Copied,
+        remote::FileStatus::Changed => FileStatus::Changed,
+        remote::FileStatus::Unchanged => FileStatus::Unchanged,
+    };
+    ChangedFile {
+        filename: file.filename.clone(),
+        status,
+        additions: file.additions,
+        deletions: file.deletions,
+        patch: file.patch.clone(),
+    }
+}
+
+/// Reviews of deleted users can't be attributed to a reviewer.
+fn review(review: PullRequestReview) -> Option<Review> {
+    let state = match review.state {
+        PullRequestReviewState::Approved => ReviewState::Approved,
+        PullRequestReviewState::ChangesRequested => ReviewState::ChangesRequested,
+        PullRequestReviewState::Commented => ReviewState::Commented,
+        PullRequestReviewState::Dismissed => ReviewState::Dismissed,
+        PullRequestReviewState::Pending => ReviewState::Pending,
+    };
+    Some(Review {
+        reviewer: review.reviewer?,
+        state,
+    })
+}
+
+/// The subject of an issue or pull request a search found.
+///
+/// Searches list pull requests as issues, they are fetched for the pull request actions.
+async fn found_subject(
+    client: &impl GitHubOperations,
+    repository: &Repository,
+    found: &issues::Issue,
+) -> Subject {
+    if found.pull_request.is_some() {
+        let pull_request = IssueRef {
+            owner: repository.owner.clone(),
+            repo: repository.name.clone(),
+            number: found.number,
+        };
+        match client.get_pull_request(&pull_request).await {
+            Ok(pull_request) => return self::pull_request(&pull_request),
+            Err(error) => {
+                tracing::warn!(number = found.number, %error, "failed to fetch the pull request")
+            }
+        }
+    }
+    issue(found)
+}
+
+/// The contexts of the issues and pull requests a searching validator like `stale` found.
+pub(crate) fn found_contexts(context: &Context, numbers: &[u64]) -> Vec<Context> {
+    let found = context.search_results.values().flatten();
+    numbers
+        .iter()
+        .filter_map(|number| {
+            let subject = found
+                .clone()
+                .find(|subject| subject.issue().number == *number)?;
+            Some(Context {
+                subject: Some(subject.clone()),
+                search_results: HashMap::new(),
+                ..context.clone()
+            })
+        })
+        .collect()
+}
+
+/// The issue or pull request actions of a rule are applied to.
+pub(crate) fn issue_ref(context: &Context) -> Option<IssueRef> {
+    let subject = context.subject.as_ref()?;
+    Some(IssueRef {
+        owner: context.repository.owner.clone(),
+        repo: context.repository.name.clone(),
+        number: subject.issue().number,
+    })
+}
+
+fn login(author: Option<&Author>) -> String {
+    author
+        .map(|author| author.login.clone())
+        .unwrap_or_default()
+}
+
+fn pull_request(pull_request: &pulls::PullRequest) -> Subject {
+    Subject::PullRequest(Box::new(PullRequest {
+        issue: Issue {
+            number: pull_request.number,
+            title: pull_request.title.clone().unwrap_or_default(),
+            body: pull_request.body.clone(),
+            author: login(pull_request.user.as_deref()),
+            labels: pull_request
+                .labels
+                .iter()
+                .flatten()
+                .map(|label| label.name.clone())
+                .collect(),
+            milestone: pull_request
+                .milestone
+                .as_ref()
+                .map(|milestone| milestone.title.clone()),
+            assignees: pull_request
+                .assignees
+                .iter()
+                .flatten()
+                .map(|assignee| assignee.login.clone())
+                .collect(),
+            created_at: pull_request.created_at,
+            updated_at: pull_request.updated_at,
+            projects: None,
+        },
+        base_ref: pull_request.base.ref_field.clone(),
+        head_ref: pull_request.head.ref_field.clone(),
+        head_sha: pull_request.head.sha.clone(),
+        draft: pull_request.draft.unwrap_or_default(),
+        requested_reviewers: pull_request
+            .requested_reviewers
+            .iter()
+            .flatten()
+            .map(|reviewer| reviewer.login.clone())
+            .collect(),
+        reviews: None,
+        files: None,
+        tree: None,
+        contents: None,
+        commits: None,
+        code_owners: None,
+    }))
+}
+
+fn issue(issue: &issues::Issue) -> Subject {
+    Subject::Issue(Issue {
+        number: issue.number,
+        title: issue.title.clone(),
+        body: issue.body.clone(),
+        author: issue.user.login.clone(),
+        labels: issue
+            .labels
+            .iter()
+            .map(|label| label.name.clone())
+            .collect(),
+        milestone: issue
+            .milestone
+            .as_ref()
+            .map(|milestone| milestone.title.clone()),
+        assignees: issue
+            .assignees
+            .iter()
+            .map(|assignee| assignee.login.clone())
+            .collect(),
+        created_at: Some(issue.created_at),
+        updated_at: Some(issue.updated_at),
+        projects: None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use mergeable_compatibility_layer::{
+        codeowners::Owner,
+        configuration::Configuration,
+        evaluation::{
+            context::{
+                self, Context, Issue, Project, PullRequest, Repository, Review, ReviewState,
+                Subject,
+            },
+            Status,
+        },
+    };
+
+    use chrono::Utc;
+    use octocrab::models::webhook_events::WebhookEvent;
+    use serde_json::json;
+
+    use super::{build_context, fetch_requirements, pull_request_contexts};
+    use crate::routes::event_handler::{
+        dispatch::EventDescriptor,
+        jira::JiraIssues,
+        remote::{
+            self,
+            fake::{FakeGitHub, FakePullRequest},
+            ChangedFile, FileStatus, ProjectItem, PullRequestReview, PullRequestReviewState,
+        },
+        teams::TeamMemberships,
+    };
+
+    #[tokio::test]
+    async fn fetches_the_data_validators_need() {
+        let configuration: Configuration = serde_yaml::from_str(
+            r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    filter:
+      - do: author
+        team: org/staff
+    validate:
+      - do: approvals
+        min:
+          count: 1
+        limit:
+          teams: [org/core, org/gone]
+        required:
+          owners: true
+      - do: changeset
+        must_include:
+          regex: ^docs/
+      - do: commit
+        message:
+          regex: ^feat
+      - do: contents
+        files:
+          pr_diff: true
+        must_exclude:
+          regex: todo
+"#,
+        )
+        .unwrap();
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.commit_file(
+            "owner",
+            "repo",
+            ".github/CODEOWNERS",
+            Some("* @octocat\n/docs/ @org/docs\n"),
+        );
+        github.commit_file("owner", "repo", "CODEOWNERS", Some("* @ignored\n"));
+        github.commit_file("owner", "repo", "src/lib.rs", Some("// todo\n"));
+        github.commit_file("owner", "repo", "docs/index.md", Some("# Docs\n"));
+        let large = "x".repeat(context::FileContent::MAX_SIZE as usize + 1);
+        github.commit_file("owner", "repo", "docs/large.txt", Some(&large));
+        github.add_pull_request(FakePullRequest {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 7,
+            commits: vec![remote::Commit {
+                sha: "abc123".to_owned(),
+                message: "feat: docs".to_owned(),
+                author: None,
+                committed_at: None,
+                parents: 1,
+            }],
+            files: vec![
+                ChangedFile {
+                    filename: "docs/index.md".to_owned(),
+                    previous_filename: Some("README.md".to_owned()),
+                    status: FileStatus::Renamed,
+                    additions: 0,
+                    deletions: 0,
+                    patch: None,
+                },
+                ChangedFile {
+                    filename: "src/lib.rs".to_owned(),
+                    previous_filename: None,
+                    status: FileStatus::Modified,
+                    additions: 1,
+                    deletions: 0,
+                    patch: None,
+                },
+                ChangedFile {
+                    filename: "docs/large.txt".to_owned(),
+                    previous_filename: None,
+                    status: FileStatus::Added,
+                    additions: 1,
+                    deletions: 0,
+                    patch: None,
+                },
+            ],
+            reviews: vec![
+                PullRequestReview {
+                    id: 1,
+                    reviewer: Some("octocat".to_owned()),
+                    state: PullRequestReviewState::Approved,
+                    submitted_at: None,
+                },
+                PullRequestReview {
+                    id: 2,
+                    reviewer: None,
+                    state: PullRequestReviewState::Approved,
+                    submitted_at: None,
+                },
+            ],
+            ..Default::default()
+        });
+        github
+            .state()
+            .teams
+            .insert("org/core".to_owned(), vec!["octocat".to_owned()]);
+        github
+            .state()
+            .teams
+            .insert("org/docs".to_owned(), vec!["monalisa".to_owned()]);
+        github
+            .state()
+            .teams
+            .insert("org/staff".to_owned(), vec!["hubot".to_owned()]);
+        let mut context = Context {
+            event: "pull_request".to_owned(),
+            repository: Repository {
+                owner: "owner".to_owned(),
+                name: "repo".to_owned(),
+                ..Default::default()
+            },
+            subject: Some(Subject::PullRequest(Box::new(PullRequest {
+                issue: Issue {
+                    number: 7,
+                    ..Default::default()
+                },
+                base_ref: "main".to_owned(),
+                ..Default::default()
+            }))),
+            ..Default::default()
+        };
+
+        let rules: Vec<_> = configuration.rules().iter().collect();
+        fetch_requirements(
+            &github,
+            &TeamMemberships::default(),
+            &JiraIssues::default(),
+            &rules,
+            &mut context,
+        )
+        .await;
+
+        let pull_request = context
+            .subject
+            .as_ref()
+            .and_then(|subject| subject.pull_request())
+            .unwrap();
+        assert_eq!(
+            pull_request.reviews,
+            Some(vec![Review {
+                reviewer: "octocat".to_owned(),
+                state: ReviewState::Approved,
+            }])
+        );
+        assert_eq!(
+            pull_request.commits,
+            Some(vec![context::Commit {
+                sha: "abc123".to_owned(),
+                message: "feat: docs".to_owned(),
+                parents: 1,
+            }])
+        );
+        assert_eq!(
+            pull_request.files,
+            Some(vec![
+                context::ChangedFile {
+                    filename: "docs/index.md".to_owned(),
+                    status: context::FileStatus::Renamed,
+                    additions: 0,
+                    deletions: 0,
+                    patch: None,
+                },
+                context::ChangedFile {
+                    filename: "src/lib.rs".to_owned(),
+                    status: context::FileStatus::Modified,
+                    additions: 1,
+                    deletions: 0,
+                    patch: None,
+                },
+                context::ChangedFile {
+                    filename: "docs/large.txt".to_owned(),
+                    status: context::FileStatus::Added,
+                    additions: 1,
+                    deletions: 0,
+                    patch: None,
+                }
+            ])
+        );
+        assert_eq!(
+            pull_request.contents,
+            Some(vec![
+                context::FileContent {
+                    path: "docs/index.md".to_owned(),
+                    content: Some("# Docs\n".to_owned()),
+                },
+                context::FileContent {
+                    path: "src/lib.rs".to_owned(),
+                    content: Some("// todo\n".to_owned()),
+                },
+            ])
+        );
+        assert!(!github
+            .state()
+            .file_reads
+            .iter()
+            .any(|(path, _)| path == "docs/large.txt"));
+        assert_eq!(
+            pull_request.code_owners,
+            Some(vec![
+                Owner::Team("org/docs".to_owned()),
+                Owner::User("octocat".to_owned())
+            ])
+        );
+        assert_eq!(context.teams["org/core"], ["octocat"]);
+        assert_eq!(context.teams["org/docs"], ["monalisa"]);
+        assert_eq!(context.teams["org/staff"], ["hubot"]);
+        assert!(!context.teams.contains_key("org/gone"));
+    }
+
+    #[tokio::test]
+    async fn reads_the_files_of_large_repositories_up_to_the_cap() {
+        let configuration: Configuration = serde_yaml::from_str(
+            r#"
+version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: contents
+        must_include:
+          regex: license
+"#,
+        )
+        .unwrap();
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        let files = context::FileContent::MAX_FILES + 5;
+        for index in 0..files {
+            github.commit_file(
+                "owner",
+                "repo",
+                &format!("src/{index:03}.rs"),
+                Some("// license"),
+            );
+        }
+        let mut context = Context {
+            event: "pull_request".to_owned(),
+            repository: Repository {
+                owner: "owner".to_owned(),
+                name: "repo".to_owned(),
+                ..Default::default()
+            },
+            subject: Some(Subject::PullRequest(Box::new(PullRequest {
+                issue: Issue {
+                    number: 7,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }))),
+            ..Default::default()
+        };
+
+        let rules: Vec<_> = configuration.rules().iter().collect();
+        fetch_requirements(
+            &github,
+            &TeamMemberships::default(),
+            &JiraIssues::default(),
+            &rules,
+            &mut context,
+        )
+        .await;
+
+        assert_eq!(
+            github.state().file_reads.len(),
+            context::FileContent::MAX_FILES
+        );
+        let verdict = rules[0].evaluate(&context);
+        assert_eq!(verdict.status(), Status::Pass);
+        assert_eq!(
+            verdict.validators[0].message,
+            "the content of 100 file(s) matches; skipped 5 file(s) beyond the first 100"
+        );
+    }
+
+    #[test]
+    fn pins_the_time_of_the_event() {
+        let event = WebhookEvent::try_from_header_and_body(
+            "schedule",
+            &serde_json::to_vec(&json!({ "schedule": "repository", "workflow": "" })).unwrap(),
+        )
+        .unwrap();
+        let descriptor = EventDescriptor {
+            name: "schedule".to_owned(),
+            action: Some("repository".to_owned()),
+        };
+
+        let before = Utc::now();
+        let context = build_context(&event, &descriptor);
+        let now = context.now.unwrap();
+
+        assert!(before <= now && now <= Utc::now());
+        assert_eq!(context.now(), now);
+        assert_eq!(context.clone().now(), now);
+    }
+
+    #[tokio::test]
+    async fn fetches_the_projects_of_issues() {
+        let configuration: Configuration = serde_yaml::from_str(
+            r#"
+version: 2
+mergeable:
+  - when: issues.*
+    validate:
+      - do: project
+        must_include:
+          regex: ^Sprint
+"#,
+        )
+        .unwrap();
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.add_pull_request(FakePullRequest {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 3,
+            projects: vec![ProjectItem {
+                title: "Sprint 12".to_owned(),
+                status: Some("In progress".to_owned()),
+            }],
+            ..Default::default()
+        });
+        let mut context = Context {
+            event: "issues".to_owned(),
+            repository: Repository {
+                owner: "owner".to_owned(),
+                name: "repo".to_owned(),
+                ..Default::default()
+            },
+            subject: Some(Subject::Issue(Issue {
+                number: 3,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let rules: Vec<_> = configuration.rules().iter().collect();
+        fetch_requirements(
+            &github,
+            &TeamMemberships::default(),
+            &JiraIssues::default(),
+            &rules,
+            &mut context,
+        )
+        .await;
+
+        assert_eq!(
+            context.subject.unwrap().issue().projects,
+            Some(vec![Project {
+                title: "Sprint 12".to_owned(),
+                column: Some("In progress".to_owned()),
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_the_pull_requests_behind_commits() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        for (number, head_sha, closed) in [(1, "abc", false), (2, "abc", true), (3, "def", false)] {
+            github.add_pull_request(FakePullRequest {
+                owner: "owner".to_owned(),
+                repo: "repo".to_owned(),
+                number,
+                head_ref: format!("feature-{number}"),
+                base_ref: "main".to_owned(),
+                head_sha: head_sha.to_owned(),
+                closed,
+                ..Default::default()
+            });
+        }
+        let context = Context {
+            repository: Repository {
+                owner: "owner".to_owned(),
+                name: "repo".to_owned(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let heads = |contexts: Vec<Context>| -> Vec<(u64, String)> {
+            contexts
+                .iter()
+                .filter_map(|context| context.subject.as_ref()?.pull_request())
+                .map(|pull_request| (pull_request.issue.number, pull_request.head_ref.clone()))
+                .collect()
+        };
+
+        let status = WebhookEvent::try_from_header_and_body(
+            "status",
+            &serde_json::to_vec(&json!({
+                "branches": [],
+                "commit": {},
+                "context": "ci",
+                "created_at": "2024-05-01T10:00:00Z",
+                "id": 1,
+                "name": "owner/repo",
+                "sha": "abc",
+                "state": "success",
+                "updated_at": "2024-05-01T10:00:00Z",
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let contexts = pull_request_contexts(&github, &status, context.clone()).await;
+        assert_eq!(heads(contexts), [(1, "feature-1".to_owned())]);
+
+        let check_suite = WebhookEvent::try_from_header_and_body(
+            "check_suite",
+            &serde_json::to_vec(&json!({
+                "action": "completed",
+                "check_suite": { "head_sha": "def", "pull_requests": [{ "number": 3 }] },
+            }))
+            .unwrap(),
+        )
+        .unwrap();
+        let contexts = pull_request_contexts(&github, &check_suite, context).await;
+        assert_eq!(heads(contexts), [(3, "feature-3".to_owned())]);
+    }
+}
@@ -0,0 +1,117 @@
+use mergeable_compatibility_layer::configuration::{Configuration, Rule};
+use octocrab::models::webhook_events::{WebhookEvent, WebhookEventPayload, WebhookEventType};
+use serde::Serialize;
+
+/// Name and action of a webhook event as referenced by the `when` clause of a rule,
+/// e.g. `pull_request` and `opened`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EventDescriptor {
+    pub(crate) name: String,
+    pub(crate) action: Option<String>,
+}
+
+impl From<&WebhookEvent> for EventDescriptor {
+    fn from(event: &WebhookEvent) -> Self {
+        let name = match &event.kind {
+            WebhookEventType::Unknown(name) => name.clone(),
+            kind => snake_case(kind).unwrap_or_default(),
+        };
+        let action = match &event.specific {
+            WebhookEventPayload::PullRequest(payload) => snake_case(&payload.action),
+            WebhookEventPayload::PullRequestReview(payload) => snake_case(&payload.action),
+            WebhookEventPayload::PullRequestReviewComment(payload) => snake_case(&payload.action),
+            WebhookEventPayload::Issues(payload) => snake_case(&payload.action),
+            WebhookEventPayload::IssueComment(payload) => snake_case(&payload.action),
+            WebhookEventPayload::CheckSuite(payload) => snake_case(&payload.action),
+            WebhookEventPayload::CheckRun(payload) => snake_case(&payload.action),
+            WebhookEventPayload::Label(payload) => snake_case(&payload.action),
+            WebhookEventPayload::Milestone(payload) => snake_case(&payload.action),
+            WebhookEventPayload::Unknown(payload) => payload
+                .get("action")
+                .and_then(|action| action.as_str())
+                .map(str::to_owned),
+            _ => None,
+        };
+        Self { name, action }
+    }
+}
+
+/// The webhook enums serialize to the snake case names GitHub uses.
+fn snake_case(value: &impl Serialize) -> Option<String> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => Some(name),
+        _ => None,
+    }
+}
+
+/// Selects the rules of the configuration whose `when` clause matches the event.
+pub(crate) fn matching_rules<'a>(
+    configuration: &'a Configuration,
+    event: &'a EventDescriptor,
+) -> impl Iterator<Item = &'a Rule> {
+    configuration.rules_triggered_by(&event.name, event.action.as_deref())
+}
+
+#[cfg(test)]
+mod test {
+    use mergeable_compatibility_layer::configuration::Configuration;
+    use octocrab::models::webhook_events::WebhookEvent;
+    use serde_json::json;
+
+    use super::{matching_rules, EventDescriptor};
+
+    #[test]
+    fn describes_events_with_and_without_actions() {
+        let event = WebhookEvent::try_from_header_and_body(
+            "check_suite",
+            &serde_json::to_vec(&json!({"action": "requested", "check_suite": {}})).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            EventDescriptor::from(&event),
+            EventDescriptor {
+                name: "check_suite".to_owned(),
+                action: Some("requested".to_owned()),
+            }
+        );
+
+        let event = WebhookEvent::try_from_header_and_body(
+            "custom_event",
+            &serde_json::to_vec(&json!({})).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            EventDescriptor::from(&event),
+            EventDescriptor {
+                name: "custom_event".to_owned(),
+                action: None,
+            }
+        );
+    }
+
+    #[test]
+    fn selects_rules_by_event_and_action() {
+        let configuration: Configuration = serde_yaml::from_str(
+            r#"
+version: 2
+mergeable:
+  - when: pull_request.opened
+    name: opened
+  - when: pull_request.*, issues.opened
+    name: any pull request
+  - when: check_suite.*
+    name: check suites
+"#,
+        )
+        .unwrap();
+        let event = EventDescriptor {
+            name: "pull_request".to_owned(),
+            action: Some("opened".to_owned()),
+        };
+
+        let names: Vec<_> = matching_rules(&configuration, &event)
+            .map(|rule| rule.name().unwrap())
+            .collect();
+        assert_eq!(names, ["opened", "any pull request"]);
+    }
+}
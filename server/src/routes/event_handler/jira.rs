@@ -0,0 +1,309 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use axum::http::uri::InvalidUri;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Empty};
+use hyper::{header, Request, StatusCode, Uri};
+use hyper_tls::HttpsConnector;
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use mergeable_compatibility_layer::evaluation::context::JiraIssue;
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::config::JiraConfiguration;
+
+/// How long verified keys are used, status changes don't reach the application.
+const ISSUES_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Keys come from titles and branches authors choose, so the cache is capped.
+const MAX_CACHED_ISSUES: usize = 1000;
+
+#[derive(Debug, Error)]
+pub enum JiraError {
+    #[error("invalid issue url: {0}")]
+    InvalidUri(#[from] InvalidUri),
+    #[error("invalid request: {0}")]
+    Request(#[from] hyper::http::Error),
+    #[error("request to Jira failed: {0}")]
+    Client(#[from] hyper_util::client::legacy::Error),
+    #[error("failed to read the response: {0}")]
+    Body(#[from] hyper::Error),
+    #[error("unexpected response: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Jira answered with {0}")]
+    Status(StatusCode),
+}
+
+#[derive(Debug)]
+struct CachedIssue {
+    fetched_at: Instant,
+    issue: JiraIssue,
+}
+
+/// Verifies the issue keys validators extract against the Jira REST API, shared by the events
+/// of all installations. Verification is disabled without a configuration.
+#[derive(Clone, Default)]
+pub struct JiraIssues(Option<Arc<JiraClient>>);
+
+pub(crate) struct JiraClient {
+    configuration: JiraConfiguration,
+    http: Client<HttpsConnector<HttpConnector>, Empty<Bytes>>,
+    cache: RwLock<HashMap<String, CachedIssue>>,
+}
+
+impl JiraIssues {
+    pub(crate) fn new(configuration: Option<JiraConfiguration>) -> Self {
+        Self(configuration.map(|configuration| {
+            Arc::new(JiraClient {
+                configuration,
+                http: Client::builder(TokioExecutor::new()).build(HttpsConnector::new()),
+                cache: RwLock::default(),
+            })
+        }))
+    }
+
+    pub(crate) fn client(&self) -> Option<&JiraClient> {
+        self.0.as_deref()
+    }
+}
+
+impl JiraClient {
+    /// Looks the issue up unless it was verified recently. Failures aren't cached.
+    ///
+    /// Keys not shaped like `PROJ-123` don't exist and never reach Jira, they end up in the
+    /// path of an authenticated request.
+    pub(crate) async fn verify(&self, key: &str) -> Result<JiraIssue, JiraError> {
+        if !is_issue_key(key) {
+            return Ok(JiraIssue::Missing);
+        }
+        if let Some(cached) = self.cache.read().expect("jira lock poisoned").get(key) {
+            if cached.fetched_at.elapsed() < ISSUES_TTL {
+                return Ok(cached.issue.clone());
+            }
+        }
+        let issue = match self.status(key).await? {
+            None => JiraIssue::Missing,
+            Some(status) if self.is_allowed(&status) => JiraIssue::Valid,
+            Some(status) => JiraIssue::Disallowed { status },
+        };
+        let mut cache = self.cache.write().expect("jira lock poisoned");
+        if cache.len() >= MAX_CACHED_ISSUES {
+            cache.retain(|_, cached| cached.fetched_at.elapsed() < ISSUES_TTL);
+        }
+        if cache.len() >= MAX_CACHED_ISSUES {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.fetched_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+        cache.insert(
+            key.to_owned(),
+            CachedIssue {
+                fetched_at: Instant::now(),
+                issue: issue.clone(),
+            },
+        );
+        Ok(issue)
+    }
+
+    fn is_allowed(&self, status: &str) -> bool {
+        let allowed = &self.configuration.allowed_statuses;
+        allowed.is_empty()
+            || allowed
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(status))
+    }
+
+    /// The name of the status of the issue, `None` if it doesn't exist.
+    async fn status(&self, key: &str) -> Result<Option<String>, JiraError> {
+        #[derive(Deserialize)]
+        struct Issue {
+            fields: Fields,
+        }
+
+        #[derive(Deserialize)]
+        struct Fields {
+            status: Status,
+        }
+
+        #[derive(Deserialize)]
+        struct Status {
+            name: String,
+        }
+
+        let base_url = self.configuration.base_url.to_string();
+        let uri: Uri = format!(
+            "{}/rest/api/2/issue/{key}?fields=status",
+            base_url.trim_end_matches('/')
+        )
+        .parse()?;
+        let mut request = Request::get(uri).header(header::ACCEPT, "application/json");
+        if let Some(token) = &self.configuration.token {
+            request = request.header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token.expose_secret()),
+            );
+        }
+        let response = self.http.request(request.body(Empty::new())?).await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => {
+                let body = response.into_body().collect().await?.to_bytes();
+                let issue: Issue = serde_json::from_slice(&body)?;
+                Ok(Some(issue.fields.status.name))
+            }
+            status => Err(JiraError::Status(status)),
+        }
+    }
+}
+
+/// Whether the key is shaped like `^[A-Z][A-Z0-9_]*-[0-9]+$`.
+fn is_issue_key(key: &str) -> bool {
+    let Some((project, number)) = key.split_once('-') else {
+        return false;
+    };
+    let mut project = project.chars();
+    project
+        .next()
+        .is_some_and(|first| first.is_ascii_uppercase())
+        && project.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !number.is_empty()
+        && number.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        extract::{Path, State},
+        http::HeaderMap,
+        response::IntoResponse,
+        routing::get,
+        Json, Router,
+    };
+    use hyper::StatusCode;
+    use mergeable_compatibility_layer::evaluation::context::JiraIssue;
+    use secrecy::SecretString;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    use super::{JiraError, JiraIssues};
+    use crate::config::JiraConfiguration;
+
+    /// The issue keys requested from the stand-in and their authorization header.
+    type Requests = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+    /// A local stand-in for the Jira REST API.
+    async fn start_jira() -> (String, Requests) {
+        async fn issue(
+            State(requests): State<Requests>,
+            Path(key): Path<String>,
+            headers: HeaderMap,
+        ) -> impl IntoResponse {
+            let authorization = headers
+                .get("authorization")
+                .map(|value| value.to_str().unwrap().to_owned());
+            requests.lock().unwrap().push((key.clone(), authorization));
+            let status = match key.as_str() {
+                "OPS-1" => "In Progress",
+                "OPS-2" => "Done",
+                "OPS-5" => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+                _ => return StatusCode::NOT_FOUND.into_response(),
+            };
+            Json(json!({ "key": key, "fields": { "status": { "name": status } } })).into_response()
+        }
+
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let routes = Router::new()
+            .route("/rest/api/2/issue/:key", get(issue))
+            .with_state(requests.clone());
+        tokio::spawn(async move { axum::serve(listener, routes).await.unwrap() });
+        (base_url, requests)
+    }
+
+    #[tokio::test]
+    async fn verifies_and_caches_issue_keys() {
+        let (base_url, requests) = start_jira().await;
+        let jira = JiraIssues::new(Some(JiraConfiguration {
+            base_url: base_url.parse().unwrap(),
+            token: Some(SecretString::new("token".to_owned())),
+            allowed_statuses: vec!["in progress".to_owned()],
+        }));
+        let client = jira.client().unwrap();
+
+        assert_eq!(client.verify("OPS-1").await.unwrap(), JiraIssue::Valid);
+        assert_eq!(
+            client.verify("OPS-2").await.unwrap(),
+            JiraIssue::Disallowed {
+                status: "Done".to_owned()
+            }
+        );
+        assert_eq!(client.verify("OPS-3").await.unwrap(), JiraIssue::Missing);
+        assert!(matches!(
+            client.verify("OPS-5").await,
+            Err(JiraError::Status(StatusCode::INTERNAL_SERVER_ERROR))
+        ));
+        assert_eq!(client.verify("OPS-1").await.unwrap(), JiraIssue::Valid);
+        assert!(client.verify("OPS-5").await.is_err());
+
+        let requests = requests.lock().unwrap().clone();
+        let keys: Vec<_> = requests.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["OPS-1", "OPS-2", "OPS-3", "OPS-5", "OPS-5"]);
+        assert_eq!(requests[0].1.as_deref(), Some("Bearer token"));
+    }
+
+    #[tokio::test]
+    async fn never_requests_keys_that_are_not_issue_keys() {
+        let (base_url, requests) = start_jira().await;
+        let jira = JiraIssues::new(Some(JiraConfiguration {
+            base_url: base_url.parse().unwrap(),
+            token: Some(SecretString::new("token".to_owned())),
+            allowed_statuses: vec![],
+        }));
+        let client = jira.client().unwrap();
+
+        for key in [
+            "../../rest/api/2/myself?x=",
+            "OPS-1/../../myself",
+            "OPS 1",
+            "ops-1",
+            "OPS-",
+            "-1",
+        ] {
+            assert_eq!(
+                client.verify(key).await.unwrap(),
+                JiraIssue::Missing,
+                "{key}"
+            );
+        }
+        assert_eq!(client.verify("OPS_2X-1").await.unwrap(), JiraIssue::Missing);
+
+        let requests = requests.lock().unwrap().clone();
+        let keys: Vec<_> = requests.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["OPS_2X-1"]);
+    }
+
+    #[test]
+    fn is_disabled_without_configuration() {
+        assert!(JiraIssues::default().client().is_none());
+    }
+}
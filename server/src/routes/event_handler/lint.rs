@@ -0,0 +1,279 @@
+use mergeable_compatibility_layer::configuration::{
+    diagnostics::{Diagnostic, Severity},
+    parse::Location,
+    Configuration,
+};
+use octocrab::models::webhook_events::{WebhookEvent, WebhookEventPayload};
+
+use super::{
+    configuration::CONFIGURATION_PATH,
+    dispatch::EventDescriptor,
+    remote::{
+        AnnotationLevel, CheckAnnotation, CheckConclusion, CheckRun, FileStatus, GitHubOperations,
+        IssueRef, RemoteError,
+    },
+};
+
+/// Name of the check run reporting the problems of a changed configuration.
+pub(crate) const LINT_CHECK_NAME: &str = "mergeable config lint";
+
+/// GitHub accepts at most this many annotations per request.
+const MAX_ANNOTATIONS: usize = 50;
+
+/// The pull request actions which may change the configuration file.
+const LINTED_ACTIONS: &[&str] = &["opened", "reopened", "synchronize"];
+
+/// Publishes a lint check run for pull requests changing the configuration file.
+pub(crate) async fn lint_pull_request(
+    client: &impl GitHubOperations,
+    event: &WebhookEvent,
+    descriptor: &EventDescriptor,
+) -> Result<(), RemoteError> {
+    let (WebhookEventPayload::PullRequest(payload), Some(repository)) =
+        (&event.specific, &event.repository)
+    else {
+        return Ok(());
+    };
+    let linted = descriptor
+        .action
+        .as_deref()
+        .is_some_and(|action| LINTED_ACTIONS.contains(&action));
+    if !linted {
+        return Ok(());
+    }
+    let pull_request = IssueRef {
+        owner: repository
+            .owner
+            .as_ref()
+            .map(|owner| owner.login.clone())
+            .unwrap_or_default(),
+        repo: repository.name.clone(),
+        number: payload.pull_request.number,
+    };
+    let changed = client
+        .list_files(&pull_request)
+        .await?
+        .iter()
+        .any(|file| file.filename == CONFIGURATION_PATH && file.status != FileStatus::Removed);
+    if !changed {
+        return Ok(());
+    }
+    let head_sha = &payload.pull_request.head.sha;
+    let Some(yaml) = client
+        .file_content(
+            &pull_request.owner,
+            &pull_request.repo,
+            CONFIGURATION_PATH,
+            head_sha,
+        )
+        .await?
+    else {
+        return Ok(());
+    };
+    let check_run = lint(&yaml, head_sha);
+    tracing::info!(
+        pull_request = pull_request.number,
+        conclusion = ?check_run.conclusion,
+        "configuration linted"
+    );
+    client
+        .create_check_run(&pull_request.owner, &pull_request.repo, &check_run)
+        .await?;
+    Ok(())
+}
+
+/// Reports why the configuration doesn't parse, or its diagnostics otherwise.
+pub(crate) fn lint(yaml: &str, head_sha: &str) -> CheckRun {
+    let configuration = match yaml.parse::<Configuration>() {
+        Ok(configuration) => configuration,
+        Err(error) => {
+            return CheckRun {
+                name: LINT_CHECK_NAME.to_owned(),
+                head_sha: head_sha.to_owned(),
+                conclusion: CheckConclusion::Failure,
+                title: "The configuration can't be parsed".to_owned(),
+                summary: error.to_string(),
+                text: None,
+                annotations: vec![annotation(
+                    error.location(),
+                    AnnotationLevel::Failure,
+                    error.to_string(),
+                )],
+            }
+        }
+    };
+    let diagnostics = configuration.validate();
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity() == severity)
+            .count()
+    };
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    CheckRun {
+        name: LINT_CHECK_NAME.to_owned(),
+        head_sha: head_sha.to_owned(),
+        conclusion: if errors > 0 {
+            CheckConclusion::Failure
+        } else {
+            CheckConclusion::Success
+        },
+        title: match (errors, warnings) {
+            (0, 0) => "The configuration is valid".to_owned(),
+            _ => format!("{errors} error(s), {warnings} warning(s)"),
+        },
+        summary: diagnostics
+            .iter()
+            .map(|diagnostic| format!("- {diagnostic}"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        text: None,
+        annotations: diagnostics
+            .iter()
+            .take(MAX_ANNOTATIONS)
+            .map(|diagnostic| diagnostic_annotation(yaml, diagnostic))
+            .collect(),
+    }
+}
+
+fn diagnostic_annotation(yaml: &str, diagnostic: &Diagnostic) -> CheckAnnotation {
+    let level = match diagnostic.severity() {
+        Severity::Warning => AnnotationLevel::Warning,
+        Severity::Error => AnnotationLevel::Failure,
+    };
+    annotation(diagnostic.locate(yaml), level, diagnostic.to_string())
+}
+
+/// Problems without a location are attributed to the first line.
+fn annotation(
+    location: Option<Location>,
+    level: AnnotationLevel,
+    message: String,
+) -> CheckAnnotation {
+    let line = location.map_or(1, |location| location.line as u32);
+    CheckAnnotation {
+        path: CONFIGURATION_PATH.to_owned(),
+        start_line: line,
+        end_line: line,
+        level,
+        title: None,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use octocrab::models::webhook_events::WebhookEvent;
+
+    use super::{lint, lint_pull_request, LINT_CHECK_NAME};
+    use crate::routes::event_handler::{
+        configuration::CONFIGURATION_PATH,
+        dispatch::EventDescriptor,
+        remote::{
+            fake::{FakeGitHub, FakePullRequest},
+            AnnotationLevel, CheckConclusion, IssueRef,
+        },
+    };
+
+    fn pull_request(github: &FakeGitHub) -> IssueRef {
+        github.add_repository("owner", "repo");
+        github.add_pull_request(FakePullRequest {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 3,
+            ..Default::default()
+        });
+        IssueRef {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 3,
+        }
+    }
+
+    async fn synchronize(github: &FakeGitHub) {
+        let body = github.pull_request_event("synchronize", "owner", "repo", 3, 42);
+        let event = WebhookEvent::try_from_header_and_body(
+            "pull_request",
+            &serde_json::to_vec(&body).unwrap(),
+        )
+        .unwrap();
+        lint_pull_request(github, &event, &EventDescriptor::from(&event))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn annotates_the_line_a_configuration_fails_to_parse_at() {
+        let check_run = lint(
+            "version: 2\nmergeable:\n  - when: pull_request..opened\n",
+            "abc",
+        );
+
+        assert_eq!(check_run.name, LINT_CHECK_NAME);
+        assert_eq!(check_run.conclusion, CheckConclusion::Failure);
+        assert_eq!(check_run.annotations.len(), 1);
+        assert_eq!(check_run.annotations[0].start_line, 3);
+        assert_eq!(check_run.annotations[0].level, AnnotationLevel::Failure);
+    }
+
+    #[test]
+    fn annotates_diagnostics_with_their_severity() {
+        let check_run = lint(
+            r#"version: 2
+mergeable:
+  - when: pull_request.*
+    validate:
+      - do: stale
+        days: 20
+    pass:
+      - do: merge
+        merge_method: fast-forward
+"#,
+            "abc",
+        );
+
+        assert_eq!(check_run.conclusion, CheckConclusion::Failure);
+        assert_eq!(check_run.title, "1 error(s), 1 warning(s)");
+        let annotations: Vec<_> = check_run
+            .annotations
+            .iter()
+            .map(|annotation| (annotation.start_line, annotation.level))
+            .collect();
+        assert_eq!(
+            annotations,
+            [(5, AnnotationLevel::Warning), (9, AnnotationLevel::Failure)]
+        );
+
+        let valid = lint("version: 2\nmergeable:\n  - when: pull_request.*\n", "abc");
+        assert_eq!(valid.conclusion, CheckConclusion::Success);
+        assert!(valid.annotations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn lints_pull_requests_changing_the_configuration() {
+        let github = FakeGitHub::default();
+        let issue = pull_request(&github);
+        github.commit_file(
+            "owner",
+            "repo",
+            CONFIGURATION_PATH,
+            Some("version: 2\nmergeable: []\n"),
+        );
+
+        synchronize(&github).await;
+        assert!(github.state().check_runs.is_empty());
+
+        let head_sha = github.commit_pull_request_file(
+            &issue,
+            CONFIGURATION_PATH,
+            "version: 3\nmergeable: []\n",
+        );
+        synchronize(&github).await;
+
+        let check_runs = github.state().check_runs.clone();
+        assert_eq!(check_runs.len(), 1);
+        assert_eq!(check_runs[0].1.head_sha, head_sha);
+        assert_eq!(check_runs[0].1.conclusion, CheckConclusion::Failure);
+        assert_eq!(check_runs[0].1.annotations[0].start_line, 1);
+    }
+}
@@ -1,10 +1,1227 @@
-use octocrab::Octocrab;
-pub trait GitHubActionalbe {
-    fn post_message(&self);
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hyper::StatusCode;
+use octocrab::{
+    models::{
+        issues::{Comment, Issue},
+        pulls::{PullRequest, Review, ReviewState},
+        repos::{DiffEntry, DiffEntryStatus, RepoCommit},
+        Author, CheckRunId, CommentId, IssueState, Repository,
+    },
+    params::{
+        checks::{
+            CheckRunConclusion, CheckRunOutput, CheckRunOutputAnnotation,
+            CheckRunOutputAnnotationLevel, CheckRunStatus,
+        },
+        pulls,
+    },
+    Octocrab, Page,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+#[cfg(test)]
+pub(crate) mod fake;
+#[cfg(test)]
+pub(crate) mod mock_server;
+
+/// Coordinates of an issue or pull request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckConclusion {
+    Success,
+    Failure,
+    Neutral,
+    Cancelled,
+    TimedOut,
+    ActionRequired,
+}
+
+/// A completed check run reporting the outcome of a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckRun {
+    pub name: String,
+    pub head_sha: String,
+    pub conclusion: CheckConclusion,
+    pub title: String,
+    pub summary: String,
+    pub text: Option<String>,
+    pub annotations: Vec<CheckAnnotation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Notice,
+    Warning,
+    Failure,
+}
+
+/// A message attached to lines of a file in the output of a check run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub level: AnnotationLevel,
+    pub title: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueComment {
+    pub id: u64,
+    pub body: String,
+    pub author: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Removed,
+    Renamed,
+    Copied,
+    Changed,
+    Unchanged,
+}
+
+/// A file changed by a pull request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub filename: String,
+    pub previous_filename: Option<String>,
+    pub status: FileStatus,
+    pub additions: u64,
+    pub deletions: u64,
+    pub patch: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    pub sha: String,
+    pub message: String,
+    pub author: Option<String>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub parents: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullRequestReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestReview {
+    pub id: u64,
+    pub reviewer: Option<String>,
+    pub state: PullRequestReviewState,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+/// A file of a git tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry {
+    pub path: String,
+    /// The size of the blob in bytes.
+    pub size: u64,
+}
+
+/// An issue or pull request's item in a GitHub project (v2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectItem {
+    /// The title of the project.
+    pub title: String,
+    /// The value of the item's `Status` field, which boards use as columns.
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum RemoteError {
+    #[error("GitHub resource not found: {0}")]
+    NotFound(String),
+    #[error("GitHub denied access: {0}")]
+    Forbidden(String),
+    #[error("GitHub rate limit exceeded: {0}")]
+    RateLimited(String),
+    #[error("GitHub rejected the request due to the resource's state: {0}")]
+    Conflict(String),
+    #[error("GitHub rejected the request as invalid: {0}")]
+    Unprocessable(String),
+    #[error("GitHub request failed: {0}")]
+    Octocrab(octocrab::Error),
+}
+
+impl From<octocrab::Error> for RemoteError {
+    fn from(error: octocrab::Error) -> Self {
+        let octocrab::Error::GitHub { source, .. } = &error else {
+            return Self::Octocrab(error);
+        };
+        let message = source.message.clone();
+        match source.status_code {
+            StatusCode::NOT_FOUND => Self::NotFound(message),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(message),
+            StatusCode::FORBIDDEN if message.to_lowercase().contains("rate limit") => {
+                Self::RateLimited(message)
+            }
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Forbidden(message),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::CONFLICT => Self::Conflict(message),
+            StatusCode::UNPROCESSABLE_ENTITY => Self::Unprocessable(message),
+            _ => Self::Octocrab(error),
+        }
+    }
+}
+
+/// An error of a GraphQL response, GitHub answers those with `200 OK`.
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    message: String,
+}
+
+impl From<GraphQlError> for RemoteError {
+    fn from(error: GraphQlError) -> Self {
+        let message = error.message;
+        match error.kind.as_deref() {
+            Some("NOT_FOUND") => Self::NotFound(message),
+            Some("FORBIDDEN" | "INSUFFICIENT_SCOPES") => Self::Forbidden(message),
+            Some("RATE_LIMITED") => Self::RateLimited(message),
+            _ => Self::Unprocessable(message),
+        }
+    }
+}
+
+/// The project field holding the column of an item on boards.
+const STATUS_FIELD: &str = "Status";
+
+/// Project items of an issue or pull request, archived items aren't on any board.
+const PROJECT_ITEMS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $status: String!) {
+  repository(owner: $owner, name: $repo) {
+    issueOrPullRequest(number: $number) {
+      ... on Issue { projectItems(first: 100, includeArchived: false) { ...items } }
+      ... on PullRequest { projectItems(first: 100, includeArchived: false) { ...items } }
+    }
+  }
+}
+
+fragment items on ProjectV2ItemConnection {
+  nodes {
+    project { title }
+    fieldValueByName(name: $status) {
+      ... on ProjectV2ItemFieldSingleSelectValue { name }
+    }
+  }
+}
+"#;
+
+/// The GitHub operations rules need, implemented for an installation client.
+#[async_trait]
+pub trait GitHubOperations: Send + Sync {
+    async fn add_assignees(
+        &self,
+        issue: &IssueRef,
+        assignees: &[String],
+    ) -> Result<(), RemoteError>;
+
+    async fn list_comments(&self, issue: &IssueRef) -> Result<Vec<IssueComment>, RemoteError>;
+
+    async fn create_comment(
+        &self,
+        issue: &IssueRef,
+        body: &str,
+    ) -> Result<IssueComment, RemoteError>;
+
+    async fn update_comment(
+        &self,
+        issue: &IssueRef,
+        comment_id: u64,
+        body: &str,
+    ) -> Result<(), RemoteError>;
+
+    async fn delete_comment(&self, issue: &IssueRef, comment_id: u64) -> Result<(), RemoteError>;
+
+    async fn add_labels(&self, issue: &IssueRef, labels: &[String]) -> Result<(), RemoteError>;
+
+    async fn remove_label(&self, issue: &IssueRef, label: &str) -> Result<(), RemoteError>;
+
+    async fn replace_labels(&self, issue: &IssueRef, labels: &[String]) -> Result<(), RemoteError>;
+
+    async fn request_reviewers(
+        &self,
+        issue: &IssueRef,
+        reviewers: &[String],
+        teams: &[String],
+    ) -> Result<(), RemoteError>;
+
+    /// Returns the id of the created check run.
+    async fn create_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run: &CheckRun,
+    ) -> Result<u64, RemoteError>;
+
+    async fn update_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run_id: u64,
+        check_run: &CheckRun,
+    ) -> Result<(), RemoteError>;
+
+    /// The id of the latest check run with the name on the commit.
+    async fn find_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+        name: &str,
+    ) -> Result<Option<u64>, RemoteError>;
+
+    async fn merge(
+        &self,
+        issue: &IssueRef,
+        method: MergeMethod,
+        commit_title: Option<&str>,
+        commit_message: Option<&str>,
+    ) -> Result<(), RemoteError>;
+
+    async fn close(&self, issue: &IssueRef) -> Result<(), RemoteError>;
+
+    async fn list_files(&self, pull_request: &IssueRef) -> Result<Vec<ChangedFile>, RemoteError>;
+
+    async fn list_commits(&self, pull_request: &IssueRef) -> Result<Vec<Commit>, RemoteError>;
+
+    async fn list_reviews(
+        &self,
+        pull_request: &IssueRef,
+    ) -> Result<Vec<PullRequestReview>, RemoteError>;
+
+    async fn get_pull_request(&self, pull_request: &IssueRef) -> Result<PullRequest, RemoteError>;
+
+    /// Numbers of the open pull requests whose head is the commit, e.g. the ones behind a
+    /// check suite or a commit status.
+    async fn pull_requests_for_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<u64>, RemoteError>;
+
+    /// Issues and pull requests matching a search query, GitHub caps the results at 1000.
+    async fn search_issues(&self, query: &str) -> Result<Vec<Issue>, RemoteError>;
+
+    /// The repositories the installation has access to.
+    async fn installation_repositories(&self) -> Result<Vec<Repository>, RemoteError>;
+
+    /// The items of an issue or pull request in projects, read through the GraphQL API as the
+    /// REST API doesn't cover projects (v2). Only the first 100 are read.
+    async fn project_items(&self, issue: &IssueRef) -> Result<Vec<ProjectItem>, RemoteError>;
+
+    /// Logins of the members of an organization team, including those of child teams.
+    async fn team_members(&self, org: &str, team_slug: &str) -> Result<Vec<String>, RemoteError>;
+
+    /// Resolves a branch, tag or commit to the sha of its commit.
+    async fn commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        reference: &str,
+    ) -> Result<String, RemoteError>;
+
+    /// The files of the repository at the commit, without submodules. GitHub truncates the
+    /// listing of very large trees.
+    async fn tree(&self, owner: &str, repo: &str, sha: &str)
+        -> Result<Vec<TreeEntry>, RemoteError>;
+
+    /// Returns `None` when the file doesn't exist at the reference.
+    async fn file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        reference: &str,
+    ) -> Result<Option<String>, RemoteError>;
 }
 
-impl GitHubActionalbe for Octocrab {
-    fn post_message(&self) {
-        todo!()
+#[async_trait]
+impl GitHubOperations for Octocrab {
+    async fn add_assignees(
+        &self,
+        issue: &IssueRef,
+        assignees: &[String],
+    ) -> Result<(), RemoteError> {
+        let assignees: Vec<_> = assignees.iter().map(String::as_str).collect();
+        self.issues(&issue.owner, &issue.repo)
+            .add_assignees(issue.number, &assignees)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_comments(&self, issue: &IssueRef) -> Result<Vec<IssueComment>, RemoteError> {
+        let page = self
+            .issues(&issue.owner, &issue.repo)
+            .list_comments(issue.number)
+            .per_page(100)
+            .send()
+            .await?;
+        let comments = self.all_pages::<Comment>(page).await?;
+        Ok(comments.into_iter().map(IssueComment::from).collect())
+    }
+
+    async fn create_comment(
+        &self,
+        issue: &IssueRef,
+        body: &str,
+    ) -> Result<IssueComment, RemoteError> {
+        let comment = self
+            .issues(&issue.owner, &issue.repo)
+            .create_comment(issue.number, body)
+            .await?;
+        Ok(comment.into())
+    }
+
+    async fn update_comment(
+        &self,
+        issue: &IssueRef,
+        comment_id: u64,
+        body: &str,
+    ) -> Result<(), RemoteError> {
+        // octocrab's `update_comment` posts, GitHub expects a patch
+        let route = format!(
+            "/repos/{}/{}/issues/comments/{comment_id}",
+            issue.owner, issue.repo
+        );
+        let body = serde_json::json!({ "body": body });
+        self.patch::<Comment, _, _>(route, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn delete_comment(&self, issue: &IssueRef, comment_id: u64) -> Result<(), RemoteError> {
+        self.issues(&issue.owner, &issue.repo)
+            .delete_comment(CommentId(comment_id))
+            .await?;
+        Ok(())
+    }
+
+    async fn add_labels(&self, issue: &IssueRef, labels: &[String]) -> Result<(), RemoteError> {
+        self.issues(&issue.owner, &issue.repo)
+            .add_labels(issue.number, labels)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_label(&self, issue: &IssueRef, label: &str) -> Result<(), RemoteError> {
+        self.issues(&issue.owner, &issue.repo)
+            .remove_label(issue.number, label)
+            .await?;
+        Ok(())
+    }
+
+    async fn replace_labels(&self, issue: &IssueRef, labels: &[String]) -> Result<(), RemoteError> {
+        self.issues(&issue.owner, &issue.repo)
+            .replace_all_labels(issue.number, labels)
+            .await?;
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        issue: &IssueRef,
+        reviewers: &[String],
+        teams: &[String],
+    ) -> Result<(), RemoteError> {
+        // the response is a pull request, octocrab's `request_reviews` expects a review
+        let route = format!(
+            "/repos/{}/{}/pulls/{}/requested_reviewers",
+            issue.owner, issue.repo, issue.number
+        );
+        let body = serde_json::json!({ "reviewers": reviewers, "team_reviewers": teams });
+        self.post::<_, serde_json::Value>(route, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    async fn create_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run: &CheckRun,
+    ) -> Result<u64, RemoteError> {
+        let created = self
+            .checks(owner, repo)
+            .create_check_run(&check_run.name, &check_run.head_sha)
+            .status(CheckRunStatus::Completed)
+            .conclusion(check_run.conclusion.into())
+            .output(check_run.output())
+            .send()
+            .await?;
+        Ok(created.id.into_inner())
+    }
+
+    async fn update_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run_id: u64,
+        check_run: &CheckRun,
+    ) -> Result<(), RemoteError> {
+        self.checks(owner, repo)
+            .update_check_run(CheckRunId(check_run_id))
+            .name(&check_run.name)
+            .status(CheckRunStatus::Completed)
+            .conclusion(check_run.conclusion.into())
+            .output(check_run.output())
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    async fn find_check_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_sha: &str,
+        name: &str,
+    ) -> Result<Option<u64>, RemoteError> {
+        // octocrab's `list_check_runs_for_git_ref` can't filter by name
+        #[derive(Deserialize)]
+        struct CheckRuns {
+            check_runs: Vec<Run>,
+        }
+
+        #[derive(Deserialize)]
+        struct Run {
+            id: u64,
+        }
+
+        let route = format!("/repos/{owner}/{repo}/commits/{head_sha}/check-runs");
+        let query = serde_json::json!({ "check_name": name, "filter": "latest" });
+        let runs: CheckRuns = self.get(route, Some(&query)).await?;
+        Ok(runs.check_runs.first().map(|run| run.id))
+    }
+
+    async fn merge(
+        &self,
+        issue: &IssueRef,
+        method: MergeMethod,
+        commit_title: Option<&str>,
+        commit_message: Option<&str>,
+    ) -> Result<(), RemoteError> {
+        let pulls = self.pulls(&issue.owner, &issue.repo);
+        let mut merge = pulls.merge(issue.number).method(match method {
+            MergeMethod::Merge => pulls::MergeMethod::Merge,
+            MergeMethod::Squash => pulls::MergeMethod::Squash,
+            MergeMethod::Rebase => pulls::MergeMethod::Rebase,
+        });
+        if let Some(title) = commit_title {
+            merge = merge.title(title);
+        }
+        if let Some(message) = commit_message {
+            merge = merge.message(message);
+        }
+        merge.send().await?;
+        Ok(())
+    }
+
+    async fn close(&self, issue: &IssueRef) -> Result<(), RemoteError> {
+        self.issues(&issue.owner, &issue.repo)
+            .update(issue.number)
+            .state(IssueState::Closed)
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    async fn list_files(&self, pull_request: &IssueRef) -> Result<Vec<ChangedFile>, RemoteError> {
+        let route = format!(
+            "/repos/{}/{}/pulls/{}/files?per_page=100",
+            pull_request.owner, pull_request.repo, pull_request.number
+        );
+        let page: Page<DiffEntry> = self.get(route, None::<&()>).await?;
+        let files = self.all_pages(page).await?;
+        Ok(files.into_iter().map(ChangedFile::from).collect())
+    }
+
+    async fn list_commits(&self, pull_request: &IssueRef) -> Result<Vec<Commit>, RemoteError> {
+        let route = format!(
+            "/repos/{}/{}/pulls/{}/commits?per_page=100",
+            pull_request.owner, pull_request.repo, pull_request.number
+        );
+        let page: Page<RepoCommit> = self.get(route, None::<&()>).await?;
+        let commits = self.all_pages(page).await?;
+        Ok(commits.into_iter().map(Commit::from).collect())
+    }
+
+    async fn list_reviews(
+        &self,
+        pull_request: &IssueRef,
+    ) -> Result<Vec<PullRequestReview>, RemoteError> {
+        let page = self
+            .pulls(&pull_request.owner, &pull_request.repo)
+            .list_reviews(pull_request.number)
+            .per_page(100)
+            .send()
+            .await?;
+        let reviews = self.all_pages::<Review>(page).await?;
+        Ok(reviews
+            .into_iter()
+            .filter_map(PullRequestReview::from_review)
+            .collect())
+    }
+
+    async fn get_pull_request(&self, pull_request: &IssueRef) -> Result<PullRequest, RemoteError> {
+        Ok(self
+            .pulls(&pull_request.owner, &pull_request.repo)
+            .get(pull_request.number)
+            .await?)
+    }
+
+    async fn pull_requests_for_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<u64>, RemoteError> {
+        // lists the pull requests containing the commit, not only those it heads
+        let route = format!("/repos/{owner}/{repo}/commits/{sha}/pulls?per_page=100");
+        let page: Page<PullRequest> = self.get(route, None::<&()>).await?;
+        let pull_requests = self.all_pages(page).await?;
+        Ok(pull_requests
+            .into_iter()
+            .filter(|pull_request| {
+                pull_request.state == Some(IssueState::Open) && pull_request.head.sha == sha
+            })
+            .map(|pull_request| pull_request.number)
+            .collect())
+    }
+
+    async fn search_issues(&self, query: &str) -> Result<Vec<Issue>, RemoteError> {
+        let page = self
+            .search()
+            .issues_and_pull_requests(query)
+            .per_page(100)
+            .send()
+            .await?;
+        Ok(self.all_pages(page).await?)
+    }
+
+    async fn installation_repositories(&self) -> Result<Vec<Repository>, RemoteError> {
+        // the repositories are wrapped in an object with their total count
+        let page: Page<Repository> = self
+            .get("/installation/repositories?per_page=100", None::<&()>)
+            .await?;
+        Ok(self.all_pages(page).await?)
+    }
+
+    async fn project_items(&self, issue: &IssueRef) -> Result<Vec<ProjectItem>, RemoteError> {
+        #[derive(Deserialize)]
+        struct Response {
+            data: Option<Data>,
+            #[serde(default)]
+            errors: Vec<GraphQlError>,
+        }
+
+        #[derive(Deserialize)]
+        struct Data {
+            repository: Option<RepositoryData>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RepositoryData {
+            issue_or_pull_request: Option<IssueData>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct IssueData {
+            project_items: Nodes<Item>,
+        }
+
+        #[derive(Deserialize)]
+        struct Nodes<T> {
+            nodes: Vec<Option<T>>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Item {
+            project: Project,
+            field_value_by_name: Option<FieldValue>,
+        }
+
+        #[derive(Deserialize)]
+        struct Project {
+            title: String,
+        }
+
+        /// Empty unless the field is a single select.
+        #[derive(Deserialize)]
+        struct FieldValue {
+            name: Option<String>,
+        }
+
+        let response: Response = self
+            .graphql(&json!({
+                "query": PROJECT_ITEMS_QUERY,
+                "variables": {
+                    "owner": issue.owner,
+                    "repo": issue.repo,
+                    "number": issue.number,
+                    "status": STATUS_FIELD,
+                },
+            }))
+            .await?;
+        if let Some(error) = response.errors.into_iter().next() {
+            return Err(error.into());
+        }
+        let item = response
+            .data
+            .and_then(|data| data.repository)
+            .and_then(|repository| repository.issue_or_pull_request)
+            .ok_or_else(|| {
+                RemoteError::NotFound(format!("{}/{}#{}", issue.owner, issue.repo, issue.number))
+            })?;
+        Ok(item
+            .project_items
+            .nodes
+            .into_iter()
+            .flatten()
+            .map(|item| ProjectItem {
+                title: item.project.title,
+                status: item.field_value_by_name.and_then(|value| value.name),
+            })
+            .collect())
+    }
+
+    async fn team_members(&self, org: &str, team_slug: &str) -> Result<Vec<String>, RemoteError> {
+        let route = format!("/orgs/{org}/teams/{team_slug}/members?per_page=100");
+        let page: Page<Author> = self.get(route, None::<&()>).await?;
+        let members = self.all_pages(page).await?;
+        Ok(members.into_iter().map(|member| member.login).collect())
+    }
+
+    async fn commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        reference: &str,
+    ) -> Result<String, RemoteError> {
+        let route = format!("/repos/{owner}/{repo}/commits/{reference}");
+        let commit: RepoCommit = self.get(route, None::<&()>).await?;
+        Ok(commit.sha)
+    }
+
+    async fn tree(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<TreeEntry>, RemoteError> {
+        #[derive(Deserialize)]
+        struct Tree {
+            tree: Vec<Entry>,
+            truncated: bool,
+        }
+
+        #[derive(Deserialize)]
+        struct Entry {
+            path: String,
+            r#type: String,
+            size: Option<u64>,
+        }
+
+        let route = format!("/repos/{owner}/{repo}/git/trees/{sha}?recursive=1");
+        let tree: Tree = self.get(route, None::<&()>).await?;
+        if tree.truncated {
+            tracing::warn!(owner, repo, sha, "the tree listing is truncated");
+        }
+        Ok(tree
+            .tree
+            .into_iter()
+            .filter(|entry| entry.r#type == "blob")
+            .map(|entry| TreeEntry {
+                path: entry.path,
+                size: entry.size.unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    async fn file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        reference: &str,
+    ) -> Result<Option<String>, RemoteError> {
+        let contents = self
+            .repos(owner, repo)
+            .get_content()
+            .path(path)
+            .r#ref(reference)
+            .send()
+            .await
+            .map_err(RemoteError::from);
+        match contents {
+            // a directory lists its entries, a file is the only item
+            Ok(contents) => Ok(contents
+                .items
+                .into_iter()
+                .find(|item| item.r#type == "file" && item.path == path)
+                .and_then(|file| file.decoded_content())),
+            Err(RemoteError::NotFound(_)) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl From<CheckConclusion> for CheckRunConclusion {
+    fn from(conclusion: CheckConclusion) -> Self {
+        match conclusion {
+            CheckConclusion::Success => CheckRunConclusion::Success,
+            CheckConclusion::Failure => CheckRunConclusion::Failure,
+            CheckConclusion::Neutral => CheckRunConclusion::Neutral,
+            CheckConclusion::Cancelled => CheckRunConclusion::Cancelled,
+            CheckConclusion::TimedOut => CheckRunConclusion::TimedOut,
+            CheckConclusion::ActionRequired => CheckRunConclusion::ActionRequired,
+        }
+    }
+}
+
+impl CheckRun {
+    fn output(&self) -> CheckRunOutput {
+        CheckRunOutput {
+            title: self.title.clone(),
+            summary: self.summary.clone(),
+            text: self.text.clone(),
+            annotations: self
+                .annotations
+                .iter()
+                .map(|annotation| CheckRunOutputAnnotation {
+                    path: annotation.path.clone(),
+                    start_line: annotation.start_line,
+                    end_line: annotation.end_line,
+                    start_column: None,
+                    end_column: None,
+                    annotation_level: annotation.level.into(),
+                    message: annotation.message.clone(),
+                    title: annotation.title.clone(),
+                    raw_details: None,
+                })
+                .collect(),
+            images: vec![],
+        }
+    }
+}
+
+impl From<AnnotationLevel> for CheckRunOutputAnnotationLevel {
+    fn from(level: AnnotationLevel) -> Self {
+        match level {
+            AnnotationLevel::Notice => CheckRunOutputAnnotationLevel::Notice,
+            AnnotationLevel::Warning => CheckRunOutputAnnotationLevel::Warning,
+            AnnotationLevel::Failure => CheckRunOutputAnnotationLevel::Failure,
+        }
+    }
+}
+
+impl From<DiffEntry> for ChangedFile {
+    fn from(entry: DiffEntry) -> Self {
+        let status = match entry.status {
+            DiffEntryStatus::Added => FileStatus::Added,
+            DiffEntryStatus::Removed => FileStatus::Removed,
+            DiffEntryStatus::Modified => FileStatus::Modified,
+            DiffEntryStatus::Renamed => FileStatus::Renamed,
+            DiffEntryStatus::Copied => FileStatus::Copied,
+            DiffEntryStatus::Unchanged => FileStatus::Unchanged,
+            _ => FileStatus::Changed,
+        };
+        Self {
+            filename: entry.filename,
+            previous_filename: entry.previous_filename,
+            status,
+            additions: entry.additions,
+            deletions: entry.deletions,
+            patch: entry.patch,
+        }
+    }
+}
+
+impl From<Comment> for IssueComment {
+    fn from(comment: Comment) -> Self {
+        Self {
+            id: comment.id.into_inner(),
+            body: comment.body.unwrap_or_default(),
+            author: comment.user.login,
+        }
+    }
+}
+
+impl From<RepoCommit> for Commit {
+    fn from(commit: RepoCommit) -> Self {
+        Self {
+            sha: commit.sha,
+            message: commit.commit.message,
+            author: commit.author.map(|author| author.login),
+            committed_at: commit.commit.committer.and_then(|committer| committer.date),
+            parents: commit.parents.len(),
+        }
+    }
+}
+
+impl PullRequestReview {
+    /// Reviews without a state can't be attributed and are skipped.
+    fn from_review(review: Review) -> Option<Self> {
+        let state = match review.state? {
+            ReviewState::Approved => PullRequestReviewState::Approved,
+            ReviewState::ChangesRequested => PullRequestReviewState::ChangesRequested,
+            ReviewState::Commented => PullRequestReviewState::Commented,
+            ReviewState::Dismissed => PullRequestReviewState::Dismissed,
+            ReviewState::Pending | ReviewState::Open => PullRequestReviewState::Pending,
+            _ => return None,
+        };
+        Some(Self {
+            id: review.id.into_inner(),
+            reviewer: review.user.map(|user| user.login),
+            state,
+            submitted_at: review.submitted_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyper::StatusCode;
+    use octocrab::{models::InstallationId, Octocrab};
+
+    use super::{
+        mock_server::{app_key, MockGitHubServer},
+        AnnotationLevel, ChangedFile, CheckAnnotation, CheckConclusion, CheckRun, Commit,
+        FileStatus, GitHubOperations, IssueRef, MergeMethod, ProjectItem, PullRequestReview,
+        PullRequestReviewState, RemoteError, TreeEntry,
+    };
+    use crate::routes::event_handler::GitHubAuthenticator;
+
+    fn installation_client(server: &MockGitHubServer) -> Octocrab {
+        let app = Octocrab::authenticate_app(server.uri(), 1.into(), app_key()).unwrap();
+        app.installation(InstallationId(42))
+    }
+
+    fn pull_request() -> IssueRef {
+        IssueRef {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 7,
+        }
+    }
+
+    fn file(filename: &str) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_owned(),
+            previous_filename: None,
+            status: FileStatus::Modified,
+            additions: 1,
+            deletions: 2,
+            patch: Some("@@ -1,2 +1 @@".to_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_all_pages_with_an_installation_token() {
+        let server = MockGitHubServer::start().await;
+        {
+            let mut state = server.state();
+            state.max_page_size = Some(2);
+            state
+                .files
+                .insert(7, vec![file("a.rs"), file("b.rs"), file("c.rs")]);
+            state.commits.insert(
+                7,
+                vec![Commit {
+                    sha: "abc123".to_owned(),
+                    message: "Add feature".to_owned(),
+                    author: Some("octocat".to_owned()),
+                    committed_at: Some("2024-05-01T10:00:00Z".parse().unwrap()),
+                    parents: 1,
+                }],
+            );
+            state.reviews.insert(
+                7,
+                vec![PullRequestReview {
+                    id: 3,
+                    reviewer: Some("hubot".to_owned()),
+                    state: PullRequestReviewState::Approved,
+                    submitted_at: Some("2024-05-02T10:00:00Z".parse().unwrap()),
+                }],
+            );
+            state.teams.insert(
+                "octo/core".to_owned(),
+                vec!["alice".to_owned(), "bob".to_owned(), "carol".to_owned()],
+            );
+        }
+        let client = installation_client(&server);
+
+        let files = client.list_files(&pull_request()).await.unwrap();
+        let commits = client.list_commits(&pull_request()).await.unwrap();
+        let reviews = client.list_reviews(&pull_request()).await.unwrap();
+        let members = client.team_members("octo", "core").await.unwrap();
+        let unknown = client.team_members("octo", "unknown").await;
+
+        assert_eq!(files, [file("a.rs"), file("b.rs"), file("c.rs")]);
+        assert_eq!(commits[0].sha, "abc123");
+        assert_eq!(commits[0].author.as_deref(), Some("octocat"));
+        assert_eq!(reviews[0].state, PullRequestReviewState::Approved);
+        assert_eq!(members, ["alice", "bob", "carol"]);
+        assert!(matches!(unknown, Err(RemoteError::NotFound(_))));
+        let state = server.state();
+        assert_eq!(state.installation_tokens, [42]);
+        let file_requests: Vec<_> = state
+            .requests
+            .iter()
+            .filter(|request| request.path.starts_with("/repos/owner/repo/pulls/7/files"))
+            .collect();
+        assert_eq!(file_requests.len(), 2);
+        assert_eq!(
+            file_requests[0].authorization.as_deref(),
+            Some("Bearer installation-token-42")
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_mutations() {
+        let server = MockGitHubServer::start().await;
+        server.state().labels.insert(7, vec!["bug".to_owned()]);
+        let client = installation_client(&server);
+        let issue = pull_request();
+        let check_run = CheckRun {
+            name: "rule".to_owned(),
+            head_sha: "abc123".to_owned(),
+            conclusion: CheckConclusion::Neutral,
+            title: "pending".to_owned(),
+            summary: "evaluating".to_owned(),
+            text: None,
+            annotations: vec![CheckAnnotation {
+                path: "README.md".to_owned(),
+                start_line: 3,
+                end_line: 3,
+                level: AnnotationLevel::Warning,
+                title: None,
+                message: "typo".to_owned(),
+            }],
+        };
+
+        let comment = client.create_comment(&issue, "first").await.unwrap();
+        assert_eq!(comment.author, "source-warden[bot]");
+        client
+            .update_comment(&issue, comment.id, "edited")
+            .await
+            .unwrap();
+        client.create_comment(&issue, "second").await.unwrap();
+        client.delete_comment(&issue, comment.id).await.unwrap();
+        client
+            .add_labels(&issue, &["wip".to_owned()])
+            .await
+            .unwrap();
+        client.remove_label(&issue, "bug").await.unwrap();
+        client
+            .add_assignees(&issue, &["octocat".to_owned()])
+            .await
+            .unwrap();
+        client
+            .request_reviewers(&issue, &["hubot".to_owned()], &["core".to_owned()])
+            .await
+            .unwrap();
+        let check_run_id = client
+            .create_check_run("owner", "repo", &check_run)
+            .await
+            .unwrap();
+        let completed = CheckRun {
+            conclusion: CheckConclusion::Success,
+            title: "passed".to_owned(),
+            ..check_run
+        };
+        client
+            .update_check_run("owner", "repo", check_run_id, &completed)
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .find_check_run("owner", "repo", "abc123", "rule")
+                .await
+                .unwrap(),
+            Some(check_run_id)
+        );
+        assert_eq!(
+            client
+                .find_check_run("owner", "repo", "abc123", "other")
+                .await
+                .unwrap(),
+            None
+        );
+        client
+            .merge(&issue, MergeMethod::Squash, Some("Title"), None)
+            .await
+            .unwrap();
+        client.close(&issue).await.unwrap();
+
+        let comments = client.list_comments(&issue).await.unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body, "second");
+        let state = server.state();
+        assert_eq!(state.labels[&7], ["wip"]);
+        assert_eq!(state.assignees[&7], ["octocat"]);
+        assert_eq!(state.requested_reviewers[&7]["team_reviewers"][0], "core");
+        assert_eq!(state.check_runs.len(), 1);
+        assert_eq!(state.check_runs[0].1["conclusion"], "success");
+        assert_eq!(state.check_runs[0].1["output"]["title"], "passed");
+        assert_eq!(
+            state.check_runs[0].1["output"]["annotations"][0]["annotation_level"],
+            "warning"
+        );
+        assert_eq!(state.merged[&7]["merge_method"], "squash");
+        assert_eq!(state.merged[&7]["commit_title"], "Title");
+        assert!(state.closed.contains(&7));
+    }
+
+    #[tokio::test]
+    async fn reads_repository_files() {
+        let server = MockGitHubServer::start().await;
+        {
+            let mut state = server.state();
+            state
+                .references
+                .insert("main".to_owned(), "abc123".to_owned());
+            state.contents.insert(
+                ".github/mergeable.yml".to_owned(),
+                "version: 2\nmergeable: []\n".repeat(10),
+            );
+        }
+        let client = installation_client(&server);
+
+        let sha = client.commit_sha("owner", "repo", "main").await.unwrap();
+        let content = client
+            .file_content("owner", "repo", ".github/mergeable.yml", &sha)
+            .await
+            .unwrap();
+        let missing = client
+            .file_content("owner", "repo", ".github/missing.yml", &sha)
+            .await
+            .unwrap();
+        let unknown = client.commit_sha("owner", "repo", "unknown").await;
+        let tree = client.tree("owner", "repo", &sha).await.unwrap();
+
+        assert_eq!(sha, "abc123");
+        assert_eq!(
+            tree,
+            [TreeEntry {
+                path: ".github/mergeable.yml".to_owned(),
+                size: 250,
+            }]
+        );
+        assert_eq!(content, Some("version: 2\nmergeable: []\n".repeat(10)));
+        assert_eq!(missing, None);
+        assert!(matches!(unknown, Err(RemoteError::NotFound(_))));
+        assert!(server
+            .state()
+            .requests
+            .iter()
+            .any(|request| request.path
+                == "/repos/owner/repo/contents/.github/mergeable.yml?ref=abc123"));
+    }
+
+    #[tokio::test]
+    async fn reads_projects_through_graphql() {
+        let server = MockGitHubServer::start().await;
+        let items = vec![
+            ProjectItem {
+                title: "Sprint 12".to_owned(),
+                status: Some("In review".to_owned()),
+            },
+            ProjectItem {
+                title: "Roadmap".to_owned(),
+                status: None,
+            },
+        ];
+        server.state().projects.insert(7, items.clone());
+        let client = installation_client(&server);
+
+        assert_eq!(client.project_items(&pull_request()).await.unwrap(), items);
+        let missing = IssueRef {
+            number: 8,
+            ..pull_request()
+        };
+        assert!(matches!(
+            client.project_items(&missing).await,
+            Err(RemoteError::NotFound(message)) if message.contains("number of 8")
+        ));
+        let requests = server.state().requests.clone();
+        assert_eq!(requests.last().unwrap().path, "/graphql");
+    }
+
+    #[tokio::test]
+    async fn classifies_github_errors() {
+        let server = MockGitHubServer::start().await;
+        server.fail(
+            "/repos/owner/repo/pulls/7/merge",
+            StatusCode::METHOD_NOT_ALLOWED,
+            "Pull Request is not mergeable",
+        );
+        server.fail(
+            "/repos/owner/repo/issues/7/labels",
+            StatusCode::FORBIDDEN,
+            "API rate limit exceeded",
+        );
+        server.fail(
+            "/repos/owner/repo/issues/7",
+            StatusCode::NOT_FOUND,
+            "Not Found",
+        );
+        server.fail(
+            "/repos/owner/repo/check-runs",
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Validation Failed",
+        );
+        let client = installation_client(&server);
+        let issue = pull_request();
+        let check_run = CheckRun {
+            name: "rule".to_owned(),
+            head_sha: "unknown".to_owned(),
+            conclusion: CheckConclusion::Success,
+            title: String::new(),
+            summary: String::new(),
+            text: None,
+            annotations: vec![],
+        };
+
+        let merge = client.merge(&issue, MergeMethod::Merge, None, None).await;
+        let labels = client.add_labels(&issue, &["wip".to_owned()]).await;
+        let close = client.close(&issue).await;
+        let check_run = client.create_check_run("owner", "repo", &check_run).await;
+
+        assert!(
+            matches!(merge, Err(RemoteError::Conflict(message)) if message == "Pull Request is not mergeable")
+        );
+        assert!(matches!(labels, Err(RemoteError::RateLimited(_))));
+        assert!(matches!(close, Err(RemoteError::NotFound(_))));
+        assert!(matches!(check_run, Err(RemoteError::Unprocessable(_))));
     }
 }
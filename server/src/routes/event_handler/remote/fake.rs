@@ -0,0 +1,861 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use hyper::Uri;
+use jsonwebtoken::EncodingKey;
+use octocrab::models::{issues::Issue, pulls::PullRequest, AppId, InstallationId, Repository};
+use serde_json::{json, Value};
+
+use super::{
+    ChangedFile, CheckRun, Commit, FileStatus, GitHubOperations, IssueComment, IssueRef,
+    MergeMethod, ProjectItem, PullRequestReview, RemoteError, TreeEntry,
+};
+use crate::routes::event_handler::{GitHubAuthenticator, InstallationAuthenticator};
+
+/// The login of the app's bot account the fake comments as.
+pub(crate) const BOT: &str = "source-warden[bot]";
+
+/// A mutation performed against the fake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Call {
+    AddAssignees(u64, Vec<String>),
+    CreateComment(u64, String),
+    UpdateComment(u64, String),
+    DeleteComment(u64),
+    AddLabels(u64, Vec<String>),
+    RemoveLabel(u64, String),
+    ReplaceLabels(u64, Vec<String>),
+    RequestReviewers(u64, Vec<String>, Vec<String>),
+    CreateCheckRun(CheckRun),
+    UpdateCheckRun(u64, CheckRun),
+    Merge(u64, MergeMethod),
+    Close(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FakeRepository {
+    pub(crate) id: u64,
+    pub(crate) owner: String,
+    pub(crate) name: String,
+    pub(crate) private: bool,
+    pub(crate) default_branch: String,
+    /// The commit the default branch points to.
+    pub(crate) head_sha: String,
+    /// Contents of the files on the default branch by path.
+    pub(crate) files: HashMap<String, String>,
+    /// Contents of the files by path as of the commits made through the fake.
+    pub(crate) snapshots: HashMap<String, HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FakePullRequest {
+    pub(crate) owner: String,
+    pub(crate) repo: String,
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    pub(crate) body: Option<String>,
+    pub(crate) author: String,
+    pub(crate) labels: Vec<String>,
+    pub(crate) assignees: Vec<String>,
+    pub(crate) requested_reviewers: Vec<String>,
+    pub(crate) requested_teams: Vec<String>,
+    pub(crate) head_ref: String,
+    pub(crate) base_ref: String,
+    pub(crate) head_sha: String,
+    pub(crate) draft: bool,
+    pub(crate) closed: bool,
+    pub(crate) merged: Option<MergeMethod>,
+    pub(crate) comments: Vec<IssueComment>,
+    pub(crate) files: Vec<ChangedFile>,
+    pub(crate) commits: Vec<Commit>,
+    pub(crate) reviews: Vec<PullRequestReview>,
+    pub(crate) projects: Vec<ProjectItem>,
+}
+
+/// The data the fake serves and the calls it recorded.
+///
+/// Mutations of pull requests the fake doesn't know are only recorded.
+#[derive(Debug, Default)]
+pub(crate) struct FakeState {
+    pub(crate) repositories: Vec<FakeRepository>,
+    pub(crate) pull_requests: Vec<FakePullRequest>,
+    pub(crate) check_runs: Vec<(u64, CheckRun)>,
+    /// Logins of the members of organization teams by `org/team_slug`.
+    pub(crate) teams: HashMap<String, Vec<String>>,
+    /// Installations clients were requested for.
+    pub(crate) installations: Vec<u64>,
+    /// Installations the application lists, each has access to all repositories.
+    pub(crate) installed: Vec<u64>,
+    /// Queries searched for, any query finds the open pull requests of its `repo:`.
+    pub(crate) searches: Vec<String>,
+    pub(crate) calls: Vec<Call>,
+    /// Paths of the files read and the reference they were read at.
+    pub(crate) file_reads: Vec<(String, String)>,
+    /// Operations answering with [`RemoteError::NotFound`].
+    pub(crate) failing: HashSet<&'static str>,
+    next_id: u64,
+}
+
+impl FakeState {
+    fn repository(&mut self, owner: &str, name: &str) -> Option<&mut FakeRepository> {
+        self.repositories
+            .iter_mut()
+            .find(|repository| repository.owner == owner && repository.name == name)
+    }
+
+    fn pull_request(&mut self, issue: &IssueRef) -> Option<&mut FakePullRequest> {
+        self.pull_requests.iter_mut().find(|pull_request| {
+            pull_request.owner == issue.owner
+                && pull_request.repo == issue.repo
+                && pull_request.number == issue.number
+        })
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+/// An in-memory GitHub, clones share their state.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FakeGitHub(Arc<Mutex<FakeState>>);
+
+impl FakeGitHub {
+    pub(crate) fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.0.lock().expect("fake state lock poisoned")
+    }
+
+    pub(crate) fn calls(&self) -> Vec<Call> {
+        self.state().calls.clone()
+    }
+
+    /// Returns the id of the new repository.
+    pub(crate) fn add_repository(&self, owner: &str, name: &str) -> u64 {
+        let mut state = self.state();
+        let id = state.next_id();
+        state.repositories.push(FakeRepository {
+            id,
+            owner: owner.to_owned(),
+            name: name.to_owned(),
+            private: false,
+            default_branch: "main".to_owned(),
+            head_sha: format!("{id:040x}"),
+            files: HashMap::new(),
+            snapshots: HashMap::new(),
+        });
+        id
+    }
+
+    /// Commits a file to the default branch, `None` deletes it. Returns the new head sha.
+    pub(crate) fn commit_file(
+        &self,
+        owner: &str,
+        name: &str,
+        path: &str,
+        content: Option<&str>,
+    ) -> String {
+        let mut state = self.state();
+        let sha = format!("{:040x}", state.next_id());
+        let repository = state.repository(owner, name).expect("unknown repository");
+        match content {
+            Some(content) => repository.files.insert(path.to_owned(), content.to_owned()),
+            None => repository.files.remove(path),
+        };
+        repository.head_sha.clone_from(&sha);
+        let snapshot = repository.files.clone();
+        repository.snapshots.insert(sha.clone(), snapshot);
+        sha
+    }
+
+    /// Commits a file to the head branch of a pull request, listing it as changed.
+    pub(crate) fn commit_pull_request_file(
+        &self,
+        issue: &IssueRef,
+        path: &str,
+        content: &str,
+    ) -> String {
+        let mut state = self.state();
+        let sha = format!("{:040x}", state.next_id());
+        let repository = state
+            .repository(&issue.owner, &issue.repo)
+            .expect("unknown repository");
+        let mut snapshot = repository.files.clone();
+        let status = match snapshot.insert(path.to_owned(), content.to_owned()) {
+            Some(_) => FileStatus::Modified,
+            None => FileStatus::Added,
+        };
+        repository.snapshots.insert(sha.clone(), snapshot);
+        let pull_request = state.pull_request(issue).expect("unknown pull request");
+        pull_request.head_sha.clone_from(&sha);
+        pull_request.files.push(ChangedFile {
+            filename: path.to_owned(),
+            previous_filename: None,
+            status,
+            additions: content.lines().count() as u64,
+            deletions: 0,
+            patch: None,
+        });
+        sha
+    }
+
+    pub(crate) fn add_pull_request(&self, pull_request: FakePullRequest) {
+        self.state().pull_requests.push(pull_request);
+    }
+
+    pub(crate) fn pull_request(&self, owner: &str, repo: &str, number: u64) -> FakePullRequest {
+        self.state()
+            .pull_request(&IssueRef {
+                owner: owner.to_owned(),
+                repo: repo.to_owned(),
+                number,
+            })
+            .cloned()
+            .expect("unknown pull request")
+    }
+
+    /// The body of a `pull_request` webhook for a stored pull request.
+    pub(crate) fn pull_request_event(
+        &self,
+        action: &str,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        installation: u64,
+    ) -> Value {
+        let pull_request = self.pull_request(owner, repo, number);
+        json!({
+            "action": action,
+            "number": number,
+            "pull_request": self.pull_request_json(owner, repo, number),
+            "repository": self.repository_json(owner, repo),
+            "sender": user_json(&pull_request.author),
+            "installation": { "id": installation, "node_id": installation.to_string() },
+        })
+    }
+
+    /// A stored pull request as the REST API and webhooks represent it.
+    pub(crate) fn pull_request_json(&self, owner: &str, repo: &str, number: u64) -> Value {
+        let pull_request = self.pull_request(owner, repo, number);
+        json!({
+            "url": format!("https://github.local/repos/{owner}/{repo}/pulls/{number}"),
+            "id": number,
+            "number": number,
+            "state": if pull_request.closed { "closed" } else { "open" },
+            "title": pull_request.title,
+            "body": pull_request.body,
+            "user": user_json(&pull_request.author),
+            "labels": pull_request.labels.iter().enumerate().map(|(id, name)| json!({
+                "id": id,
+                "node_id": name,
+                "url": format!("https://github.local/labels/{name}"),
+                "name": name,
+                "color": "ffffff",
+                "default": false,
+            })).collect::<Vec<_>>(),
+            "assignees": pull_request.assignees.iter().map(|login| user_json(login)).collect::<Vec<_>>(),
+            "requested_reviewers": pull_request.requested_reviewers.iter().map(|login| user_json(login)).collect::<Vec<_>>(),
+            "draft": pull_request.draft,
+            "head": { "ref": pull_request.head_ref, "sha": pull_request.head_sha },
+            "base": { "ref": pull_request.base_ref, "sha": "0000000" },
+        })
+    }
+
+    /// A stored pull request as the search API represents it.
+    pub(crate) fn issue_json(&self, owner: &str, repo: &str, number: u64) -> Value {
+        let pull_request = self.pull_request_json(owner, repo, number);
+        let url = format!("https://github.local/repos/{owner}/{repo}/issues/{number}");
+        let html_url = format!("https://github.local/{owner}/{repo}/pull/{number}");
+        json!({
+            "id": number,
+            "node_id": number.to_string(),
+            "url": url,
+            "repository_url": format!("https://github.local/repos/{owner}/{repo}"),
+            "labels_url": format!("{url}/labels"),
+            "comments_url": format!("{url}/comments"),
+            "events_url": format!("{url}/events"),
+            "html_url": url,
+            "number": number,
+            "state": pull_request["state"],
+            "title": pull_request["title"],
+            "body": pull_request["body"],
+            "user": pull_request["user"],
+            "labels": pull_request["labels"],
+            "assignees": pull_request["assignees"],
+            "author_association": "CONTRIBUTOR",
+            "locked": false,
+            "comments": 0,
+            "created_at": "2024-05-01T10:00:00Z",
+            "updated_at": "2024-05-01T10:00:00Z",
+            "pull_request": {
+                "url": pull_request["url"],
+                "html_url": html_url,
+                "diff_url": format!("{html_url}.diff"),
+                "patch_url": format!("{html_url}.patch"),
+            },
+        })
+    }
+
+    /// The body of a `push` webhook for a push to the default branch changing the files.
+    pub(crate) fn push_event(
+        &self,
+        owner: &str,
+        repo: &str,
+        before: &str,
+        modified: &[&str],
+        installation: u64,
+    ) -> Value {
+        let (branch, after) = {
+            let mut state = self.state();
+            let repository = state.repository(owner, repo).expect("unknown repository");
+            (
+                repository.default_branch.clone(),
+                repository.head_sha.clone(),
+            )
+        };
+        let signature = json!({
+            "name": "octocat",
+            "email": "octocat@github.local",
+            "username": "octocat",
+        });
+        json!({
+            "ref": format!("refs/heads/{branch}"),
+            "before": before,
+            "after": after,
+            "created": false,
+            "deleted": false,
+            "forced": false,
+            "compare": format!("https://github.local/{owner}/{repo}/compare/{before}...{after}"),
+            "pusher": signature,
+            "commits": [{
+                "id": after,
+                "tree_id": after,
+                "distinct": true,
+                "message": "Update files",
+                "timestamp": "2024-05-01T10:00:00Z",
+                "url": format!("https://github.local/{owner}/{repo}/commit/{after}"),
+                "author": signature,
+                "committer": signature,
+                "added": [],
+                "modified": modified,
+                "removed": [],
+            }],
+            "head_commit": null,
+            "repository": self.repository_json(owner, repo),
+            "sender": user_json("octocat"),
+            "installation": { "id": installation, "node_id": installation.to_string() },
+        })
+    }
+
+    pub(crate) fn repository_json(&self, owner: &str, repo: &str) -> Value {
+        let mut state = self.state();
+        let repository = state.repository(owner, repo).expect("unknown repository");
+        json!({
+            "id": repository.id,
+            "name": repository.name,
+            "full_name": format!("{owner}/{repo}"),
+            "url": format!("https://github.local/repos/{owner}/{repo}"),
+            "owner": user_json(owner),
+            "private": repository.private,
+            "default_branch": repository.default_branch,
+        })
+    }
+
+    fn record(&self, operation: &'static str, call: Call) -> Result<(), RemoteError> {
+        let mut state = self.state();
+        if state.failing.contains(operation) {
+            return Err(RemoteError::NotFound(operation.to_owned()));
+        }
+        state.calls.push(call);
+        Ok(())
+    }
+
+    fn read<T: Clone>(
+        &self,
+        operation: &'static str,
+        issue: &IssueRef,
+        data: impl FnOnce(&FakePullRequest) -> &Vec<T>,
+    ) -> Result<Vec<T>, RemoteError> {
+        let mut state = self.state();
+        if state.failing.contains(operation) {
+            return Err(RemoteError::NotFound(operation.to_owned()));
+        }
+        Ok(state
+            .pull_request(issue)
+            .map(|pull_request| data(pull_request).clone())
+            .unwrap_or_default())
+    }
+
+    fn update(&self, issue: &IssueRef, update: impl FnOnce(&mut FakePullRequest)) {
+        if let Some(pull_request) = self.state().pull_request(issue) {
+            update(pull_request);
+        }
+    }
+}
+
+pub(crate) fn user_json(login: &str) -> Value {
+    let url = format!("https://github.local/users/{login}");
+    json!({
+        "login": login,
+        "id": 1,
+        "node_id": login,
+        "avatar_url": url,
+        "gravatar_id": "",
+        "url": url,
+        "html_url": url,
+        "followers_url": url,
+        "following_url": url,
+        "gists_url": url,
+        "starred_url": url,
+        "subscriptions_url": url,
+        "organizations_url": url,
+        "repos_url": url,
+        "events_url": url,
+        "received_events_url": url,
+        "type": "User",
+        "site_admin": false,
+    })
+}
+
+impl GitHubAuthenticator for FakeGitHub {
+    type Next = FakeGitHub;
+    type Error = Infallible;
+
+    fn authenticate_app(_: Uri, _: AppId, _: EncodingKey) -> Result<Self::Next, Self::Error> {
+        Ok(FakeGitHub::default())
+    }
+}
+
+impl InstallationAuthenticator for FakeGitHub {
+    fn for_installation(&self, id: InstallationId) -> impl GitHubOperations {
+        self.state().installations.push(id.into_inner());
+        self.clone()
+    }
+
+    async fn installations(&self) -> Result<Vec<InstallationId>, RemoteError> {
+        let state = self.state();
+        if state.failing.contains("installations") {
+            return Err(RemoteError::NotFound("installations".to_owned()));
+        }
+        Ok(state
+            .installed
+            .iter()
+            .copied()
+            .map(InstallationId)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl GitHubOperations for FakeGitHub {
+    async fn add_assignees(
+        &self,
+        issue: &IssueRef,
+        assignees: &[String],
+    ) -> Result<(), RemoteError> {
+        self.record(
+            "add_assignees",
+            Call::AddAssignees(issue.number, assignees.to_vec()),
+        )?;
+        self.update(issue, |pull_request| {
+            pull_request.assignees.extend_from_slice(assignees)
+        });
+        Ok(())
+    }
+
+    async fn list_comments(&self, issue: &IssueRef) -> Result<Vec<IssueComment>, RemoteError> {
+        self.read("list_comments", issue, |pull_request| {
+            &pull_request.comments
+        })
+    }
+
+    async fn create_comment(
+        &self,
+        issue: &IssueRef,
+        body: &str,
+    ) -> Result<IssueComment, RemoteError> {
+        self.record(
+            "create_comment",
+            Call::CreateComment(issue.number, body.to_owned()),
+        )?;
+        let comment = IssueComment {
+            id: self.state().next_id(),
+            body: body.to_owned(),
+            author: BOT.to_owned(),
+        };
+        self.update(issue, |pull_request| {
+            pull_request.comments.push(comment.clone())
+        });
+        Ok(comment)
+    }
+
+    async fn update_comment(
+        &self,
+        issue: &IssueRef,
+        comment_id: u64,
+        body: &str,
+    ) -> Result<(), RemoteError> {
+        self.record(
+            "update_comment",
+            Call::UpdateComment(comment_id, body.to_owned()),
+        )?;
+        self.update(issue, |pull_request| {
+            let comment = pull_request
+                .comments
+                .iter_mut()
+                .find(|c| c.id == comment_id);
+            if let Some(comment) = comment {
+                comment.body = body.to_owned();
+            }
+        });
+        Ok(())
+    }
+
+    async fn delete_comment(&self, issue: &IssueRef, comment_id: u64) -> Result<(), RemoteError> {
+        self.record("delete_comment", Call::DeleteComment(comment_id))?;
+        self.update(issue, |pull_request| {
+            pull_request
+                .comments
+                .retain(|comment| comment.id != comment_id)
+        });
+        Ok(())
+    }
+
+    async fn add_labels(&self, issue: &IssueRef, labels: &[String]) -> Result<(), RemoteError> {
+        self.record("add_labels", Call::AddLabels(issue.number, labels.to_vec()))?;
+        self.update(issue, |pull_request| {
+            for label in labels {
+                if !pull_request.labels.contains(label) {
+                    pull_request.labels.push(label.clone());
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn remove_label(&self, issue: &IssueRef, label: &str) -> Result<(), RemoteError> {
+        self.record(
+            "remove_label",
+            Call::RemoveLabel(issue.number, label.to_owned()),
+        )?;
+        let mut state = self.state();
+        let Some(pull_request) = state.pull_request(issue) else {
+            return Ok(());
+        };
+        if !pull_request.labels.iter().any(|existing| existing == label) {
+            return Err(RemoteError::NotFound(format!("label {label}")));
+        }
+        pull_request.labels.retain(|existing| existing != label);
+        Ok(())
+    }
+
+    async fn replace_labels(&self, issue: &IssueRef, labels: &[String]) -> Result<(), RemoteError> {
+        self.record(
+            "replace_labels",
+            Call::ReplaceLabels(issue.number, labels.to_vec()),
+        )?;
+        self.update(issue, |pull_request| pull_request.labels = labels.to_vec());
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        issue: &IssueRef,
+        reviewers: &[String],
+        teams: &[String],
+    ) -> Result<(), RemoteError> {
+        self.record(
+            "request_reviewers",
+            Call::RequestReviewers(issue.number, reviewers.to_vec(), teams.to_vec()),
+        )?;
+        self.update(issue, |pull_request| {
+            pull_request
+                .requested_reviewers
+                .extend_from_slice(reviewers);
+            pull_request.requested_teams.extend_from_slice(teams);
+        });
+        Ok(())
+    }
+
+    async fn create_check_run(
+        &self,
+        _: &str,
+        _: &str,
+        check_run: &CheckRun,
+    ) -> Result<u64, RemoteError> {
+        self.record("create_check_run", Call::CreateCheckRun(check_run.clone()))?;
+        let mut state = self.state();
+        let id = state.next_id();
+        state.check_runs.push((id, check_run.clone()));
+        Ok(id)
+    }
+
+    async fn update_check_run(
+        &self,
+        _: &str,
+        _: &str,
+        check_run_id: u64,
+        check_run: &CheckRun,
+    ) -> Result<(), RemoteError> {
+        self.record(
+            "update_check_run",
+            Call::UpdateCheckRun(check_run_id, check_run.clone()),
+        )?;
+        let mut state = self.state();
+        match state
+            .check_runs
+            .iter_mut()
+            .find(|(id, _)| *id == check_run_id)
+        {
+            Some((_, existing)) => *existing = check_run.clone(),
+            None => return Err(RemoteError::NotFound(format!("check run {check_run_id}"))),
+        }
+        Ok(())
+    }
+
+    async fn find_check_run(
+        &self,
+        _: &str,
+        _: &str,
+        head_sha: &str,
+        name: &str,
+    ) -> Result<Option<u64>, RemoteError> {
+        Ok(self
+            .state()
+            .check_runs
+            .iter()
+            .rev()
+            .find(|(_, check_run)| check_run.head_sha == head_sha && check_run.name == name)
+            .map(|(id, _)| *id))
+    }
+
+    async fn merge(
+        &self,
+        issue: &IssueRef,
+        method: MergeMethod,
+        _: Option<&str>,
+        _: Option<&str>,
+    ) -> Result<(), RemoteError> {
+        self.record("merge", Call::Merge(issue.number, method))?;
+        self.update(issue, |pull_request| {
+            pull_request.merged = Some(method);
+            pull_request.closed = true;
+        });
+        Ok(())
+    }
+
+    async fn close(&self, issue: &IssueRef) -> Result<(), RemoteError> {
+        self.record("close", Call::Close(issue.number))?;
+        self.update(issue, |pull_request| pull_request.closed = true);
+        Ok(())
+    }
+
+    async fn list_files(&self, issue: &IssueRef) -> Result<Vec<ChangedFile>, RemoteError> {
+        self.read("list_files", issue, |pull_request| &pull_request.files)
+    }
+
+    async fn list_commits(&self, issue: &IssueRef) -> Result<Vec<Commit>, RemoteError> {
+        self.read("list_commits", issue, |pull_request| &pull_request.commits)
+    }
+
+    async fn get_pull_request(&self, issue: &IssueRef) -> Result<PullRequest, RemoteError> {
+        let known = {
+            let mut state = self.state();
+            if state.failing.contains("get_pull_request") {
+                return Err(RemoteError::NotFound("get_pull_request".to_owned()));
+            }
+            state.pull_request(issue).is_some()
+        };
+        if !known {
+            return Err(RemoteError::NotFound(format!(
+                "pull request {}",
+                issue.number
+            )));
+        }
+        let json = self.pull_request_json(&issue.owner, &issue.repo, issue.number);
+        Ok(serde_json::from_value(json).expect("valid pull request"))
+    }
+
+    async fn pull_requests_for_commit(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<u64>, RemoteError> {
+        let state = self.state();
+        if state.failing.contains("pull_requests_for_commit") {
+            return Err(RemoteError::NotFound("pull_requests_for_commit".to_owned()));
+        }
+        Ok(state
+            .pull_requests
+            .iter()
+            .filter(|pull_request| {
+                pull_request.owner == owner
+                    && pull_request.repo == repo
+                    && pull_request.head_sha == sha
+                    && !pull_request.closed
+            })
+            .map(|pull_request| pull_request.number)
+            .collect())
+    }
+
+    async fn search_issues(&self, query: &str) -> Result<Vec<Issue>, RemoteError> {
+        let found: Vec<(String, String, u64)> = {
+            let mut state = self.state();
+            if state.failing.contains("search_issues") {
+                return Err(RemoteError::NotFound("search_issues".to_owned()));
+            }
+            state.searches.push(query.to_owned());
+            let repository = query
+                .split(' ')
+                .find_map(|qualifier| qualifier.strip_prefix("repo:"))
+                .and_then(|repository| repository.split_once('/'))
+                .unwrap_or_default();
+            state
+                .pull_requests
+                .iter()
+                .filter(|pull_request| {
+                    (pull_request.owner.as_str(), pull_request.repo.as_str()) == repository
+                        && !pull_request.closed
+                })
+                .map(|pull_request| {
+                    let FakePullRequest {
+                        owner,
+                        repo,
+                        number,
+                        ..
+                    } = pull_request;
+                    (owner.clone(), repo.clone(), *number)
+                })
+                .collect()
+        };
+        Ok(found
+            .into_iter()
+            .map(|(owner, repo, number)| {
+                serde_json::from_value(self.issue_json(&owner, &repo, number)).expect("valid issue")
+            })
+            .collect())
+    }
+
+    async fn installation_repositories(&self) -> Result<Vec<Repository>, RemoteError> {
+        let repositories: Vec<(String, String)> = {
+            let state = self.state();
+            if state.failing.contains("installation_repositories") {
+                return Err(RemoteError::NotFound(
+                    "installation_repositories".to_owned(),
+                ));
+            }
+            state
+                .repositories
+                .iter()
+                .map(|repository| (repository.owner.clone(), repository.name.clone()))
+                .collect()
+        };
+        Ok(repositories
+            .into_iter()
+            .map(|(owner, name)| {
+                serde_json::from_value(self.repository_json(&owner, &name))
+                    .expect("valid repository")
+            })
+            .collect())
+    }
+
+    async fn team_members(&self, org: &str, team_slug: &str) -> Result<Vec<String>, RemoteError> {
+        let state = self.state();
+        if state.failing.contains("team_members") {
+            return Err(RemoteError::NotFound("team_members".to_owned()));
+        }
+        state
+            .teams
+            .get(&format!("{org}/{team_slug}"))
+            .cloned()
+            .ok_or_else(|| RemoteError::NotFound(format!("{org}/{team_slug}")))
+    }
+
+    async fn list_reviews(&self, issue: &IssueRef) -> Result<Vec<PullRequestReview>, RemoteError> {
+        self.read("list_reviews", issue, |pull_request| &pull_request.reviews)
+    }
+
+    async fn project_items(&self, issue: &IssueRef) -> Result<Vec<ProjectItem>, RemoteError> {
+        self.read("project_items", issue, |pull_request| {
+            &pull_request.projects
+        })
+    }
+
+    async fn commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        reference: &str,
+    ) -> Result<String, RemoteError> {
+        let mut state = self.state();
+        if state.failing.contains("commit_sha") {
+            return Err(RemoteError::NotFound("commit_sha".to_owned()));
+        }
+        match state.repository(owner, repo) {
+            Some(repository)
+                if reference == "HEAD"
+                    || reference == repository.default_branch
+                    || reference == repository.head_sha =>
+            {
+                Ok(repository.head_sha.clone())
+            }
+            _ => Err(RemoteError::NotFound(format!("{owner}/{repo}@{reference}"))),
+        }
+    }
+
+    async fn tree(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<TreeEntry>, RemoteError> {
+        let mut state = self.state();
+        if state.failing.contains("tree") {
+            return Err(RemoteError::NotFound("tree".to_owned()));
+        }
+        let repository = state
+            .repository(owner, repo)
+            .ok_or_else(|| RemoteError::NotFound(format!("{owner}/{repo}")))?;
+        let mut entries: Vec<_> = repository
+            .snapshots
+            .get(sha)
+            .unwrap_or(&repository.files)
+            .iter()
+            .map(|(path, content)| TreeEntry {
+                path: path.clone(),
+                size: content.len() as u64,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(entries)
+    }
+
+    async fn file_content(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+        reference: &str,
+    ) -> Result<Option<String>, RemoteError> {
+        let mut state = self.state();
+        if state.failing.contains("file_content") {
+            return Err(RemoteError::NotFound("file_content".to_owned()));
+        }
+        state
+            .file_reads
+            .push((path.to_owned(), reference.to_owned()));
+        Ok(state.repository(owner, repo).and_then(|repository| {
+            repository
+                .snapshots
+                .get(reference)
+                .unwrap_or(&repository.files)
+                .get(path)
+                .cloned()
+        }))
+    }
+}
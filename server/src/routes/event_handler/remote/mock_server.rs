@@ -0,0 +1,824 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+};
+
+use axum::{
+    extract::{Path, Query, Request, State},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, patch, post},
+    Json, Router,
+};
+use hyper::{header, StatusCode, Uri};
+use jsonwebtoken::EncodingKey;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use super::{
+    fake::user_json, ChangedFile, Commit, FileStatus, IssueComment, ProjectItem, PullRequestReview,
+    PullRequestReviewState,
+};
+
+/// A request received by the mock server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RecordedRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) authorization: Option<String>,
+}
+
+/// The GitHub data served by the mock server, keyed by pull request or issue number.
+///
+/// The owner and repository of a request are only recorded, not used for lookups.
+#[derive(Debug, Default)]
+pub(crate) struct MockState {
+    pub(crate) files: HashMap<u64, Vec<ChangedFile>>,
+    pub(crate) commits: HashMap<u64, Vec<Commit>>,
+    pub(crate) reviews: HashMap<u64, Vec<PullRequestReview>>,
+    pub(crate) comments: HashMap<u64, Vec<IssueComment>>,
+    pub(crate) labels: HashMap<u64, Vec<String>>,
+    pub(crate) assignees: HashMap<u64, Vec<String>>,
+    pub(crate) requested_reviewers: HashMap<u64, Value>,
+    pub(crate) merged: HashMap<u64, Value>,
+    pub(crate) closed: HashSet<u64>,
+    /// Project items served through GraphQL, other numbers don't resolve.
+    pub(crate) projects: HashMap<u64, Vec<ProjectItem>>,
+    /// Logins of the members of organization teams by `org/team_slug`.
+    pub(crate) teams: HashMap<String, Vec<String>>,
+    /// Commit shas by the branch, tag or sha resolving to them.
+    pub(crate) references: HashMap<String, String>,
+    /// Repository file contents by path, served for any reference.
+    pub(crate) contents: HashMap<String, String>,
+    /// Request bodies of created or updated check runs by id.
+    pub(crate) check_runs: Vec<(u64, Value)>,
+    /// Installations access tokens were issued for.
+    pub(crate) installation_tokens: Vec<u64>,
+    pub(crate) requests: Vec<RecordedRequest>,
+    /// Paths answering with the status and message instead of being served.
+    pub(crate) errors: HashMap<String, (StatusCode, String)>,
+    /// Caps `per_page` to exercise pagination.
+    pub(crate) max_page_size: Option<usize>,
+    next_id: u64,
+}
+
+impl MockState {
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+#[derive(Clone)]
+struct ServerState {
+    base: String,
+    data: Arc<Mutex<MockState>>,
+}
+
+impl ServerState {
+    fn data(&self) -> MutexGuard<'_, MockState> {
+        self.data.lock().expect("mock state lock poisoned")
+    }
+}
+
+/// A local stand-in for the GitHub REST API, stopped when dropped.
+pub(crate) struct MockGitHubServer {
+    uri: Uri,
+    data: Arc<Mutex<MockState>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockGitHubServer {
+    pub(crate) async fn start() -> Self {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .expect("failed to bind the mock server");
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let data = Arc::new(Mutex::new(MockState::default()));
+        let state = ServerState {
+            base: base.clone(),
+            data: data.clone(),
+        };
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, routes(state)).await.unwrap();
+        });
+        Self {
+            uri: base.parse().unwrap(),
+            data,
+            handle,
+        }
+    }
+
+    pub(crate) fn uri(&self) -> Uri {
+        self.uri.clone()
+    }
+
+    pub(crate) fn state(&self) -> MutexGuard<'_, MockState> {
+        self.data.lock().expect("mock state lock poisoned")
+    }
+
+    /// Answers requests to `path` with a GitHub error.
+    pub(crate) fn fail(&self, path: &str, status: StatusCode, message: &str) {
+        self.state()
+            .errors
+            .insert(path.to_owned(), (status, message.to_owned()));
+    }
+}
+
+impl Drop for MockGitHubServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// An app key accepted by the JWT signer, generating RSA keys is slow in debug builds.
+pub(crate) fn app_key() -> EncodingKey {
+    static KEY: OnceLock<EncodingKey> = OnceLock::new();
+    KEY.get_or_init(|| {
+        use rand::SeedableRng;
+        use rsa::pkcs8::EncodePrivateKey;
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let key = rsa::RsaPrivateKey::new(&mut rng, 2048).expect("failed to generate a key");
+        let pem = key.to_pkcs8_pem(rsa::pkcs8::LineEnding::LF).unwrap();
+        EncodingKey::from_rsa_pem(pem.as_bytes()).unwrap()
+    })
+    .clone()
+}
+
+fn routes(state: ServerState) -> Router {
+    let repo = "/repos/:owner/:repo";
+    Router::new()
+        .route(
+            "/app/installations/:id/access_tokens",
+            post(installation_token),
+        )
+        .route(&format!("{repo}/pulls/:number/files"), get(list_files))
+        .route(&format!("{repo}/pulls/:number/commits"), get(list_commits))
+        .route(&format!("{repo}/pulls/:number/reviews"), get(list_reviews))
+        .route(
+            &format!("{repo}/pulls/:number/requested_reviewers"),
+            post(request_reviewers),
+        )
+        .route(
+            &format!("{repo}/pulls/:number/merge"),
+            axum::routing::put(merge),
+        )
+        .route(&format!("{repo}/issues/:number"), patch(update_issue))
+        .route(
+            &format!("{repo}/issues/:number/comments"),
+            get(list_comments).post(create_comment),
+        )
+        .route(
+            &format!("{repo}/issues/comments/:id"),
+            patch(update_comment).delete(delete_comment),
+        )
+        .route(
+            &format!("{repo}/issues/:number/labels"),
+            post(add_labels).put(replace_labels),
+        )
+        .route(
+            &format!("{repo}/issues/:number/labels/:name"),
+            delete(remove_label),
+        )
+        .route(
+            &format!("{repo}/issues/:number/assignees"),
+            post(add_assignees),
+        )
+        .route("/orgs/:org/teams/:slug/members", get(list_team_members))
+        .route(&format!("{repo}/commits/:reference"), get(get_commit))
+        .route(&format!("{repo}/contents/*path"), get(get_content))
+        .route(&format!("{repo}/git/trees/:sha"), get(get_tree))
+        .route(&format!("{repo}/check-runs"), post(create_check_run))
+        .route(&format!("{repo}/check-runs/:id"), patch(update_check_run))
+        .route(
+            &format!("{repo}/commits/:sha/check-runs"),
+            get(list_check_runs),
+        )
+        .route("/graphql", post(graphql))
+        .layer(middleware::from_fn_with_state(state.clone(), record))
+        .with_state(state)
+}
+
+/// Records every request and answers with the configured errors.
+async fn record(State(state): State<ServerState>, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_owned();
+    let error = {
+        let mut data = state.data();
+        data.requests.push(RecordedRequest {
+            method: request.method().to_string(),
+            path: request
+                .uri()
+                .path_and_query()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| path.clone()),
+            authorization: request
+                .headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned),
+        });
+        data.errors.get(&path).cloned()
+    };
+    match error {
+        Some((status, message)) => (
+            status,
+            Json(json!({
+                "message": message,
+                "documentation_url": "https://docs.github.com/rest",
+            })),
+        )
+            .into_response(),
+        None => next.run(request).await,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PageQuery {
+    per_page: Option<usize>,
+    page: Option<usize>,
+}
+
+/// Serves a page of `items` with the `Link` header GitHub uses for pagination.
+fn paginate(state: &ServerState, path: &str, query: &PageQuery, items: Vec<Value>) -> Response {
+    let max_page_size = state.data().max_page_size.unwrap_or(100);
+    let per_page = query.per_page.unwrap_or(30).min(max_page_size).max(1);
+    let page = query.page.unwrap_or(1).max(1);
+    let pages = items.len().div_ceil(per_page).max(1);
+    let items: Vec<_> = items
+        .into_iter()
+        .skip((page - 1) * per_page)
+        .take(per_page)
+        .collect();
+    let link = |page: usize, rel: &str| {
+        format!(
+            "<{}{path}?per_page={per_page}&page={page}>; rel=\"{rel}\"",
+            state.base
+        )
+    };
+    let mut links = vec![link(1, "first"), link(pages, "last")];
+    if page < pages {
+        links.push(link(page + 1, "next"));
+    }
+    if page > 1 {
+        links.push(link(page - 1, "prev"));
+    }
+    ([(header::LINK, links.join(", "))], Json(items)).into_response()
+}
+
+async fn installation_token(
+    State(state): State<ServerState>,
+    Path(id): Path<u64>,
+) -> impl IntoResponse {
+    state.data().installation_tokens.push(id);
+    (
+        StatusCode::CREATED,
+        Json(json!({
+            "token": format!("installation-token-{id}"),
+            "expires_at": "2099-01-01T00:00:00Z",
+            "permissions": {},
+        })),
+    )
+}
+
+async fn list_files(
+    State(state): State<ServerState>,
+    Path((owner, repo, number)): Path<(String, String, u64)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let files = state.data().files.get(&number).cloned().unwrap_or_default();
+    let items = files.iter().map(file_json).collect();
+    let path = format!("/repos/{owner}/{repo}/pulls/{number}/files");
+    paginate(&state, &path, &query, items)
+}
+
+async fn list_commits(
+    State(state): State<ServerState>,
+    Path((owner, repo, number)): Path<(String, String, u64)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let commits = state
+        .data()
+        .commits
+        .get(&number)
+        .cloned()
+        .unwrap_or_default();
+    let items = commits.iter().map(commit_json).collect();
+    let path = format!("/repos/{owner}/{repo}/pulls/{number}/commits");
+    paginate(&state, &path, &query, items)
+}
+
+async fn list_reviews(
+    State(state): State<ServerState>,
+    Path((owner, repo, number)): Path<(String, String, u64)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let reviews = state
+        .data()
+        .reviews
+        .get(&number)
+        .cloned()
+        .unwrap_or_default();
+    let items = reviews.iter().map(review_json).collect();
+    let path = format!("/repos/{owner}/{repo}/pulls/{number}/reviews");
+    paginate(&state, &path, &query, items)
+}
+
+async fn list_team_members(
+    State(state): State<ServerState>,
+    Path((org, slug)): Path<(String, String)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let Some(members) = state.data().teams.get(&format!("{org}/{slug}")).cloned() else {
+        return not_found();
+    };
+    let items = members.iter().map(|login| user_json(login)).collect();
+    let path = format!("/orgs/{org}/teams/{slug}/members");
+    paginate(&state, &path, &query, items)
+}
+
+async fn request_reviewers(
+    State(state): State<ServerState>,
+    Path((_, _, number)): Path<(String, String, u64)>,
+    Json(body): Json<Value>,
+) -> impl IntoResponse {
+    state.data().requested_reviewers.insert(number, body);
+    (StatusCode::CREATED, Json(json!({ "number": number })))
+}
+
+async fn merge(
+    State(state): State<ServerState>,
+    Path((_, _, number)): Path<(String, String, u64)>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    state.data().merged.insert(number, body);
+    Json(json!({ "sha": "merged", "merged": true, "message": "Pull Request successfully merged" }))
+}
+
+async fn update_issue(
+    State(state): State<ServerState>,
+    Path((owner, repo, number)): Path<(String, String, u64)>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    let mut data = state.data();
+    if body["state"] == "closed" {
+        data.closed.insert(number);
+    }
+    Json(issue_json(&data, &owner, &repo, number))
+}
+
+async fn list_comments(
+    State(state): State<ServerState>,
+    Path((owner, repo, number)): Path<(String, String, u64)>,
+    Query(query): Query<PageQuery>,
+) -> Response {
+    let comments = state
+        .data()
+        .comments
+        .get(&number)
+        .cloned()
+        .unwrap_or_default();
+    let items = comments
+        .iter()
+        .map(|comment| comment_json(&state.base, comment))
+        .collect();
+    let path = format!("/repos/{owner}/{repo}/issues/{number}/comments");
+    paginate(&state, &path, &query, items)
+}
+
+async fn create_comment(
+    State(state): State<ServerState>,
+    Path((_, _, number)): Path<(String, String, u64)>,
+    Json(body): Json<Value>,
+) -> impl IntoResponse {
+    let mut data = state.data();
+    let comment = IssueComment {
+        id: data.next_id(),
+        body: body["body"].as_str().unwrap_or_default().to_owned(),
+        author: "source-warden[bot]".to_owned(),
+    };
+    data.comments
+        .entry(number)
+        .or_default()
+        .push(comment.clone());
+    (
+        StatusCode::CREATED,
+        Json(comment_json(&state.base, &comment)),
+    )
+}
+
+async fn update_comment(
+    State(state): State<ServerState>,
+    Path((_, _, id)): Path<(String, String, u64)>,
+    Json(body): Json<Value>,
+) -> Response {
+    let mut data = state.data();
+    let comment = data
+        .comments
+        .values_mut()
+        .flatten()
+        .find(|comment| comment.id == id);
+    match comment {
+        Some(comment) => {
+            comment.body = body["body"].as_str().unwrap_or_default().to_owned();
+            Json(comment_json(&state.base, comment)).into_response()
+        }
+        None => not_found(),
+    }
+}
+
+async fn delete_comment(
+    State(state): State<ServerState>,
+    Path((_, _, id)): Path<(String, String, u64)>,
+) -> StatusCode {
+    for comments in state.data().comments.values_mut() {
+        comments.retain(|comment| comment.id != id);
+    }
+    StatusCode::NO_CONTENT
+}
+
+#[derive(Debug, Deserialize)]
+struct Labels {
+    labels: Vec<String>,
+}
+
+async fn add_labels(
+    State(state): State<ServerState>,
+    Path((_, _, number)): Path<(String, String, u64)>,
+    Json(body): Json<Labels>,
+) -> Json<Value> {
+    let mut data = state.data();
+    let labels = data.labels.entry(number).or_default();
+    for label in body.labels {
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    Json(labels_json(labels))
+}
+
+async fn replace_labels(
+    State(state): State<ServerState>,
+    Path((_, _, number)): Path<(String, String, u64)>,
+    Json(body): Json<Labels>,
+) -> Json<Value> {
+    let mut data = state.data();
+    data.labels.insert(number, body.labels.clone());
+    Json(labels_json(&body.labels))
+}
+
+async fn remove_label(
+    State(state): State<ServerState>,
+    Path((_, _, number, name)): Path<(String, String, u64, String)>,
+) -> Json<Value> {
+    let mut data = state.data();
+    let labels = data.labels.entry(number).or_default();
+    labels.retain(|label| *label != name);
+    Json(labels_json(labels))
+}
+
+#[derive(Debug, Deserialize)]
+struct Assignees {
+    assignees: Vec<String>,
+}
+
+async fn add_assignees(
+    State(state): State<ServerState>,
+    Path((owner, repo, number)): Path<(String, String, u64)>,
+    Json(body): Json<Assignees>,
+) -> impl IntoResponse {
+    let mut data = state.data();
+    data.assignees
+        .entry(number)
+        .or_default()
+        .extend(body.assignees);
+    (
+        StatusCode::CREATED,
+        Json(issue_json(&data, &owner, &repo, number)),
+    )
+}
+
+async fn create_check_run(
+    State(state): State<ServerState>,
+    Json(body): Json<Value>,
+) -> impl IntoResponse {
+    let mut data = state.data();
+    let id = data.next_id();
+    data.check_runs.push((id, body.clone()));
+    (
+        StatusCode::CREATED,
+        Json(check_run_json(&state.base, id, &body)),
+    )
+}
+
+async fn update_check_run(
+    State(state): State<ServerState>,
+    Path((_, _, id)): Path<(String, String, u64)>,
+    Json(body): Json<Value>,
+) -> Response {
+    let mut data = state.data();
+    let Some((_, existing)) = data.check_runs.iter_mut().find(|(run, _)| *run == id) else {
+        return not_found();
+    };
+    for (key, value) in body.as_object().into_iter().flatten() {
+        existing[key] = value.clone();
+    }
+    Json(check_run_json(&state.base, id, existing)).into_response()
+}
+
+/// The check runs on the commit with the `check_name`, the latest first.
+async fn list_check_runs(
+    State(state): State<ServerState>,
+    Path((_, _, sha)): Path<(String, String, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    let name = query.get("check_name");
+    let check_runs: Vec<_> = state
+        .data()
+        .check_runs
+        .iter()
+        .rev()
+        .filter(|(_, body)| body["head_sha"] == sha.as_str())
+        .filter(|(_, body)| name.map_or(true, |name| body["name"] == name.as_str()))
+        .map(|(id, body)| check_run_json(&state.base, *id, body))
+        .collect();
+    Json(json!({ "total_count": check_runs.len(), "check_runs": check_runs }))
+}
+
+async fn get_commit(
+    State(state): State<ServerState>,
+    Path((_, _, reference)): Path<(String, String, String)>,
+) -> Response {
+    let Some(sha) = state.data().references.get(&reference).cloned() else {
+        return not_found();
+    };
+    Json(commit_json(&Commit {
+        sha,
+        message: "Head commit".to_owned(),
+        author: None,
+        committed_at: None,
+        parents: 1,
+    }))
+    .into_response()
+}
+
+/// Serves the contents as the tree of any commit, with a directory per parent path.
+async fn get_tree(
+    State(state): State<ServerState>,
+    Path((_, _, sha)): Path<(String, String, String)>,
+) -> Json<Value> {
+    let contents = state.data().contents.clone();
+    let mut paths: Vec<_> = contents.keys().collect();
+    paths.sort();
+    let mut tree = vec![];
+    for path in paths {
+        if let Some((directory, _)) = path.rsplit_once('/') {
+            tree.push(json!({ "path": directory, "mode": "040000", "type": "tree", "sha": "0" }));
+        }
+        tree.push(json!({
+            "path": path,
+            "mode": "100644",
+            "type": "blob",
+            "sha": "0",
+            "size": contents[path].len(),
+        }));
+    }
+    Json(json!({ "sha": sha, "tree": tree, "truncated": false }))
+}
+
+async fn get_content(
+    State(state): State<ServerState>,
+    Path((owner, repo, path)): Path<(String, String, String)>,
+) -> Response {
+    use base64::Engine;
+
+    let Some(content) = state.data().contents.get(&path).cloned() else {
+        return not_found();
+    };
+    let url = format!("{}/repos/{owner}/{repo}/contents/{path}", state.base);
+    // GitHub wraps the encoded content at 60 characters
+    let encoded = base64::prelude::BASE64_STANDARD.encode(&content);
+    let encoded: Vec<_> = encoded
+        .as_bytes()
+        .chunks(60)
+        .map(|line| String::from_utf8_lossy(line).into_owned())
+        .collect();
+    Json(json!({
+        "type": "file",
+        "encoding": "base64",
+        "size": content.len(),
+        "name": path.rsplit('/').next(),
+        "path": path,
+        "content": encoded.join("\n"),
+        "sha": "0000000",
+        "url": url,
+        "git_url": url,
+        "html_url": url,
+        "download_url": url,
+        "_links": { "git": url, "self": url, "html": url },
+    }))
+    .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlRequest {
+    variables: GraphQlVariables,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlVariables {
+    number: u64,
+}
+
+/// Answers the project items query, the only GraphQL query the application sends.
+async fn graphql(
+    State(state): State<ServerState>,
+    Json(request): Json<GraphQlRequest>,
+) -> Json<Value> {
+    let number = request.variables.number;
+    let Some(items) = state.data().projects.get(&number).cloned() else {
+        return Json(json!({
+            "data": { "repository": { "issueOrPullRequest": null } },
+            "errors": [{
+                "type": "NOT_FOUND",
+                "path": ["repository", "issueOrPullRequest"],
+                "message": format!("Could not resolve to an issue or pull request with the number of {number}."),
+            }],
+        }));
+    };
+    let nodes: Vec<_> = items
+        .iter()
+        .map(|item| {
+            json!({
+                "project": { "title": item.title },
+                "fieldValueByName": item.status.as_ref().map(|name| json!({ "name": name })),
+            })
+        })
+        .collect();
+    Json(json!({
+        "data": {
+            "repository": { "issueOrPullRequest": { "projectItems": { "nodes": nodes } } },
+        },
+    }))
+}
+
+fn not_found() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(
+            json!({ "message": "Not Found", "documentation_url": "https://docs.github.com/rest" }),
+        ),
+    )
+        .into_response()
+}
+
+fn labels_json(labels: &[String]) -> Value {
+    labels
+        .iter()
+        .enumerate()
+        .map(|(id, name)| {
+            json!({
+                "id": id,
+                "node_id": name,
+                "url": format!("https://github.local/labels/{name}"),
+                "name": name,
+                "color": "ffffff",
+                "default": false,
+            })
+        })
+        .collect()
+}
+
+fn issue_json(data: &MockState, owner: &str, repo: &str, number: u64) -> Value {
+    let url = format!("https://github.local/repos/{owner}/{repo}/issues/{number}");
+    let labels = data.labels.get(&number).cloned().unwrap_or_default();
+    let assignees = data.assignees.get(&number).cloned().unwrap_or_default();
+    json!({
+        "id": number,
+        "node_id": number.to_string(),
+        "url": url,
+        "repository_url": format!("https://github.local/repos/{owner}/{repo}"),
+        "labels_url": format!("{url}/labels"),
+        "comments_url": format!("{url}/comments"),
+        "events_url": format!("{url}/events"),
+        "html_url": url,
+        "number": number,
+        "state": if data.closed.contains(&number) { "closed" } else { "open" },
+        "title": format!("Issue {number}"),
+        "user": user_json("octocat"),
+        "labels": labels_json(&labels),
+        "assignees": assignees.iter().map(|login| user_json(login)).collect::<Vec<_>>(),
+        "author_association": "OWNER",
+        "locked": false,
+        "comments": 0,
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z",
+    })
+}
+
+fn comment_json(base: &str, comment: &IssueComment) -> Value {
+    let url = format!("{base}/comments/{}", comment.id);
+    json!({
+        "id": comment.id,
+        "node_id": comment.id.to_string(),
+        "url": url,
+        "html_url": url,
+        "body": comment.body,
+        "author_association": "NONE",
+        "user": user_json(&comment.author),
+        "created_at": "2024-01-01T00:00:00Z",
+    })
+}
+
+fn check_run_json(base: &str, id: u64, body: &Value) -> Value {
+    let output = &body["output"];
+    json!({
+        "id": id,
+        "node_id": id.to_string(),
+        "head_sha": body["head_sha"],
+        "url": format!("{base}/check-runs/{id}"),
+        "name": body["name"],
+        "conclusion": body["conclusion"],
+        "output": {
+            "title": output["title"],
+            "summary": output["summary"],
+            "text": output["text"],
+            "annotations_count": 0,
+            "annotations_url": format!("{base}/check-runs/{id}/annotations"),
+        },
+        "pull_requests": [],
+    })
+}
+
+fn file_json(file: &ChangedFile) -> Value {
+    let status = match file.status {
+        FileStatus::Added => "added",
+        FileStatus::Modified => "modified",
+        FileStatus::Removed => "removed",
+        FileStatus::Renamed => "renamed",
+        FileStatus::Copied => "copied",
+        FileStatus::Changed => "changed",
+        FileStatus::Unchanged => "unchanged",
+    };
+    let url = format!("https://github.local/files/{}", file.filename);
+    json!({
+        "sha": "0000000",
+        "filename": file.filename,
+        "previous_filename": file.previous_filename,
+        "status": status,
+        "additions": file.additions,
+        "deletions": file.deletions,
+        "changes": file.additions + file.deletions,
+        "blob_url": url,
+        "raw_url": url,
+        "contents_url": url,
+        "patch": file.patch,
+    })
+}
+
+fn commit_json(commit: &Commit) -> Value {
+    let url = format!("https://github.local/commits/{}", commit.sha);
+    let signature = json!({
+        "name": commit.author.clone().unwrap_or_default(),
+        "email": "author@github.local",
+        "date": commit.committed_at,
+    });
+    json!({
+        "url": url,
+        "sha": commit.sha,
+        "node_id": commit.sha,
+        "html_url": url,
+        "comments_url": format!("{url}/comments"),
+        "commit": {
+            "url": url,
+            "author": signature,
+            "committer": signature,
+            "message": commit.message,
+            "comment_count": 0,
+            "tree": { "sha": commit.sha, "url": url },
+        },
+        "author": commit.author.as_deref().map(user_json),
+        "committer": null,
+        "parents": (0..commit.parents).map(|parent| json!({ "sha": parent.to_string() })).collect::<Vec<_>>(),
+    })
+}
+
+fn review_json(review: &PullRequestReview) -> Value {
+    let state = match review.state {
+        PullRequestReviewState::Approved => "APPROVED",
+        PullRequestReviewState::ChangesRequested => "CHANGES_REQUESTED",
+        PullRequestReviewState::Commented => "COMMENTED",
+        PullRequestReviewState::Dismissed => "DISMISSED",
+        PullRequestReviewState::Pending => "PENDING",
+    };
+    json!({
+        "id": review.id,
+        "node_id": review.id.to_string(),
+        "html_url": format!("https://github.local/reviews/{}", review.id),
+        "user": review.reviewer.as_deref().map(user_json),
+        "state": state,
+        "submitted_at": review.submitted_at,
+    })
+}
@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use octocrab::models::{webhook_events::WebhookEvent, InstallationId, Repository};
+use serde_json::json;
+use tokio::time::{self, Instant, MissedTickBehavior};
+
+use super::{
+    configuration::RepositoryConfigurations, dispatch::EventDescriptor, jira::JiraIssues,
+    process_event, remote::GitHubOperations, teams::TeamMemberships, InstallationAuthenticator,
+};
+
+/// How often every installed repository receives a `schedule.repository` event.
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Emits a synthetic `schedule.repository` event for every installed repository once per
+/// interval, GitHub has no such event. The first run is one interval after the start.
+pub(crate) async fn run<C: InstallationAuthenticator>(
+    client: C,
+    configurations: RepositoryConfigurations,
+    teams: TeamMemberships,
+    jira: JiraIssues,
+) {
+    let mut interval = time::interval_at(Instant::now() + SCHEDULE_INTERVAL, SCHEDULE_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        schedule_repositories(&client, &configurations, &teams, &jira).await;
+    }
+}
+
+/// Processes a `schedule.repository` event for each repository of each installation.
+pub(crate) async fn schedule_repositories<C: InstallationAuthenticator>(
+    client: &C,
+    configurations: &RepositoryConfigurations,
+    teams: &TeamMemberships,
+    jira: &JiraIssues,
+) {
+    let installations = match client.installations().await {
+        Ok(installations) => installations,
+        Err(error) => {
+            tracing::warn!(%error, "failed to list the installations");
+            return;
+        }
+    };
+    let descriptor = EventDescriptor {
+        name: "schedule".to_owned(),
+        action: Some("repository".to_owned()),
+    };
+    for id in installations {
+        let installation = client.for_installation(id);
+        let repositories = match installation.installation_repositories().await {
+            Ok(repositories) => repositories,
+            Err(error) => {
+                tracing::warn!(installation = id.0, %error, "failed to list the repositories");
+                continue;
+            }
+        };
+        for repository in repositories {
+            match schedule_event(id, &repository) {
+                Ok(event) => {
+                    process_event(
+                        &installation,
+                        configurations,
+                        teams,
+                        jira,
+                        &event,
+                        &descriptor,
+                        &repository,
+                    )
+                    .await
+                }
+                Err(error) => {
+                    tracing::warn!(repository = repository.id.0, %error, "failed to build the event")
+                }
+            }
+        }
+    }
+}
+
+/// The event is parsed like a webhook so that rules see the repository as usual.
+fn schedule_event(
+    installation: InstallationId,
+    repository: &Repository,
+) -> Result<WebhookEvent, serde_json::Error> {
+    let body = json!({
+        "schedule": "repository",
+        "workflow": "",
+        "repository": repository,
+        "installation": { "id": installation, "node_id": installation.to_string() },
+    });
+    WebhookEvent::try_from_header_and_body("schedule", &serde_json::to_vec(&body)?)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::routes::event_handler::{
+        configuration::{RepositoryConfigurations, CONFIGURATION_PATH},
+        jira::JiraIssues,
+        remote::fake::{Call, FakeGitHub, FakePullRequest},
+        teams::TeamMemberships,
+    };
+
+    use super::schedule_repositories;
+
+    #[tokio::test]
+    async fn applies_stale_rules_to_the_found_items() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.add_repository("owner", "other");
+        for number in [4, 9] {
+            github.add_pull_request(FakePullRequest {
+                owner: "owner".to_owned(),
+                repo: "repo".to_owned(),
+                number,
+                title: "Old feature".to_owned(),
+                author: "octocat".to_owned(),
+                ..Default::default()
+            });
+        }
+        github.commit_file(
+            "owner",
+            "repo",
+            CONFIGURATION_PATH,
+            Some(
+                r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        days: 30
+        type: pull_request
+    pass:
+      - do: labels
+        add: stale
+"#,
+            ),
+        );
+        github.state().installed.push(42);
+
+        schedule_repositories(
+            &github,
+            &RepositoryConfigurations::default(),
+            &TeamMemberships::default(),
+            &JiraIssues::default(),
+        )
+        .await;
+
+        assert_eq!(github.state().installations, [42]);
+        let searches = github.state().searches.clone();
+        assert_eq!(searches.len(), 1);
+        assert!(searches[0].starts_with("repo:owner/repo is:open updated:<"));
+        assert!(searches[0].ends_with(" is:pr"));
+        assert_eq!(
+            github.calls(),
+            [
+                Call::AddLabels(4, vec!["stale".to_owned()]),
+                Call::AddLabels(9, vec!["stale".to_owned()]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_pull_request_actions_to_stale_pull_requests() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.add_pull_request(FakePullRequest {
+            owner: "owner".to_owned(),
+            repo: "repo".to_owned(),
+            number: 4,
+            title: "Old feature".to_owned(),
+            author: "octocat".to_owned(),
+            ..Default::default()
+        });
+        github.commit_file(
+            "owner",
+            "repo",
+            CONFIGURATION_PATH,
+            Some(
+                r#"
+version: 2
+mergeable:
+  - when: schedule.repository
+    validate:
+      - do: stale
+        days: 30
+        type: pull_request
+    pass:
+      - do: request_review
+        reviewers: [maintainer]
+"#,
+            ),
+        );
+        github.state().installed.push(42);
+
+        schedule_repositories(
+            &github,
+            &RepositoryConfigurations::default(),
+            &TeamMemberships::default(),
+            &JiraIssues::default(),
+        )
+        .await;
+
+        assert_eq!(
+            github.calls(),
+            [Call::RequestReviewers(
+                4,
+                vec!["maintainer".to_owned()],
+                vec![]
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_installations_that_fail() {
+        let github = FakeGitHub::default();
+        github.add_repository("owner", "repo");
+        github.state().installed.push(42);
+        github.state().failing.insert("installation_repositories");
+
+        schedule_repositories(
+            &github,
+            &RepositoryConfigurations::default(),
+            &TeamMemberships::default(),
+            &JiraIssues::default(),
+        )
+        .await;
+
+        assert!(github.state().searches.is_empty());
+        assert!(github.calls().is_empty());
+    }
+}
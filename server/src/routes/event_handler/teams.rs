@@ -0,0 +1,82 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use super::remote::{GitHubOperations, RemoteError};
+
+/// How long fetched members are used, membership changes don't reach the application.
+const MEMBERS_TTL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug)]
+struct CachedMembers {
+    fetched_at: Instant,
+    members: Vec<String>,
+}
+
+/// The members of the teams rules refer to, shared by the events of all installations.
+///
+/// Teams belong to the organization the application is installed on, so `org/team_slug`
+/// identifies them across installations.
+#[derive(Clone, Default)]
+pub struct TeamMemberships(Arc<RwLock<HashMap<String, CachedMembers>>>);
+
+impl TeamMemberships {
+    /// Returns the logins of the members of the team, fetching them through the installation
+    /// client unless cached recently. Failures aren't cached.
+    pub(crate) async fn members(
+        &self,
+        client: &impl GitHubOperations,
+        org: &str,
+        team_slug: &str,
+    ) -> Result<Vec<String>, RemoteError> {
+        let team = format!("{org}/{team_slug}");
+        if let Some(cached) = self.0.read().expect("team lock poisoned").get(&team) {
+            if cached.fetched_at.elapsed() < MEMBERS_TTL {
+                return Ok(cached.members.clone());
+            }
+        }
+        let members = client.team_members(org, team_slug).await?;
+        self.0.write().expect("team lock poisoned").insert(
+            team,
+            CachedMembers {
+                fetched_at: Instant::now(),
+                members: members.clone(),
+            },
+        );
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::routes::event_handler::remote::fake::FakeGitHub;
+
+    use super::TeamMemberships;
+
+    #[tokio::test]
+    async fn caches_the_members_of_teams() {
+        let github = FakeGitHub::default();
+        let teams = TeamMemberships::default();
+
+        assert!(teams.members(&github, "org", "core").await.is_err());
+        github
+            .state()
+            .teams
+            .insert("org/core".to_owned(), vec!["octocat".to_owned()]);
+        assert_eq!(
+            teams.members(&github, "org", "core").await.unwrap(),
+            ["octocat"]
+        );
+
+        github
+            .state()
+            .teams
+            .insert("org/core".to_owned(), vec!["monalisa".to_owned()]);
+        assert_eq!(
+            teams.members(&github, "org", "core").await.unwrap(),
+            ["octocat"]
+        );
+    }
+}